@@ -5,15 +5,19 @@ use crate::components::actor::*;
 use crate::components::agent::*;
 use crate::components::obstacle::*;
 use crate::components::physics::*;
+use crate::components::steering::*;
 use crate::components::vehicle::*;
 
 use super::actor::ActorBundle;
 
 pub const VEHICLE_RADIUS: f32 = 10.0;
+pub const VEHICLE_HEADING_SMOOTHING_SAMPLES: usize = 10;
 
 #[derive(Debug, Default, Bundle)]
 pub struct VehicleBundle {
     pub physical: Physical,
+    pub steering: Steering,
+    pub steering_forces: SteeringForces,
     pub agent: Agent,
     pub obstacle: Obstacle,
     pub obstacle_avoidance: ObstacleAvoidance,
@@ -44,8 +48,12 @@ impl VehicleBundle {
                 max_speed,
                 max_force,
                 max_turn_rate,
+                limit_turn_rate: true,
+                heading_smoothing_samples: VEHICLE_HEADING_SMOOTHING_SAMPLES,
                 ..Default::default()
             },
+            steering: Steering::default(),
+            steering_forces: SteeringForces::default(),
             agent: Agent::default(),
             obstacle: Obstacle::default(),
             obstacle_avoidance: ObstacleAvoidance {
@@ -120,4 +128,39 @@ impl VehicleBundle {
 
         bundle.id()
     }
+
+    // world space marker that follows the vehicle's steering target
+    pub fn spawn_target_debug(commands: &mut Commands, owner: Entity, color: Color) -> Entity {
+        commands
+            .spawn_bundle(GeometryBuilder::build_as(
+                &shapes::Circle {
+                    radius: VEHICLE_RADIUS * 0.5,
+                    ..Default::default()
+                },
+                DrawMode::Stroke(StrokeMode::new(color, 1.0)),
+                Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+            ))
+            .insert(SteeringTargetMarker { owner })
+            .insert(Name::new("Steering Target"))
+            .id()
+    }
+
+    pub fn spawn_path_debug(
+        commands: &mut Commands,
+        waypoints: impl Into<Vec<Vec2>>,
+        looped: bool,
+        color: Color,
+    ) -> Entity {
+        commands
+            .spawn_bundle(GeometryBuilder::build_as(
+                &shapes::Polygon {
+                    points: waypoints.into(),
+                    closed: looped,
+                },
+                DrawMode::Stroke(StrokeMode::new(color, 1.0)),
+                Transform::default(),
+            ))
+            .insert(Name::new("Path"))
+            .id()
+    }
 }
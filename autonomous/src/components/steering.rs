@@ -3,18 +3,15 @@ use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
 use rand::Rng;
 
+use crate::components::actor::*;
 use crate::util::point_to_world_space;
 
 use super::physics::*;
 
-pub trait SteeringBehavior: std::fmt::Debug + Component {}
-
-fn seek_force(target: Vec2, physical: &PhysicalQueryMutItem) -> Vec2 {
-    let translation = physical.transform.translation.truncate();
-
-    let desired_velocity = (target - translation).normalize_or_zero() * physical.physical.max_speed;
-    desired_velocity - physical.physical.velocity
-}
+pub use game_ai_core::path::WaypointPath;
+pub use game_ai_core::steering::{
+    arrive_force, seek_force, Deceleration, Steering, SteeringBehavior,
+};
 
 #[derive(Debug, Default, Component, Inspectable)]
 pub struct SeekTarget {
@@ -27,8 +24,8 @@ pub struct Seek;
 impl SteeringBehavior for Seek {}
 
 impl Seek {
-    pub fn force(&self, target: &SeekTarget, physical: &PhysicalQueryMutItem) -> Vec2 {
-        seek_force(target.position, physical)
+    pub fn force(&self, target: &SeekTarget, physical: &PhysicalQueryItem) -> Vec2 {
+        seek_force(target.position, physical.transform, physical.physical)
     }
 }
 
@@ -39,7 +36,7 @@ pub struct SeekQuery {
     pub target: &'static SeekTarget,
 }
 
-fn flee_force(target: Vec2, physical: &PhysicalQueryMutItem) -> Vec2 {
+fn flee_force(target: Vec2, physical: &PhysicalQueryItem) -> Vec2 {
     let translation = physical.transform.translation.truncate();
 
     let panic_distance_squared = 100.0 * 100.0;
@@ -64,7 +61,7 @@ pub struct Flee;
 impl SteeringBehavior for Flee {}
 
 impl Flee {
-    pub fn force(&self, target: &FleeTarget, physical: &PhysicalQueryMutItem) -> Vec2 {
+    pub fn force(&self, target: &FleeTarget, physical: &PhysicalQueryItem) -> Vec2 {
         flee_force(target.position, physical)
     }
 }
@@ -81,19 +78,6 @@ pub struct ArriveTarget {
     pub position: Vec2,
 }
 
-#[derive(Debug, Clone, Copy, Inspectable)]
-pub enum Deceleration {
-    Slow = 3,
-    Normal = 2,
-    Fast = 1,
-}
-
-impl Default for Deceleration {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 #[derive(Debug, Default, Component, Inspectable)]
 pub struct Arrive {
     pub deceleration: Deceleration,
@@ -102,24 +86,13 @@ pub struct Arrive {
 impl SteeringBehavior for Arrive {}
 
 impl Arrive {
-    pub fn force(&self, target: &ArriveTarget, physical: &PhysicalQueryMutItem) -> Vec2 {
-        let translation = physical.transform.translation.truncate();
-        let deceleration = self.deceleration as i32;
-
-        let to_target = target.position - translation;
-
-        let dist = to_target.length();
-        if dist > 0.0 {
-            // fine tweaking of deceleration
-            let deceleration_tweaker = 0.3;
-
-            let speed = (dist / (deceleration as f32 * deceleration_tweaker))
-                .min(physical.physical.max_speed);
-            let desired_velocity = to_target * speed / dist;
-            return desired_velocity - physical.physical.velocity;
-        }
-
-        Vec2::ZERO
+    pub fn force(&self, target: &ArriveTarget, physical: &PhysicalQueryItem) -> Vec2 {
+        arrive_force(
+            target.position,
+            physical.transform,
+            physical.physical,
+            self.deceleration,
+        )
     }
 }
 
@@ -130,7 +103,7 @@ pub struct ArriveQuery {
     pub target: &'static ArriveTarget,
 }
 
-fn turnaround_time(target: Vec2, physical: &PhysicalQueryMutItem) -> f32 {
+fn turnaround_time(target: Vec2, physical: &PhysicalQueryItem) -> f32 {
     let to_target = (target - physical.transform.translation.truncate()).normalize_or_zero();
     let dot = physical.physical.heading.dot(to_target);
 
@@ -158,16 +131,20 @@ impl Pursuit {
         &self,
         pursuer: Entity,
         target: &PursuitTarget,
-        entities: &mut Query<PhysicalQueryMut>,
+        entities: &Query<PhysicalQuery>,
     ) -> Vec2 {
-        let [pursuer, evader] = entities.many_mut([pursuer, target.entity]);
+        let [pursuer, evader] = entities.many([pursuer, target.entity]);
 
         let to_evader = (evader.transform.translation - pursuer.transform.translation).truncate();
         let relative_heading = pursuer.physical.heading.dot(evader.physical.heading);
 
         // if the evader is ahead and facing us, we can just seek it
         if to_evader.dot(pursuer.physical.heading) > 0.0 && relative_heading < -0.95 {
-            return seek_force(evader.transform.translation.truncate(), &pursuer);
+            return seek_force(
+                evader.transform.translation.truncate(),
+                pursuer.transform,
+                pursuer.physical,
+            );
         }
 
         // not ahead, so predict future position and seek that
@@ -181,7 +158,8 @@ impl Pursuit {
 
         seek_force(
             evader.transform.translation.truncate() + evader.physical.velocity * look_ahead_time,
-            &pursuer,
+            pursuer.transform,
+            pursuer.physical,
         )
     }
 }
@@ -193,7 +171,61 @@ pub struct PursuitQuery {
     pub target: &'static PursuitTarget,
 }
 
-// TODO: offset pursuit
+#[derive(Debug, Component, Inspectable)]
+pub struct OffsetPursuitTarget {
+    pub entity: Entity,
+
+    // in the leader's local space
+    pub offset: Vec2,
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct OffsetPursuit;
+
+impl SteeringBehavior for OffsetPursuit {}
+
+impl OffsetPursuit {
+    pub fn get_offset_position(
+        &self,
+        target: &OffsetPursuitTarget,
+        leader: &PhysicalQueryItem,
+    ) -> Vec2 {
+        point_to_world_space(
+            target.offset,
+            leader.physical.heading,
+            leader.physical.side,
+            leader.transform.translation.truncate(),
+        )
+    }
+
+    pub fn force(
+        &self,
+        offset_position: Vec2,
+        pursuer: &PhysicalQueryItem,
+        leader: &PhysicalQueryItem,
+    ) -> Vec2 {
+        let to_offset = offset_position - pursuer.transform.translation.truncate();
+
+        // the further the offset is, the further ahead of the leader we aim,
+        // scaled down the faster the leader and us can close the gap
+        let look_ahead_time =
+            to_offset.length() / (pursuer.physical.max_speed + leader.physical.speed());
+
+        arrive_force(
+            offset_position + leader.physical.velocity * look_ahead_time,
+            pursuer.transform,
+            pursuer.physical,
+            Deceleration::Fast,
+        )
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(derive(Debug))]
+pub struct OffsetPursuitQuery {
+    pub steering: &'static OffsetPursuit,
+    pub target: &'static OffsetPursuitTarget,
+}
 
 #[derive(Debug, Component, Inspectable)]
 pub struct EvadeTarget {
@@ -210,26 +242,29 @@ impl Evade {
         &self,
         evader: Entity,
         target: &EvadeTarget,
-        entities: &mut Query<PhysicalQueryMut>,
+        entities: &Query<PhysicalQuery>,
     ) -> Vec2 {
-        // TODO: if the target the evader is evading is on top of it
-        // (to_pursuer.length() == 0) then the evader won't try to evade
+        let [evader, pursuer] = entities.many([evader, target.entity]);
+        evade_force(&evader, &pursuer)
+    }
+}
 
-        let [evader, pursuer] = entities.many_mut([evader, target.entity]);
+fn evade_force(evader: &PhysicalQueryItem, pursuer: &PhysicalQueryItem) -> Vec2 {
+    // TODO: if the target the evader is evading is on top of it
+    // (to_pursuer.length() == 0) then the evader won't try to evade
 
-        let to_pursuer = (pursuer.transform.translation - evader.transform.translation).truncate();
+    let to_pursuer = (pursuer.transform.translation - evader.transform.translation).truncate();
 
-        // look-ahead time is proportional to the distance between the pursuer
-        // and us; and is inversly proportional to the sum of our velocities
-        // TODO: zero check this
-        let look_ahead_time =
-            to_pursuer.length() / (evader.physical.max_speed + pursuer.physical.speed());
+    // look-ahead time is proportional to the distance between the pursuer
+    // and us; and is inversly proportional to the sum of our velocities
+    // TODO: zero check this
+    let look_ahead_time =
+        to_pursuer.length() / (evader.physical.max_speed + pursuer.physical.speed());
 
-        flee_force(
-            pursuer.transform.translation.truncate() + pursuer.physical.velocity * look_ahead_time,
-            &evader,
-        )
-    }
+    flee_force(
+        pursuer.transform.translation.truncate() + pursuer.physical.velocity * look_ahead_time,
+        evader,
+    )
 }
 
 #[derive(WorldQuery)]
@@ -261,9 +296,7 @@ impl Wander {
         }
     }
 
-    pub fn force(&mut self, physical: &PhysicalQueryMutItem) -> Vec2 {
-        let mut rng = rand::thread_rng();
-
+    pub fn force(&mut self, physical: &PhysicalQueryItem, rng: &mut impl Rng) -> Vec2 {
         // add some jitter to the target
         self.target += Vec2::new(
             rng.gen_range(-1.0..=1.0) * self.jitter,
@@ -289,6 +322,728 @@ impl Wander {
     }
 }
 
-// TODO: interpose
+// vehicles within view distance, tagged for the group behaviors
+#[derive(Debug, Default, Component)]
+pub struct Neighbors {
+    pub entities: Vec<Entity>,
+}
+
+impl Neighbors {
+    fn physicals<'a>(&self, entities: &'a Query<PhysicalQuery>) -> Vec<PhysicalQueryItem<'a>> {
+        self.entities
+            .iter()
+            .filter_map(|entity| entities.get(*entity).ok())
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct Separation;
+
+impl SteeringBehavior for Separation {}
+
+impl Separation {
+    pub fn force(
+        &self,
+        physical: &PhysicalQueryItem,
+        neighbors: &Neighbors,
+        entities: &Query<PhysicalQuery>,
+    ) -> Vec2 {
+        let translation = physical.transform.translation.truncate();
+
+        let mut force = Vec2::ZERO;
+        for neighbor in neighbors.physicals(entities) {
+            // scale the force inversely proportional to the distance from the neighbor
+            let to_agent = translation - neighbor.transform.translation.truncate();
+            let length_squared = to_agent.length_squared();
+            if length_squared > f32::EPSILON {
+                force += to_agent / length_squared;
+            }
+        }
+
+        force
+    }
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct Alignment;
+
+impl SteeringBehavior for Alignment {}
+
+impl Alignment {
+    pub fn force(
+        &self,
+        physical: &PhysicalQueryItem,
+        neighbors: &Neighbors,
+        entities: &Query<PhysicalQuery>,
+    ) -> Vec2 {
+        let neighbors = neighbors.physicals(entities);
+        if neighbors.is_empty() {
+            return Vec2::ZERO;
+        }
+
+        let average_heading = neighbors
+            .iter()
+            .fold(Vec2::ZERO, |sum, neighbor| sum + neighbor.physical.heading)
+            / neighbors.len() as f32;
+
+        average_heading - physical.physical.heading
+    }
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct Cohesion;
+
+impl SteeringBehavior for Cohesion {}
+
+impl Cohesion {
+    pub fn force(
+        &self,
+        physical: &PhysicalQueryItem,
+        neighbors: &Neighbors,
+        entities: &Query<PhysicalQuery>,
+    ) -> Vec2 {
+        let neighbors = neighbors.physicals(entities);
+        if neighbors.is_empty() {
+            return Vec2::ZERO;
+        }
+
+        let center_of_mass = neighbors.iter().fold(Vec2::ZERO, |sum, neighbor| {
+            sum + neighbor.transform.translation.truncate()
+        }) / neighbors.len() as f32;
+
+        // cohesion is usually a lot larger than separation and alignment
+        // so it's normalized to keep them in balance
+        seek_force(center_of_mass, physical.transform, physical.physical).normalize_or_zero()
+    }
+}
+
+// steering behaviors, declared in priority order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Behavior {
+    WallAvoidance,
+    ObstacleAvoidance,
+    Evade,
+    Hide,
+    Flee,
+    Separation,
+    Alignment,
+    Cohesion,
+    Seek,
+    Arrive,
+    Wander,
+    Pursuit,
+    OffsetPursuit,
+    Interpose,
+    FollowPath,
+}
+
+// how the forces from each behavior are combined into the steering force
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Inspectable)]
+pub enum CombinationMode {
+    WeightedTruncatedSum,
+    #[default]
+    PrioritizedTruncatedSum,
+    PrioritizedDithering,
+}
+
+impl CombinationMode {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::WeightedTruncatedSum => "Weighted Truncated Sum",
+            Self::PrioritizedTruncatedSum => "Prioritized Truncated Sum",
+            Self::PrioritizedDithering => "Prioritized Dithering",
+        }
+    }
+}
+
+// raw behavior forces for the current physics step
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct SteeringForces {
+    pub mode: CombinationMode,
+
+    #[inspectable(ignore)]
+    forces: Vec<(Behavior, Vec2)>,
+}
+
+impl SteeringForces {
+    pub fn add(&mut self, behavior: Behavior, force: Vec2) {
+        self.forces.push((behavior, force));
+    }
+
+    // combines (and clears) the behavior forces into the steering force
+    pub fn combine(
+        &mut self,
+        steering: &mut Steering,
+        physical: &Physical,
+        rng: &mut impl Rng,
+        weight: impl Fn(Behavior) -> f32,
+        probability: impl Fn(Behavior) -> f32,
+    ) {
+        // the order the behaviors ran in doesn't matter, only their priority
+        self.forces.sort_by_key(|(behavior, _)| *behavior);
+
+        match self.mode {
+            CombinationMode::WeightedTruncatedSum => {
+                let force = self
+                    .forces
+                    .iter()
+                    .fold(Vec2::ZERO, |sum, (behavior, force)| {
+                        sum + *force * weight(*behavior)
+                    });
+                steering.accumulate_force(physical, force, 1.0);
+            }
+            CombinationMode::PrioritizedTruncatedSum => {
+                for (behavior, force) in self.forces.iter() {
+                    steering.accumulate_force(physical, *force, weight(*behavior));
+                }
+            }
+            CombinationMode::PrioritizedDithering => {
+                // only the first behavior that passes its probability check
+                // and has a force is used, weighted up by its probability
+                for (behavior, force) in self.forces.iter() {
+                    let probability = probability(*behavior);
+                    if rng.gen::<f32>() >= probability {
+                        continue;
+                    }
+
+                    let force = *force * weight(*behavior) / probability;
+                    if force != Vec2::ZERO {
+                        steering.accumulate_force(physical, force, 1.0);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.forces.clear();
+    }
+}
+
+#[derive(Debug, Component, Inspectable)]
+pub struct InterposeTarget {
+    pub a: Entity,
+    pub b: Entity,
+}
 
-// TODO: path follow
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct Interpose;
+
+impl SteeringBehavior for Interpose {}
+
+impl Interpose {
+    // where the midpoint between the two agents will be by the time we get there
+    pub fn get_target_position(
+        &self,
+        physical: &PhysicalQueryItem,
+        a: &PhysicalQueryItem,
+        b: &PhysicalQueryItem,
+    ) -> Vec2 {
+        let a_position = a.transform.translation.truncate();
+        let b_position = b.transform.translation.truncate();
+
+        let midpoint = (a_position + b_position) * 0.5;
+        let time_to_reach_midpoint = physical.transform.translation.truncate().distance(midpoint)
+            / physical.physical.max_speed;
+
+        let a_position = a_position + a.physical.velocity * time_to_reach_midpoint;
+        let b_position = b_position + b.physical.velocity * time_to_reach_midpoint;
+
+        (a_position + b_position) * 0.5
+    }
+
+    pub fn force(&self, target_position: Vec2, physical: &PhysicalQueryItem) -> Vec2 {
+        arrive_force(
+            target_position,
+            physical.transform,
+            physical.physical,
+            Deceleration::Fast,
+        )
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(derive(Debug))]
+pub struct InterposeQuery {
+    pub steering: &'static Interpose,
+    pub target: &'static InterposeTarget,
+}
+
+#[derive(Debug, Component, Inspectable)]
+pub struct HideTarget {
+    pub entity: Entity,
+}
+
+#[derive(Debug, Component, Inspectable)]
+pub struct Hide {
+    // how far behind the obstacle to hide
+    pub distance_from_boundary: f32,
+}
+
+impl Default for Hide {
+    fn default() -> Self {
+        Self {
+            distance_from_boundary: 30.0,
+        }
+    }
+}
+
+impl SteeringBehavior for Hide {}
+
+impl Hide {
+    // the closest spot on the far side of an obstacle from the hunter
+    pub fn get_hiding_spot<'a>(
+        &self,
+        physical: &PhysicalQueryItem,
+        hunter: &PhysicalQueryItem,
+        obstacles: impl Iterator<Item = (&'a Actor, &'a Transform)>,
+    ) -> Option<Vec2> {
+        let position = physical.transform.translation.truncate();
+        let hunter_position = hunter.transform.translation.truncate();
+
+        let mut closest = None;
+        let mut dist_to_closest = f32::MAX;
+        for (actor, transform) in obstacles {
+            let obstacle_position = transform.translation.truncate();
+
+            let to_obstacle = (obstacle_position - hunter_position).normalize_or_zero();
+            let hiding_spot = obstacle_position
+                + to_obstacle * (actor.bounding_radius + self.distance_from_boundary);
+
+            let dist = position.distance_squared(hiding_spot);
+            if dist < dist_to_closest {
+                dist_to_closest = dist;
+                closest = Some(hiding_spot);
+            }
+        }
+
+        closest
+    }
+
+    // evade the hunter if there's nowhere to hide
+    pub fn force(
+        &self,
+        hiding_spot: Option<Vec2>,
+        physical: &PhysicalQueryItem,
+        hunter: &PhysicalQueryItem,
+    ) -> Vec2 {
+        match hiding_spot {
+            Some(hiding_spot) => arrive_force(
+                hiding_spot,
+                physical.transform,
+                physical.physical,
+                Deceleration::Fast,
+            ),
+            None => evade_force(physical, hunter),
+        }
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(derive(Debug))]
+pub struct HideQuery {
+    pub steering: &'static Hide,
+    pub target: &'static HideTarget,
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct FollowPathTarget {
+    pub path: WaypointPath,
+}
+
+impl FollowPathTarget {
+    pub fn new(waypoints: Vec<Vec2>, looped: bool) -> Self {
+        Self {
+            path: WaypointPath::new(waypoints, looped),
+        }
+    }
+}
+
+// seeks each waypoint in turn, arriving at the last one unless the path loops
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct FollowPath;
+
+impl SteeringBehavior for FollowPath {}
+
+impl FollowPath {
+    pub fn force(&self, target: &FollowPathTarget, physical: &PhysicalQueryItem) -> Vec2 {
+        target.path.force(physical.transform, physical.physical)
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct FollowPathQueryMut {
+    pub steering: &'static FollowPath,
+    pub target: &'static mut FollowPathTarget,
+}
+
+// marks the steering target of the owner
+#[derive(Debug, Component)]
+pub struct SteeringTargetMarker {
+    pub owner: Entity,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+    use game_ai_core::random::Random;
+
+    use crate::resources::SimulationParams;
+
+    use super::*;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 0.0001, "{} != {}", a, b);
+    }
+
+    fn spawn(world: &mut World, position: Vec2, heading: Vec2) -> Entity {
+        world
+            .spawn()
+            .insert(Transform::from_translation(position.extend(0.0)))
+            .insert(Physical {
+                heading,
+                side: heading.perp(),
+                max_speed: 10.0,
+                ..Default::default()
+            })
+            .id()
+    }
+
+    // runs f with the agent's physical and a query over every physical
+    fn with_physicals<T>(
+        world: &mut World,
+        agent: Entity,
+        f: impl FnOnce(&PhysicalQueryItem, &Query<PhysicalQuery>) -> T,
+    ) -> T {
+        let mut state = SystemState::<Query<PhysicalQuery>>::new(world);
+        let entities = state.get(world);
+        let physical = entities.get(agent).unwrap();
+        f(&physical, &entities)
+    }
+
+    #[test]
+    fn test_separation() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::ZERO, Vec2::X);
+        let neighbors = Neighbors {
+            entities: vec![
+                spawn(&mut world, Vec2::new(10.0, 0.0), Vec2::X),
+                spawn(&mut world, Vec2::new(0.0, -5.0), Vec2::X),
+            ],
+        };
+
+        // away from each neighbor, closer neighbors push harder
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            Separation.force(physical, &neighbors, entities)
+        });
+        assert_near(force, Vec2::new(-0.1, 0.2));
+
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            Separation.force(physical, &Neighbors::default(), entities)
+        });
+        assert_eq!(force, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_alignment() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::ZERO, Vec2::X);
+        let neighbors = Neighbors {
+            entities: vec![
+                spawn(&mut world, Vec2::new(10.0, 0.0), Vec2::Y),
+                spawn(&mut world, Vec2::new(0.0, 10.0), Vec2::Y),
+            ],
+        };
+
+        // turns towards the average neighbor heading
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            Alignment.force(physical, &neighbors, entities)
+        });
+        assert_near(force, Vec2::Y - Vec2::X);
+
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            Alignment.force(physical, &Neighbors::default(), entities)
+        });
+        assert_eq!(force, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_cohesion() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::ZERO, Vec2::X);
+        let neighbors = Neighbors {
+            entities: vec![
+                spawn(&mut world, Vec2::new(10.0, 0.0), Vec2::X),
+                spawn(&mut world, Vec2::new(10.0, 20.0), Vec2::X),
+            ],
+        };
+
+        // normalized seek towards the center of mass
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            Cohesion.force(physical, &neighbors, entities)
+        });
+        assert_near(force, Vec2::new(1.0, 1.0).normalize());
+
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            Cohesion.force(physical, &Neighbors::default(), entities)
+        });
+        assert_eq!(force, Vec2::ZERO);
+    }
+
+    fn set_velocity(world: &mut World, entity: Entity, velocity: Vec2) {
+        world.get_mut::<Physical>(entity).unwrap().velocity = velocity;
+    }
+
+    #[test]
+    fn test_interpose() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::new(-50.0, 0.0), Vec2::X);
+        let a = spawn(&mut world, Vec2::new(0.0, 20.0), Vec2::X);
+        let b = spawn(&mut world, Vec2::new(0.0, -20.0), Vec2::X);
+        set_velocity(&mut world, a, Vec2::new(10.0, 0.0));
+        set_velocity(&mut world, b, Vec2::new(10.0, 0.0));
+
+        // the midpoint has moved on by the time we reach where it is now
+        let target = with_physicals(&mut world, agent, |physical, entities| {
+            let [a, b] = entities.many([a, b]);
+            Interpose.get_target_position(physical, &a, &b)
+        });
+        assert_near(target, Vec2::new(50.0, 0.0));
+
+        let force = with_physicals(&mut world, agent, |physical, _| {
+            Interpose.force(target, physical)
+        });
+        assert_near(force, Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn test_hide() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::ZERO, Vec2::X);
+        let hunter = spawn(&mut world, Vec2::new(100.0, 0.0), Vec2::X);
+
+        let obstacle = Actor {
+            bounding_radius: 10.0,
+        };
+        let near = Transform::from_xyz(50.0, 0.0, 0.0);
+        let far = Transform::from_xyz(-40.0, 0.0, 0.0);
+
+        // the closest spot on the far side of an obstacle from the hunter
+        let hide = Hide::default();
+        let hiding_spot = with_physicals(&mut world, agent, |physical, entities| {
+            let hunter = entities.get(hunter).unwrap();
+            hide.get_hiding_spot(
+                physical,
+                &hunter,
+                [(&obstacle, &far), (&obstacle, &near)].into_iter(),
+            )
+        });
+        assert_near(hiding_spot.unwrap(), Vec2::new(10.0, 0.0));
+
+        // nowhere to hide, so evade the hunter
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            let hunter = entities.get(hunter).unwrap();
+            assert!(hide
+                .get_hiding_spot(physical, &hunter, std::iter::empty())
+                .is_none());
+            hide.force(None, physical, &hunter)
+        });
+        assert_near(force, Vec2::new(-10.0, 0.0));
+    }
+
+    #[test]
+    fn test_offset_pursuit() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::ZERO, Vec2::X);
+        let leader = spawn(&mut world, Vec2::new(10.0, 0.0), Vec2::Y);
+
+        // behind and to the right of the leader
+        let target = OffsetPursuitTarget {
+            entity: leader,
+            offset: Vec2::new(-5.0, 2.0),
+        };
+        let offset_position = with_physicals(&mut world, agent, |_, entities| {
+            let leader = entities.get(leader).unwrap();
+            OffsetPursuit.get_offset_position(&target, &leader)
+        });
+        assert_near(offset_position, Vec2::new(8.0, -5.0));
+
+        // a still leader means arriving at the offset itself
+        let force = with_physicals(&mut world, agent, |physical, entities| {
+            let leader = entities.get(leader).unwrap();
+            OffsetPursuit.force(offset_position, physical, &leader)
+        });
+        assert!(force
+            .normalize()
+            .abs_diff_eq(offset_position.normalize(), 0.0001));
+    }
+
+    #[test]
+    fn test_follow_path() {
+        let mut world = World::new();
+        let agent = spawn(&mut world, Vec2::new(10.0, 0.0), Vec2::X);
+
+        let waypoints = vec![Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)];
+
+        // advances past reached waypoints and loops back to the start
+        let mut target = FollowPathTarget::new(waypoints.clone(), true);
+        target.path.update(Vec2::new(10.0, 0.0), 1.0);
+        assert_eq!(target.path.current_waypoint(), Some(waypoints[1]));
+
+        let force = with_physicals(&mut world, agent, |physical, _| {
+            FollowPath.force(&target, physical)
+        });
+        assert_near(force, Vec2::new(0.0, 10.0));
+
+        target.path.update(Vec2::new(10.0, 10.0), 1.0);
+        assert_eq!(target.path.current_waypoint(), Some(waypoints[0]));
+
+        // an open path stops at its last waypoint, slowing down on the way
+        let mut target = FollowPathTarget::new(waypoints.clone(), false);
+        target.path.update(Vec2::new(10.0, 0.0), 1.0);
+        target.path.update(Vec2::new(10.0, 10.0), 1.0);
+        assert_eq!(target.path.current_waypoint(), Some(waypoints[1]));
+
+        let agent = spawn(&mut world, Vec2::new(10.0, 9.0), Vec2::X);
+        let force = with_physicals(&mut world, agent, |physical, _| {
+            FollowPath.force(&target, physical)
+        });
+        assert!(force.y > 0.0 && force.y < 10.0);
+    }
+
+    fn combine(
+        mode: CombinationMode,
+        forces: &[(Behavior, Vec2)],
+        weight: impl Fn(Behavior) -> f32,
+        probability: impl Fn(Behavior) -> f32,
+    ) -> Vec2 {
+        let mut steering_forces = SteeringForces {
+            mode,
+            ..Default::default()
+        };
+        for (behavior, force) in forces {
+            steering_forces.add(*behavior, *force);
+        }
+
+        let mut steering = Steering::default();
+        let physical = Physical {
+            max_force: 10.0,
+            ..Default::default()
+        };
+        steering_forces.combine(
+            &mut steering,
+            &physical,
+            &mut Random::new(0),
+            weight,
+            probability,
+        );
+        assert!(steering_forces.forces.is_empty());
+
+        steering.accumulated_force
+    }
+
+    #[test]
+    fn test_combine_weighted_truncated_sum() {
+        let weight = |behavior| match behavior {
+            Behavior::Seek => 2.0,
+            _ => 1.0,
+        };
+
+        // everything is weighted and summed
+        let force = combine(
+            CombinationMode::WeightedTruncatedSum,
+            &[
+                (Behavior::Seek, Vec2::new(3.0, 0.0)),
+                (Behavior::Flee, Vec2::new(0.0, 4.0)),
+            ],
+            weight,
+            |_| 1.0,
+        );
+        assert_near(force, Vec2::new(6.0, 4.0));
+
+        // and then truncated to the max force
+        let force = combine(
+            CombinationMode::WeightedTruncatedSum,
+            &[
+                (Behavior::Seek, Vec2::new(10.0, 0.0)),
+                (Behavior::Flee, Vec2::new(-5.0, 0.0)),
+            ],
+            weight,
+            |_| 1.0,
+        );
+        assert_near(force, Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn test_combine_prioritized_truncated_sum() {
+        // flee has priority over seek, regardless of the order they were added in
+        let force = combine(
+            CombinationMode::PrioritizedTruncatedSum,
+            &[
+                (Behavior::Seek, Vec2::new(20.0, 0.0)),
+                (Behavior::Flee, Vec2::new(0.0, 8.0)),
+            ],
+            |_| 1.0,
+            |_| 1.0,
+        );
+        assert_near(force, Vec2::new(2.0, 8.0));
+
+        // weights apply before the remaining magnitude is used up
+        let force = combine(
+            CombinationMode::PrioritizedTruncatedSum,
+            &[
+                (Behavior::Seek, Vec2::new(20.0, 0.0)),
+                (Behavior::Flee, Vec2::new(0.0, 8.0)),
+            ],
+            |behavior| match behavior {
+                Behavior::Flee => 0.75,
+                _ => 1.0,
+            },
+            |_| 1.0,
+        );
+        assert_near(force, Vec2::new(4.0, 6.0));
+    }
+
+    #[test]
+    fn test_combine_prioritized_dithering() {
+        let forces = [
+            (Behavior::Seek, Vec2::new(1.0, 0.0)),
+            (Behavior::Flee, Vec2::new(0.0, 1.0)),
+            (Behavior::Separation, Vec2::ZERO),
+        ];
+
+        // only the highest priority behavior with a force is used
+        let force = combine(
+            CombinationMode::PrioritizedDithering,
+            &forces,
+            |_| 2.0,
+            |_| 1.0,
+        );
+        assert_near(force, Vec2::new(0.0, 2.0));
+
+        // behaviors that fail their probability check are skipped
+        let force = combine(
+            CombinationMode::PrioritizedDithering,
+            &forces,
+            |_| 2.0,
+            |behavior| match behavior {
+                Behavior::Flee => 0.0,
+                _ => 1.0,
+            },
+        );
+        assert_near(force, Vec2::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn test_pursuit_uses_its_own_weight() {
+        let params = SimulationParams {
+            evade_weight: 0.5,
+            pursuit_weight: 1.0,
+            ..Default::default()
+        };
+
+        assert_eq!(params.get_weight(Behavior::Pursuit), 1.0);
+        assert_eq!(params.get_weight(Behavior::Evade), 0.5);
+    }
+}
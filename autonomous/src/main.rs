@@ -18,15 +18,20 @@ use bevy_egui::{EguiPlugin, EguiSettings};
 use bevy_inspector_egui::prelude::*;
 use bevy_inspector_egui::WorldInspectorParams;
 use bevy_prototype_lyon::prelude::*;
+use game_ai_core::args::{has_arg, parse_seed};
+use game_ai_core::random::Random;
 
 use crate::components::physics::PHYSICS_STEP;
+use crate::components::steering::CombinationMode;
 use crate::plugins::debug::*;
 use crate::resources::ui::*;
 use crate::resources::*;
 use crate::states::*;
 use crate::systems::Systems;
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, random: Res<Random>) {
+    info!("random seed: {}", random.seed());
+
     #[cfg(debug_assertions)]
     asset_server.watch_for_changes().unwrap();
 
@@ -48,6 +53,12 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         min_obstacle_radius: 10.0,
         max_obstacle_radius: 30.0,
         min_gap_between_obstacles: 20.0,
+        flock: has_arg("--flock"),
+        num_flocking_vehicles: 200,
+        view_distance: 50.0,
+        num_cells_x: 16,
+        num_cells_y: 12,
+        combination_mode: CombinationMode::default(),
         seek_weight: 1.0,
         flee_weight: 1.0,
         arrive_weight: 1.0,
@@ -56,8 +67,31 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         wander_weight: 1.0,
         obstacle_avoidance_weight: 10.0,
         wall_avoidance_weight: 10.0,
+        separation_weight: 1.0 * steering_force_tweaker,
+        alignment_weight: 1.0 * steering_force_tweaker,
+        cohesion_weight: 2.0 * steering_force_tweaker,
+        hide_weight: 1.0,
+        offset_pursuit_weight: 1.0,
+        interpose_weight: 1.0,
+        follow_path_weight: 1.0,
+        seek_probability: 0.8,
+        flee_probability: 0.6,
+        arrive_probability: 0.5,
+        evade_probability: 1.0,
+        pursuit_probability: 0.8,
+        wander_probability: 0.8,
+        obstacle_avoidance_probability: 0.5,
+        wall_avoidance_probability: 0.5,
+        separation_probability: 0.2,
+        alignment_probability: 0.3,
+        cohesion_probability: 0.6,
+        hide_probability: 0.8,
+        offset_pursuit_probability: 0.8,
+        interpose_probability: 0.8,
+        follow_path_probability: 0.8,
         min_detection_box_length: 40.0,
         wall_detection_feeler_length: 40.0,
+        waypoint_seek_distance: 20.0,
     });
 }
 
@@ -82,6 +116,9 @@ fn main() {
     .add_plugins(DefaultPlugins)
     .add_plugin(FrameTimeDiagnosticsPlugin);
 
+    // seedable rng
+    app.insert_resource(parse_seed().map(Random::new).unwrap_or_default());
+
     // prototype lyon
     app.add_plugin(ShapePlugin);
 
@@ -105,6 +142,8 @@ fn main() {
     .register_inspectable::<components::obstacle::Wall>()
     .register_inspectable::<components::obstacle::WallAvoidance>()
     .register_inspectable::<components::physics::Physical>()
+    .register_inspectable::<components::steering::Steering>()
+    .register_inspectable::<components::steering::SteeringForces>()
     .register_inspectable::<components::steering::Seek>()
     .register_inspectable::<components::steering::SeekTarget>()
     .register_inspectable::<components::steering::Flee>()
@@ -115,7 +154,18 @@ fn main() {
     .register_inspectable::<components::steering::PursuitTarget>()
     .register_inspectable::<components::steering::Evade>()
     .register_inspectable::<components::steering::EvadeTarget>()
-    .register_inspectable::<components::steering::Wander>();
+    .register_inspectable::<components::steering::OffsetPursuit>()
+    .register_inspectable::<components::steering::OffsetPursuitTarget>()
+    .register_inspectable::<components::steering::Wander>()
+    .register_inspectable::<components::steering::Interpose>()
+    .register_inspectable::<components::steering::InterposeTarget>()
+    .register_inspectable::<components::steering::Hide>()
+    .register_inspectable::<components::steering::HideTarget>()
+    .register_inspectable::<components::steering::FollowPath>()
+    .register_inspectable::<components::steering::FollowPathTarget>()
+    .register_inspectable::<components::steering::Separation>()
+    .register_inspectable::<components::steering::Alignment>()
+    .register_inspectable::<components::steering::Cohesion>();
 
     // plugins
     app.add_plugin(DebugPlugin);
@@ -139,9 +189,11 @@ fn main() {
         .add_system_set(
             SystemSet::on_update(GameState::Main)
                 .with_run_criteria(FixedTimestep::step(PHYSICS_STEP as f64))
+                .with_system(systems::update_partition.label(Systems::Partition))
                 .with_system(
                     systems::steering::update_obstacle_avoidance
                         .label(Systems::Avoidance)
+                        .after(Systems::Partition)
                         .before(Systems::Steering),
                 )
                 .with_system(
@@ -162,17 +214,61 @@ fn main() {
                         .label(Systems::Steering)
                         .after(Systems::Pursuit),
                 )
-                .with_system(systems::steering::update_wander.label(Systems::Steering))
+                .with_system(systems::steering::update_offset_pursuit.label(Systems::Steering))
+                .with_system(systems::steering::update_interpose.label(Systems::Steering))
+                .with_system(systems::steering::update_hide.label(Systems::Steering))
+                .with_system(systems::steering::update_follow_path.label(Systems::Steering))
+                .with_system(
+                    systems::steering::update_neighbors
+                        .label(Systems::Neighbors)
+                        .after(Systems::Partition)
+                        .before(Systems::Steering),
+                )
+                .with_system(
+                    systems::steering::update_separation
+                        .label(Systems::Steering)
+                        .label(Systems::Flocking)
+                        .after(Systems::Neighbors),
+                )
+                .with_system(
+                    systems::steering::update_alignment
+                        .label(Systems::Steering)
+                        .label(Systems::Flocking)
+                        .after(Systems::Neighbors),
+                )
+                .with_system(
+                    systems::steering::update_cohesion
+                        .label(Systems::Steering)
+                        .label(Systems::Flocking)
+                        .after(Systems::Neighbors),
+                )
+                .with_system(
+                    systems::steering::update_wander
+                        .label(Systems::Steering)
+                        .after(Systems::Flocking),
+                )
+                .with_system(
+                    systems::steering::update_combination
+                        .label(Systems::Combination)
+                        .after(Systems::Avoidance)
+                        .after(Systems::Steering),
+                )
+                .with_system(
+                    game_ai_core::systems::steering::update
+                        .label(Systems::SteeringUpdatePhysics)
+                        .after(Systems::Combination),
+                )
                 .with_system(
                     systems::physics::update
                         .label(Systems::Physics)
-                        .after(Systems::Steering),
+                        .after(Systems::SteeringUpdatePhysics),
                 )
                 .with_system(systems::wrap.after(Systems::Physics))
                 .with_system(systems::facing.after(Systems::Physics)),
         )
-        // TODO: non-physics systems here
-        //.add_system_set(SystemSet::on_update(GameState::Main).with_system())
+        .add_system_set(
+            SystemSet::on_update(GameState::Main).with_system(systems::steering::update_debug),
+        )
         .add_system_set(SystemSet::on_exit(GameState::Main).with_system(states::main::teardown));
 
     app.run();
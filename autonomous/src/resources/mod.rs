@@ -1,6 +1,8 @@
 pub mod debug;
 pub mod ui;
 
+use crate::components::steering::{Behavior, CombinationMode};
+
 #[derive(Debug, Default)]
 pub struct SimulationParams {
     pub window_border: f32,
@@ -17,6 +19,18 @@ pub struct SimulationParams {
     pub max_obstacle_radius: f32,
     pub min_gap_between_obstacles: f32,
 
+    // flocking, the flock scene replaces the individual behaviors
+    pub flock: bool,
+    pub num_flocking_vehicles: usize,
+    pub view_distance: f32,
+
+    // cell-space partition
+    pub num_cells_x: usize,
+    pub num_cells_y: usize,
+
+    // steering force combination
+    pub combination_mode: CombinationMode,
+
     // steering weights
     pub seek_weight: f32,
     pub flee_weight: f32,
@@ -26,10 +40,79 @@ pub struct SimulationParams {
     pub wander_weight: f32,
     pub obstacle_avoidance_weight: f32,
     pub wall_avoidance_weight: f32,
+    pub separation_weight: f32,
+    pub alignment_weight: f32,
+    pub cohesion_weight: f32,
+    pub hide_weight: f32,
+    pub offset_pursuit_weight: f32,
+    pub interpose_weight: f32,
+    pub follow_path_weight: f32,
+
+    // steering probabilities (prioritized dithering)
+    pub seek_probability: f32,
+    pub flee_probability: f32,
+    pub arrive_probability: f32,
+    pub evade_probability: f32,
+    pub pursuit_probability: f32,
+    pub wander_probability: f32,
+    pub obstacle_avoidance_probability: f32,
+    pub wall_avoidance_probability: f32,
+    pub separation_probability: f32,
+    pub alignment_probability: f32,
+    pub cohesion_probability: f32,
+    pub hide_probability: f32,
+    pub offset_pursuit_probability: f32,
+    pub interpose_probability: f32,
+    pub follow_path_probability: f32,
 
     // obstacle avoidance
     pub min_detection_box_length: f32,
 
     // wall avoidance
     pub wall_detection_feeler_length: f32,
+
+    // path following
+    pub waypoint_seek_distance: f32,
+}
+
+impl SimulationParams {
+    pub fn get_weight(&self, behavior: Behavior) -> f32 {
+        match behavior {
+            Behavior::WallAvoidance => self.wall_avoidance_weight,
+            Behavior::ObstacleAvoidance => self.obstacle_avoidance_weight,
+            Behavior::Evade => self.evade_weight,
+            Behavior::Flee => self.flee_weight,
+            Behavior::Separation => self.separation_weight,
+            Behavior::Alignment => self.alignment_weight,
+            Behavior::Cohesion => self.cohesion_weight,
+            Behavior::Seek => self.seek_weight,
+            Behavior::Arrive => self.arrive_weight,
+            Behavior::Wander => self.wander_weight,
+            Behavior::Pursuit => self.pursuit_weight,
+            Behavior::Hide => self.hide_weight,
+            Behavior::OffsetPursuit => self.offset_pursuit_weight,
+            Behavior::Interpose => self.interpose_weight,
+            Behavior::FollowPath => self.follow_path_weight,
+        }
+    }
+
+    pub fn get_probability(&self, behavior: Behavior) -> f32 {
+        match behavior {
+            Behavior::WallAvoidance => self.wall_avoidance_probability,
+            Behavior::ObstacleAvoidance => self.obstacle_avoidance_probability,
+            Behavior::Evade => self.evade_probability,
+            Behavior::Flee => self.flee_probability,
+            Behavior::Separation => self.separation_probability,
+            Behavior::Alignment => self.alignment_probability,
+            Behavior::Cohesion => self.cohesion_probability,
+            Behavior::Seek => self.seek_probability,
+            Behavior::Arrive => self.arrive_probability,
+            Behavior::Wander => self.wander_probability,
+            Behavior::Pursuit => self.pursuit_probability,
+            Behavior::Hide => self.hide_probability,
+            Behavior::OffsetPursuit => self.offset_pursuit_probability,
+            Behavior::Interpose => self.interpose_probability,
+            Behavior::FollowPath => self.follow_path_probability,
+        }
+    }
 }
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use game_ai_core::partition::*;
+use game_ai_core::random::Random;
 use rand::Rng;
 
 use crate::bundles::obstacle::*;
@@ -8,16 +10,27 @@ use crate::components::steering;
 use crate::resources::*;
 use crate::util;
 
-pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<WindowDescriptor>) {
-    let mut rng = rand::thread_rng();
+pub fn setup(
+    mut commands: Commands,
+    params: Res<SimulationParams>,
+    window: Res<WindowDescriptor>,
+    mut rng: ResMut<Random>,
+) {
+    let rng = rng.stream("setup");
 
     let hw = window.width * 0.5;
     let hh = window.height * 0.5;
 
-    let min_x = -hw + VEHICLE_RADIUS + params.window_border;
-    let max_x = hw - VEHICLE_RADIUS - params.window_border;
-    let min_y = -hh + VEHICLE_RADIUS + params.window_border;
-    let max_y = hh - VEHICLE_RADIUS - params.window_border;
+    // vehicle spawn bounds
+    let max = Vec2::new(hw, hh) - Vec2::splat(VEHICLE_RADIUS + params.window_border);
+    let min = -max;
+
+    commands.insert_resource(CellSpacePartition::new(
+        Vec2::new(-hw, -hh),
+        Vec2::new(hw, hh),
+        params.num_cells_x,
+        params.num_cells_y,
+    ));
 
     // cameras
     commands.insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)));
@@ -26,10 +39,82 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
         .insert(MainCamera)
         .insert(Name::new("Main Camera"));
 
-    // vehicles
-    let entity = VehicleBundle::spawn(
+    if params.flock {
+        spawn_flock(&mut commands, &params, rng, min, max);
+    } else {
+        spawn_behaviors(&mut commands, &params, rng, min, max);
+    }
+
+    // build a set of non-overlapping obstacles
+    let max_tries = 2000;
+    let mut obstacles = Vec::with_capacity(params.num_obstacles);
+    for _ in 0..params.num_obstacles {
+        let mut num_tries = 0;
+        let mut overlapped = true;
+        while overlapped {
+            num_tries += 1;
+            if num_tries > max_tries {
+                break;
+            }
+
+            let radius = rng.gen_range(params.min_obstacle_radius..=params.max_obstacle_radius);
+            let min_x = -hw + radius + params.window_border;
+            let max_x = hw - radius - params.window_border;
+            let min_y = -hh + radius + params.window_border;
+            let max_y = hh - radius - params.window_border;
+            let position = Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y));
+
+            if !util::overlapped(
+                position,
+                radius,
+                &obstacles,
+                params.min_gap_between_obstacles,
+            ) {
+                obstacles.push((position, radius));
+                overlapped = false;
+            }
+        }
+
+        if num_tries > max_tries {
+            break;
+        }
+    }
+
+    // spawn the obstacles
+    for (position, radius) in obstacles {
+        ObstacleBundle::spawn(&mut commands, position, radius);
+    }
+
+    // spawn walls
+    WallBundle::spawn(
+        &mut commands,
+        Vec2::new(-hw * 0.5, 0.0),
+        Vec2::new(0.0, 0.0),
+        Vec2::new(0.0, -hh * 0.5),
+    );
+    WallBundle::spawn(
         &mut commands,
-        Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+        Vec2::new(hw * 0.5, 0.0),
+        Vec2::new(0.0, 0.0),
+        Vec2::new(0.0, hh * 0.5),
+    );
+}
+
+fn random_position(rng: &mut Random, min: Vec2, max: Vec2) -> Vec2 {
+    Vec2::new(rng.gen_range(min.x..max.x), rng.gen_range(min.y..max.y))
+}
+
+// a vehicle for each of the individual steering behaviors
+fn spawn_behaviors(
+    commands: &mut Commands,
+    params: &SimulationParams,
+    rng: &mut Random,
+    min: Vec2,
+    max: Vec2,
+) {
+    let entity = VehicleBundle::spawn(
+        commands,
+        random_position(rng, min, max),
         params.vehicle_mass,
         params.vehicle_max_speed,
         params.vehicle_max_steering_force,
@@ -42,23 +127,16 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
         .entity(entity)
         .insert(steering::Seek::default())
         .insert(steering::SeekTarget {
-            position: Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+            position: random_position(rng, min, max),
         });
 
     // TODO: fleeing seems like it may not be working quite right
     // based on how it has to be setup to flee from something right next to it
     // and once it starts fleeing, it never stops
-    let flee_position = Vec2::new(
-        rng.gen_range(
-            -hw + VEHICLE_RADIUS + params.window_border..hw - VEHICLE_RADIUS - params.window_border,
-        ),
-        rng.gen_range(
-            -hh + VEHICLE_RADIUS + params.window_border..hh - VEHICLE_RADIUS - params.window_border,
-        ),
-    );
+    let flee_position = random_position(rng, min, max);
 
     let entity = VehicleBundle::spawn(
-        &mut commands,
+        commands,
         flee_position,
         params.vehicle_mass,
         params.vehicle_max_speed,
@@ -76,8 +154,8 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
         });
 
     let entity = VehicleBundle::spawn(
-        &mut commands,
-        Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+        commands,
+        random_position(rng, min, max),
         params.vehicle_mass,
         params.vehicle_max_speed,
         params.vehicle_max_steering_force,
@@ -92,12 +170,12 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
             deceleration: steering::Deceleration::Slow,
         })
         .insert(steering::ArriveTarget {
-            position: Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+            position: random_position(rng, min, max),
         });
 
     let evade_entity = VehicleBundle::spawn(
-        &mut commands,
-        Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+        commands,
+        random_position(rng, min, max),
         params.vehicle_mass,
         params.vehicle_max_speed,
         params.vehicle_max_steering_force,
@@ -107,8 +185,8 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
     );
 
     let pursuit_entity = VehicleBundle::spawn(
-        &mut commands,
-        Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+        commands,
+        random_position(rng, min, max),
         params.vehicle_mass,
         params.vehicle_max_speed * 0.75,
         params.vehicle_max_steering_force * 0.75,
@@ -131,11 +209,30 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
             entity: evade_entity,
         });
 
-    // TODO: interpose pursuit and evade (bodyguard)
-
+    // bodyguard
     let entity = VehicleBundle::spawn(
-        &mut commands,
-        Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)),
+        commands,
+        random_position(rng, min, max),
+        params.vehicle_mass,
+        params.vehicle_max_speed,
+        params.vehicle_max_steering_force,
+        params.vehicle_max_turn_rate,
+        "interpose",
+        Color::CYAN,
+    );
+
+    commands
+        .entity(entity)
+        .insert(steering::Interpose)
+        .insert(steering::InterposeTarget {
+            a: pursuit_entity,
+            b: evade_entity,
+        });
+    VehicleBundle::spawn_target_debug(commands, entity, Color::CYAN);
+
+    let wander_entity = VehicleBundle::spawn(
+        commands,
+        random_position(rng, min, max),
         params.vehicle_mass,
         params.vehicle_max_speed,
         params.vehicle_max_steering_force,
@@ -145,64 +242,134 @@ pub fn setup(mut commands: Commands, params: Res<SimulationParams>, window: Res<
     );
 
     commands
-        .entity(entity)
+        .entity(wander_entity)
         .insert(steering::Wander::new(100.0, 100.0, 50.0));
 
-    // TODO: hide from wanderer
+    // hide from the wanderer
+    let entity = VehicleBundle::spawn(
+        commands,
+        random_position(rng, min, max),
+        params.vehicle_mass,
+        params.vehicle_max_speed,
+        params.vehicle_max_steering_force,
+        params.vehicle_max_turn_rate,
+        "hide",
+        Color::ORANGE,
+    );
 
-    // build a set of non-overlapping obstacles
-    let max_tries = 2000;
-    let mut obstacles = Vec::with_capacity(params.num_obstacles);
-    for _ in 0..params.num_obstacles {
-        let mut num_tries = 0;
-        let mut overlapped = true;
-        while overlapped {
-            num_tries += 1;
-            if num_tries > max_tries {
-                break;
-            }
+    commands
+        .entity(entity)
+        .insert(steering::Hide::default())
+        .insert(steering::HideTarget {
+            entity: wander_entity,
+        });
+    VehicleBundle::spawn_target_debug(commands, entity, Color::ORANGE);
 
-            let radius = rng.gen_range(params.min_obstacle_radius..=params.max_obstacle_radius);
-            let min_x = -hw + radius + params.window_border;
-            let max_x = hw - radius - params.window_border;
-            let min_y = -hh + radius + params.window_border;
-            let max_y = hh - radius - params.window_border;
-            let position = Vec2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y));
+    // leader and followers in a V formation
+    let leader_entity = VehicleBundle::spawn(
+        commands,
+        random_position(rng, min, max),
+        params.vehicle_mass,
+        params.vehicle_max_speed * 0.5,
+        params.vehicle_max_steering_force,
+        params.vehicle_max_turn_rate,
+        "leader",
+        Color::LIME_GREEN,
+    );
 
-            if !util::overlapped(
-                position,
-                radius,
-                &obstacles,
-                params.min_gap_between_obstacles,
-            ) {
-                obstacles.push((position, radius));
-                overlapped = false;
-            }
-        }
+    commands
+        .entity(leader_entity)
+        .insert(steering::Wander::new(100.0, 100.0, 50.0));
 
-        if num_tries > max_tries {
-            break;
-        }
-    }
+    let offsets = [
+        Vec2::new(-30.0, 30.0),
+        Vec2::new(-30.0, -30.0),
+        Vec2::new(-60.0, 60.0),
+        Vec2::new(-60.0, -60.0),
+    ];
+    for (idx, offset) in offsets.into_iter().enumerate() {
+        let entity = VehicleBundle::spawn(
+            commands,
+            random_position(rng, min, max),
+            params.vehicle_mass,
+            params.vehicle_max_speed,
+            params.vehicle_max_steering_force,
+            params.vehicle_max_turn_rate,
+            format!("follower {}", idx),
+            Color::DARK_GREEN,
+        );
 
-    // spawn the obstacles
-    for (position, radius) in obstacles {
-        ObstacleBundle::spawn(&mut commands, position, radius);
+        commands
+            .entity(entity)
+            .insert(steering::OffsetPursuit)
+            .insert(steering::OffsetPursuitTarget {
+                entity: leader_entity,
+                offset,
+            });
+        VehicleBundle::spawn_target_debug(commands, entity, Color::DARK_GREEN);
     }
 
-    // spawn walls
-    WallBundle::spawn(
-        &mut commands,
-        Vec2::new(-hw * 0.5, 0.0),
-        Vec2::new(0.0, 0.0),
-        Vec2::new(0.0, -hh * 0.5),
-    );
-    WallBundle::spawn(
-        &mut commands,
-        Vec2::new(hw * 0.5, 0.0),
-        Vec2::new(0.0, 0.0),
-        Vec2::new(0.0, hh * 0.5),
+    // follow a random looped path around the center
+    let num_waypoints = 7;
+    let waypoints = (0..num_waypoints)
+        .map(|idx| {
+            let angle = std::f32::consts::TAU * idx as f32 / num_waypoints as f32;
+            let distance = rng.gen_range(0.3..=0.9);
+            Vec2::new(
+                angle.cos() * max.x * distance,
+                angle.sin() * max.y * distance,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let entity = VehicleBundle::spawn(
+        commands,
+        waypoints[0],
+        params.vehicle_mass,
+        params.vehicle_max_speed,
+        params.vehicle_max_steering_force,
+        params.vehicle_max_turn_rate,
+        "follow path",
+        Color::PINK,
     );
+
+    VehicleBundle::spawn_path_debug(commands, waypoints.clone(), true, Color::PINK);
+
+    commands
+        .entity(entity)
+        .insert(steering::FollowPath)
+        .insert(steering::FollowPathTarget::new(waypoints, true));
+    VehicleBundle::spawn_target_debug(commands, entity, Color::PINK);
+}
+
+// a flock of wandering vehicles, run with --flock
+fn spawn_flock(
+    commands: &mut Commands,
+    params: &SimulationParams,
+    rng: &mut Random,
+    min: Vec2,
+    max: Vec2,
+) {
+    for idx in 0..params.num_flocking_vehicles {
+        let entity = VehicleBundle::spawn(
+            commands,
+            random_position(rng, min, max),
+            params.vehicle_mass,
+            params.vehicle_max_speed,
+            params.vehicle_max_steering_force,
+            params.vehicle_max_turn_rate,
+            format!("flock {}", idx),
+            Color::WHITE,
+        );
+
+        commands
+            .entity(entity)
+            .insert(steering::Neighbors::default())
+            .insert(steering::Separation)
+            .insert(steering::Alignment)
+            .insert(steering::Cohesion)
+            .insert(steering::Wander::new(100.0, 100.0, 50.0));
+    }
 }
 
 pub fn teardown(mut commands: Commands, entities: Query<Entity>) {
@@ -211,4 +378,5 @@ pub fn teardown(mut commands: Commands, entities: Query<Entity>) {
     }
 
     commands.remove_resource::<ClearColor>();
+    commands.remove_resource::<CellSpacePartition>();
 }
@@ -3,8 +3,10 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContext};
 use bevy_inspector_egui::WorldInspectorParams;
 
+use crate::components::steering::*;
 use crate::events::debug::*;
 use crate::resources::debug::*;
+use crate::resources::*;
 
 pub(crate) fn setup_debug(mut commands: Commands) {
     commands.insert_resource(DebugState::default());
@@ -51,6 +53,8 @@ pub fn debug_ui(
     debug_state: Res<DebugState>,
     mut context: ResMut<EguiContext>,
     mut inspector: ResMut<WorldInspectorParams>,
+    mut params: ResMut<SimulationParams>,
+    mut steering_forces: Query<&mut SteeringForces>,
     time: Res<Time>,
     diagnostics: Res<Diagnostics>,
 ) {
@@ -71,6 +75,49 @@ pub fn debug_ui(
             if ui.button("Inspector").clicked() {
                 inspector.enabled = !inspector.enabled;
             }
+
+            ui.collapsing("Steering", |ui| {
+                let mode = params.combination_mode;
+                for combination_mode in [
+                    CombinationMode::WeightedTruncatedSum,
+                    CombinationMode::PrioritizedTruncatedSum,
+                    CombinationMode::PrioritizedDithering,
+                ] {
+                    ui.radio_value(
+                        &mut params.combination_mode,
+                        combination_mode,
+                        combination_mode.get_name(),
+                    );
+                }
+
+                // switch every vehicle over to the new mode
+                if params.combination_mode != mode {
+                    info!(
+                        "switching steering combination to {}",
+                        params.combination_mode.get_name()
+                    );
+
+                    for mut forces in steering_forces.iter_mut() {
+                        forces.mode = params.combination_mode;
+                    }
+                }
+            });
+
+            ui.collapsing("Flocking", |ui| {
+                ui.add(
+                    egui::Slider::new(&mut params.separation_weight, 0.0..=1000.0)
+                        .text("Separation"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut params.alignment_weight, 0.0..=1000.0).text("Alignment"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut params.cohesion_weight, 0.0..=1000.0).text("Cohesion"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut params.view_distance, 0.0..=200.0).text("View Distance"),
+                );
+            });
         });
     });
 }
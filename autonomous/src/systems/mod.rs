@@ -3,21 +3,38 @@ pub mod physics;
 pub mod steering;
 
 use bevy::prelude::*;
+use game_ai_core::partition::*;
 
+use crate::components::obstacle::*;
 use crate::components::physics::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
 pub enum Systems {
     Physics,
+    Partition,
 
     // steering
     Avoidance,
     Pursuit,
+    Neighbors,
+    Flocking,
     Steering,
+    Combination,
+    SteeringUpdatePhysics,
+}
+
+pub fn update_partition(
+    mut partition: ResMut<CellSpacePartition>,
+    query: Query<(Entity, &Transform), With<Obstacle>>,
+) {
+    partition.clear();
+    for (entity, transform) in query.iter() {
+        partition.insert(entity, transform.translation.truncate());
+    }
 }
 
 // TODO: this should exclude entities that don't move
-pub fn wrap(window: Res<WindowDescriptor>, mut query: Query<PhysicalQueryMut>) {
+pub fn wrap(window: Res<WindowDescriptor>, mut query: Query<PhysicalQueryUpdateMut>) {
     let half_width = window.width / 2.0;
     let half_height = window.height / 2.0;
 
@@ -36,13 +53,13 @@ pub fn wrap(window: Res<WindowDescriptor>, mut query: Query<PhysicalQueryMut>) {
     }
 }
 
-pub fn facing(_time: Res<Time>, mut query: Query<PhysicalQueryMut>) {
+pub fn facing(mut query: Query<PhysicalQueryUpdateMut>) {
     for mut physical in query.iter_mut() {
-        if physical.physical.heading.length_squared() < std::f32::EPSILON {
+        if physical.physical.smoothed_heading.length_squared() < f32::EPSILON {
             continue;
         }
 
-        let angle = -physical.physical.heading.angle_between(Vec2::Y);
+        let angle = -physical.physical.smoothed_heading.angle_between(Vec2::Y);
         physical.transform.rotation = Quat::from_rotation_z(angle);
     }
 }
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::components::physics::*;
 
-pub fn update(_time: Res<Time>, mut query: Query<PhysicalQueryMut>) {
+pub fn update(_time: Res<Time>, mut query: Query<PhysicalQueryUpdateMut>) {
     for mut physical in query.iter_mut() {
         physical
             .physical
@@ -1,102 +1,200 @@
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
+use game_ai_core::partition::*;
+use game_ai_core::random::Random;
 
 use crate::bundles::vehicle::*;
 use crate::components::actor::*;
 use crate::components::obstacle::*;
 use crate::components::physics::*;
 use crate::components::steering::*;
+use crate::components::vehicle::*;
 use crate::resources::*;
 use crate::util::*;
 
-pub fn update_seek(params: Res<SimulationParams>, mut query: Query<(SeekQuery, PhysicalQueryMut)>) {
-    for (steering, mut physical) in query.iter_mut() {
-        let force = steering.steering.force(steering.target, &physical);
-        physical
-            .physical
-            .accumulate_stearing_force(force, params.seek_weight);
+pub fn update_seek(mut query: Query<(SeekQuery, PhysicalQuery, &mut SteeringForces)>) {
+    for (seek, physical, mut forces) in query.iter_mut() {
+        let force = seek.steering.force(seek.target, &physical);
+        forces.add(Behavior::Seek, force);
     }
 }
 
-pub fn update_flee(params: Res<SimulationParams>, mut query: Query<(FleeQuery, PhysicalQueryMut)>) {
-    for (steering, mut physical) in query.iter_mut() {
-        let force = steering.steering.force(steering.target, &physical);
-        physical
-            .physical
-            .accumulate_stearing_force(force, params.flee_weight);
+pub fn update_flee(mut query: Query<(FleeQuery, PhysicalQuery, &mut SteeringForces)>) {
+    for (flee, physical, mut forces) in query.iter_mut() {
+        let force = flee.steering.force(flee.target, &physical);
+        forces.add(Behavior::Flee, force);
     }
 }
 
-pub fn update_arrive(
-    params: Res<SimulationParams>,
-    mut query: Query<(ArriveQuery, PhysicalQueryMut)>,
-) {
-    for (steering, mut physical) in query.iter_mut() {
-        let force = steering.steering.force(steering.target, &physical);
-        physical
-            .physical
-            .accumulate_stearing_force(force, params.arrive_weight);
+pub fn update_arrive(mut query: Query<(ArriveQuery, PhysicalQuery, &mut SteeringForces)>) {
+    for (arrive, physical, mut forces) in query.iter_mut() {
+        let force = arrive.steering.force(arrive.target, &physical);
+        forces.add(Behavior::Arrive, force);
     }
 }
 
 pub fn update_pursuit(
-    params: Res<SimulationParams>,
-    mut pursuers: Query<(Entity, PursuitQuery)>,
-    mut entities: Query<PhysicalQueryMut>,
+    mut pursuers: Query<(Entity, PursuitQuery, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
 ) {
-    for (entity, steering) in pursuers.iter_mut() {
-        let force = steering
-            .steering
-            .force(entity, steering.target, &mut entities);
-        if let Ok(mut physical) = entities.get_mut(entity) {
-            physical
-                .physical
-                .accumulate_stearing_force(force, params.evade_weight);
-        }
+    for (entity, pursuit, mut forces) in pursuers.iter_mut() {
+        let force = pursuit.steering.force(entity, pursuit.target, &entities);
+        forces.add(Behavior::Pursuit, force);
     }
 }
 
 pub fn update_evade(
-    params: Res<SimulationParams>,
-    mut evaders: Query<(Entity, EvadeQuery)>,
-    mut entities: Query<PhysicalQueryMut>,
+    mut evaders: Query<(Entity, EvadeQuery, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
 ) {
-    for (entity, steering) in evaders.iter_mut() {
-        let force = steering
+    for (entity, evade, mut forces) in evaders.iter_mut() {
+        let force = evade.steering.force(entity, evade.target, &entities);
+        forces.add(Behavior::Evade, force);
+    }
+}
+
+pub fn update_offset_pursuit(
+    mut pursuers: Query<(
+        Entity,
+        OffsetPursuitQuery,
+        &mut Steering,
+        &mut SteeringForces,
+    )>,
+    entities: Query<PhysicalQuery>,
+) {
+    for (entity, offset_pursuit, mut steering, mut forces) in pursuers.iter_mut() {
+        let [pursuer, leader] = entities.many([entity, offset_pursuit.target.entity]);
+
+        let offset_position = offset_pursuit
             .steering
-            .force(entity, steering.target, &mut entities);
-        if let Ok(mut physical) = entities.get_mut(entity) {
-            physical
-                .physical
-                .accumulate_stearing_force(force, params.evade_weight);
-        }
+            .get_offset_position(offset_pursuit.target, &leader);
+        steering.target = offset_position;
+
+        let force = offset_pursuit
+            .steering
+            .force(offset_position, &pursuer, &leader);
+        forces.add(Behavior::OffsetPursuit, force);
     }
 }
 
 pub fn update_wander(
+    mut random: ResMut<Random>,
+    mut query: Query<(&mut Wander, PhysicalQuery, &mut SteeringForces)>,
+) {
+    for (mut wander, physical, mut forces) in query.iter_mut() {
+        let force = wander.force(&physical, random.stream("wander"));
+        forces.add(Behavior::Wander, force);
+    }
+}
+
+// tag the vehicles within view distance of each flocking vehicle
+pub fn update_neighbors(
     params: Res<SimulationParams>,
-    mut query: Query<(&mut Wander, PhysicalQueryMut)>,
+    partition: Res<CellSpacePartition>,
+    mut query: Query<(Entity, &Transform, &mut Neighbors)>,
+    vehicles: Query<(), With<Vehicle>>,
+) {
+    for (entity, transform, mut neighbors) in query.iter_mut() {
+        let translation = transform.translation.truncate();
+
+        neighbors.entities.clear();
+        neighbors.entities.extend(
+            partition
+                .query(translation, params.view_distance)
+                .filter(|vehicle| *vehicle != entity && vehicles.get(*vehicle).is_ok()),
+        );
+    }
+}
+
+pub fn update_separation(
+    mut query: Query<(Entity, &Separation, &Neighbors, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
 ) {
-    for (mut steering, mut physical) in query.iter_mut() {
-        let force = steering.force(&physical);
-        physical
-            .physical
-            .accumulate_stearing_force(force, params.wander_weight);
+    for (entity, separation, neighbors, mut forces) in query.iter_mut() {
+        if let Ok(physical) = entities.get(entity) {
+            let force = separation.force(&physical, neighbors, &entities);
+            forces.add(Behavior::Separation, force);
+        }
     }
 }
 
-// TODO: interpose
+pub fn update_alignment(
+    mut query: Query<(Entity, &Alignment, &Neighbors, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
+) {
+    for (entity, alignment, neighbors, mut forces) in query.iter_mut() {
+        if let Ok(physical) = entities.get(entity) {
+            let force = alignment.force(&physical, neighbors, &entities);
+            forces.add(Behavior::Alignment, force);
+        }
+    }
+}
 
-// TODO: path follow
+pub fn update_cohesion(
+    mut query: Query<(Entity, &Cohesion, &Neighbors, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
+) {
+    for (entity, cohesion, neighbors, mut forces) in query.iter_mut() {
+        if let Ok(physical) = entities.get(entity) {
+            let force = cohesion.force(&physical, neighbors, &entities);
+            forces.add(Behavior::Cohesion, force);
+        }
+    }
+}
+
+pub fn update_interpose(
+    mut query: Query<(Entity, InterposeQuery, &mut Steering, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
+) {
+    for (entity, interpose, mut steering, mut forces) in query.iter_mut() {
+        let [physical, a, b] = entities.many([entity, interpose.target.a, interpose.target.b]);
+
+        let target_position = interpose.steering.get_target_position(&physical, &a, &b);
+        steering.target = target_position;
+
+        let force = interpose.steering.force(target_position, &physical);
+        forces.add(Behavior::Interpose, force);
+    }
+}
+
+pub fn update_follow_path(
+    params: Res<SimulationParams>,
+    mut query: Query<(
+        FollowPathQueryMut,
+        PhysicalQuery,
+        &mut Steering,
+        &mut SteeringForces,
+    )>,
+) {
+    for (mut follow_path, physical, mut steering, mut forces) in query.iter_mut() {
+        follow_path.target.path.update(
+            physical.transform.translation.truncate(),
+            params.waypoint_seek_distance,
+        );
+
+        if let Some(waypoint) = follow_path.target.path.current_waypoint() {
+            steering.target = waypoint;
+        }
+
+        let force = follow_path.steering.force(&follow_path.target, &physical);
+        forces.add(Behavior::FollowPath, force);
+    }
+}
 
 pub fn update_obstacle_avoidance(
     params: Res<SimulationParams>,
-    mut query: Query<(Entity, &mut Physical, &mut ObstacleAvoidance, &Children)>,
-    obstacles: Query<Entity, With<Obstacle>>,
+    mut query: Query<(
+        Entity,
+        &Physical,
+        &mut SteeringForces,
+        &mut ObstacleAvoidance,
+        &Children,
+    )>,
+    partition: Res<CellSpacePartition>,
     actors: Query<(ActorQuery, &Transform)>,
     mut shapes: Query<&mut Path, With<ObstacleAvoidanceDebug>>,
 ) {
-    for (entity, mut physical, mut avoidance, children) in query.iter_mut() {
+    for (entity, physical, mut forces, mut avoidance, children) in query.iter_mut() {
         avoidance.box_length = params.min_detection_box_length
             + (physical.speed() / physical.max_speed) * params.min_detection_box_length;
 
@@ -113,10 +211,14 @@ pub fn update_obstacle_avoidance(
             }
         }
 
+        // only obstacles that could possibly be in range
+        let position = actors.get(entity).unwrap().1.translation.truncate();
+        let range = avoidance.box_length + params.max_obstacle_radius.max(VEHICLE_RADIUS);
+
         // find the closest obstacle for avoidance
         let mut closest_obstacle = None;
         let mut dist_to_closest = f32::MAX;
-        for obstacle in obstacles.iter() {
+        for obstacle in partition.query(position, range) {
             // ignore ourself
             if obstacle == entity {
                 continue;
@@ -201,11 +303,9 @@ pub fn update_obstacle_avoidance(
 
                 let force = Vec2::new(x, y);
 
-                let heading = physical.heading;
-                let side = physical.side;
-                physical.accumulate_stearing_force(
-                    vector_to_world_space(force, heading, side),
-                    params.obstacle_avoidance_weight,
+                forces.add(
+                    Behavior::ObstacleAvoidance,
+                    vector_to_world_space(force, physical.heading, physical.side),
                 );
             }
         }
@@ -214,10 +314,18 @@ pub fn update_obstacle_avoidance(
 
 pub fn update_wall_avoidance(
     params: Res<SimulationParams>,
-    mut query: Query<(PhysicalQueryMut, &mut WallAvoidance, &Name), Without<Wall>>,
+    mut query: Query<
+        (
+            PhysicalQuery,
+            &mut SteeringForces,
+            &mut WallAvoidance,
+            &Name,
+        ),
+        Without<Wall>,
+    >,
     walls: Query<WallQuery>,
 ) {
-    for (mut physical, mut avoidance, name) in query.iter_mut() {
+    for (physical, mut forces, mut avoidance, name) in query.iter_mut() {
         let position = physical.transform.translation.truncate();
 
         avoidance.create_feelers(
@@ -261,10 +369,51 @@ pub fn update_wall_avoidance(
             }
         }
 
-        physical
-            .physical
-            .accumulate_stearing_force(steering_force, params.wall_avoidance_weight);
+        forces.add(Behavior::WallAvoidance, steering_force);
+    }
+}
+
+pub fn update_combination(
+    params: Res<SimulationParams>,
+    mut random: ResMut<Random>,
+    mut query: Query<(&mut SteeringForces, &mut Steering, &Physical)>,
+) {
+    for (mut forces, mut steering, physical) in query.iter_mut() {
+        forces.combine(
+            &mut steering,
+            physical,
+            random.stream("combination"),
+            |behavior| params.get_weight(behavior),
+            |behavior| params.get_probability(behavior),
+        );
+    }
+}
+
+pub fn update_hide(
+    mut query: Query<(Entity, HideQuery, &mut Steering, &mut SteeringForces)>,
+    entities: Query<PhysicalQuery>,
+    obstacles: Query<(&Actor, &Transform), (With<Obstacle>, Without<Vehicle>)>,
+) {
+    for (entity, hide, mut steering, mut forces) in query.iter_mut() {
+        let [physical, hunter] = entities.many([entity, hide.target.entity]);
+
+        let hiding_spot = hide
+            .steering
+            .get_hiding_spot(&physical, &hunter, obstacles.iter());
+        steering.target = hiding_spot.unwrap_or(steering.target);
+
+        let force = hide.steering.force(hiding_spot, &physical, &hunter);
+        forces.add(Behavior::Hide, force);
     }
 }
 
-// TODO: hide
+pub fn update_debug(
+    mut debug: Query<(&SteeringTargetMarker, &mut Transform)>,
+    steering: Query<&Steering>,
+) {
+    for (debug, mut transform) in debug.iter_mut() {
+        if let Ok(steering) = steering.get(debug.owner) {
+            transform.translation = steering.target.extend(transform.translation.z);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// compares cell-space partition neighbor queries against brute force
+// cargo bench -p game-ai-core --bench partition
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use game_ai_core::partition::*;
+
+const WORLD_SIZE: f32 = 1000.0;
+const NUM_CELLS: usize = 20;
+const QUERY_RADIUS: f32 = 50.0;
+const ITERATIONS: usize = 10;
+
+// simple xorshift so that runs are repeatable without pulling in rand
+struct Positions(u32);
+
+impl Iterator for Positions {
+    type Item = Vec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next = || {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0 as f32 / u32::MAX as f32
+        };
+
+        Some(Vec2::new(
+            (next() - 0.5) * WORLD_SIZE,
+            (next() - 0.5) * WORLD_SIZE,
+        ))
+    }
+}
+
+fn brute_force(entities: &[(Entity, Vec2)]) -> usize {
+    let radius_squared = QUERY_RADIUS * QUERY_RADIUS;
+
+    let mut count = 0;
+    for (entity, position) in entities {
+        count += entities
+            .iter()
+            .filter(|(e, p)| e != entity && p.distance_squared(*position) <= radius_squared)
+            .count();
+    }
+    count
+}
+
+fn partitioned(partition: &mut CellSpacePartition, entities: &[(Entity, Vec2)]) -> usize {
+    // include the rebuild, it happens every physics step
+    partition.clear();
+    for (entity, position) in entities {
+        partition.insert(*entity, *position);
+    }
+
+    let mut count = 0;
+    for (entity, position) in entities {
+        count += partition
+            .query(*position, QUERY_RADIUS)
+            .filter(|e| e != entity)
+            .count();
+    }
+    count
+}
+
+fn time(f: &mut impl FnMut() -> usize) -> (usize, Duration) {
+    let now = Instant::now();
+    let mut count = 0;
+    for _ in 0..ITERATIONS {
+        count = f();
+    }
+    (count, now.elapsed() / ITERATIONS as u32)
+}
+
+fn main() {
+    let half = Vec2::splat(WORLD_SIZE / 2.0);
+
+    for num_entities in [100, 500, 1000, 2000] {
+        let entities = Positions(0x2545f491)
+            .take(num_entities)
+            .enumerate()
+            .map(|(idx, position)| (Entity::from_raw(idx as u32), position))
+            .collect::<Vec<_>>();
+
+        let mut partition = CellSpacePartition::new(-half, half, NUM_CELLS, NUM_CELLS);
+
+        let (brute_count, brute_time) = time(&mut || brute_force(&entities));
+        let (partition_count, partition_time) =
+            time(&mut || partitioned(&mut partition, &entities));
+
+        assert_eq!(brute_count, partition_count);
+
+        println!(
+            "{:>5} entities: brute force {:>10.3?}, partition {:>10.3?} ({:.1}x)",
+            num_entities,
+            brute_time,
+            partition_time,
+            brute_time.as_secs_f64() / partition_time.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,77 @@
+// minimal command line parsing shared by the examples
+// bad arguments are programmer / user errors, so they panic
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+// --<name> <value>
+pub fn parse_arg<T>(name: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    parse_arg_from(std::env::args().skip(1), name)
+}
+
+// --<name>
+pub fn has_arg(name: &str) -> bool {
+    std::env::args().skip(1).any(|arg| arg == name)
+}
+
+// --seed <seed>
+pub fn parse_seed() -> Option<u64> {
+    parse_arg("--seed")
+}
+
+fn parse_arg_from<T>(args: impl IntoIterator<Item = String>, name: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            let value = args
+                .next()
+                .unwrap_or_else(|| panic!("{} requires a value", name));
+
+            return Some(
+                value
+                    .parse()
+                    .unwrap_or_else(|err| panic!("invalid {} value: {:?}", name, err)),
+            );
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_arg() {
+        assert_eq!(
+            parse_arg_from::<u64>(args(&["--headless", "--seed", "42"]), "--seed"),
+            Some(42)
+        );
+        assert_eq!(parse_arg_from::<u64>(args(&["--headless"]), "--seed"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "--seed requires a value")]
+    fn test_missing_value() {
+        parse_arg_from::<u64>(args(&["--seed"]), "--seed");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid --seed value")]
+    fn test_invalid_value() {
+        parse_arg_from::<u64>(args(&["--seed", "abc"]), "--seed");
+    }
+}
@@ -1,6 +1,5 @@
 // modified from https://github.com/kokounet/bevy_time/blob/main/src/cooldown.rs
 // TODO: I'm pretty sure this can now be done with Timer
-#![allow(dead_code)]
 
 use std::time::Duration;
 
@@ -16,12 +15,7 @@ pub struct Cooldown {
 
 impl Cooldown {
     pub fn new(duration: Duration) -> Self {
-        Self {
-            duration: duration.as_secs_f32(),
-            stopwatch: Default::default(),
-            available: true,
-            just_available: true,
-        }
+        Self::from_seconds(duration.as_secs_f32())
     }
 
     pub fn from_seconds(duration: f32) -> Self {
@@ -50,12 +44,12 @@ impl Cooldown {
     }
 
     #[inline]
-    pub fn get_elapsed(&self) -> f32 {
+    pub fn elapsed(&self) -> f32 {
         self.stopwatch.elapsed().as_secs_f32()
     }
 
     #[inline]
-    pub fn get_duration(&self) -> f32 {
+    pub fn duration(&self) -> f32 {
         self.duration
     }
 
@@ -71,7 +65,7 @@ impl Cooldown {
         }
 
         self.stopwatch.tick(Duration::from_secs_f32(delta));
-        if self.get_elapsed() >= self.get_duration() {
+        if self.elapsed() >= self.duration() {
             self.reset();
         }
 
@@ -84,3 +78,49 @@ impl Cooldown {
         self.just_available = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_until_started() {
+        let mut cooldown = Cooldown::from_seconds(1.0);
+        assert!(cooldown.is_available());
+        assert!(cooldown.is_just_available());
+
+        // only just available for the first tick
+        cooldown.tick(0.1);
+        assert!(cooldown.is_available());
+        assert!(!cooldown.is_just_available());
+
+        // and ticking while available doesn't count towards the duration
+        assert_eq!(cooldown.elapsed(), 0.0);
+    }
+
+    #[test]
+    fn test_cools_down() {
+        let mut cooldown = Cooldown::new(Duration::from_secs(1));
+        cooldown.start();
+        assert!(!cooldown.is_available());
+
+        cooldown.tick(0.5);
+        assert!(!cooldown.is_available());
+
+        cooldown.tick(0.5);
+        assert!(cooldown.is_available());
+        assert!(cooldown.is_just_available());
+        assert_eq!(cooldown.elapsed(), 0.0);
+    }
+
+    #[test]
+    fn test_set_duration() {
+        let mut cooldown = Cooldown::from_seconds(10.0);
+        cooldown.set_duration(0.5);
+        assert_eq!(cooldown.duration(), 0.5);
+
+        cooldown.start();
+        cooldown.tick(0.5);
+        assert!(cooldown.is_available());
+    }
+}
@@ -0,0 +1,24 @@
+// shared between the examples
+
+pub mod args;
+#[cfg(feature = "bevy")]
+pub mod cooldown;
+#[cfg(feature = "bevy")]
+pub mod messaging;
+#[cfg(feature = "bevy")]
+pub mod partition;
+#[cfg(feature = "bevy")]
+pub mod path;
+#[cfg(feature = "bevy")]
+pub mod physics;
+#[cfg(feature = "bevy")]
+pub mod plugins;
+pub mod random;
+#[cfg(feature = "bevy")]
+pub mod steering;
+#[cfg(feature = "bevy")]
+pub mod systems;
+#[cfg(feature = "bevy")]
+pub mod util;
+#[cfg(feature = "bevy")]
+pub mod wall;
@@ -0,0 +1,252 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+
+pub trait MessageEvent: Eq {}
+
+#[derive(Debug)]
+pub struct DispatchedMessageEvent<T>
+where
+    T: MessageEvent,
+{
+    // None is a broadcast
+    pub receiver: Option<Entity>,
+    pub message: T,
+}
+
+impl<T> DispatchedMessageEvent<T>
+where
+    T: MessageEvent,
+{
+    pub fn is_for(&self, entity: Entity) -> bool {
+        self.receiver.is_none() || self.receiver == Some(entity)
+    }
+}
+
+#[derive(Debug)]
+struct Telegram<T>
+where
+    T: MessageEvent,
+{
+    dispatch_time: i64,
+
+    // dispatch order, breaks ties between telegrams with the same dispatch time
+    sequence: u64,
+
+    pub receiver: Option<Entity>,
+
+    pub message: T,
+}
+
+impl<T> Eq for Telegram<T> where T: MessageEvent {}
+
+impl<T> PartialEq for Telegram<T>
+where
+    T: MessageEvent,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.dispatch_time == other.dispatch_time && self.sequence == other.sequence
+    }
+}
+
+impl<T> PartialOrd for Telegram<T>
+where
+    T: MessageEvent,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Telegram<T>
+where
+    T: MessageEvent,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap, so this is reversed
+        // to pop the earliest telegram first,
+        // and the first dispatched of those with the same dispatch time
+        other
+            .dispatch_time
+            .cmp(&self.dispatch_time)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl<T> Telegram<T>
+where
+    T: MessageEvent,
+{
+    fn new(dispatch_time: i64, sequence: u64, receiver: Option<Entity>, message: T) -> Self {
+        Self {
+            dispatch_time,
+            sequence,
+            receiver,
+            message,
+        }
+    }
+}
+
+pub struct MessageDispatcher<T>
+where
+    T: MessageEvent,
+{
+    queue: BinaryHeap<Telegram<T>>,
+    next_sequence: u64,
+
+    // milliseconds, as of the last dispatch
+    // messages are deferred relative to this
+    now: i64,
+}
+
+impl<T> Default for MessageDispatcher<T>
+where
+    T: MessageEvent,
+{
+    fn default() -> Self {
+        Self {
+            queue: BinaryHeap::default(),
+            next_sequence: 0,
+            now: 0,
+        }
+    }
+}
+
+impl<T> MessageDispatcher<T>
+where
+    T: MessageEvent + Send + Sync + 'static,
+{
+    // now is the time in seconds, usually Time::seconds_since_startup()
+    pub fn dispatch_deferred_messages(
+        &mut self,
+        now: f64,
+        message_events: &mut EventWriter<DispatchedMessageEvent<T>>,
+    ) {
+        self.now = (now * 1000.0) as i64;
+
+        while let Some(telegram) = self.pop_due(self.now) {
+            self.discharge(telegram, message_events);
+        }
+    }
+
+    fn pop_due(&mut self, now: i64) -> Option<Telegram<T>> {
+        if self.queue.peek()?.dispatch_time > now {
+            return None;
+        }
+
+        self.queue.pop()
+    }
+
+    fn discharge(
+        &self,
+        telegram: Telegram<T>,
+        message_events: &mut EventWriter<DispatchedMessageEvent<T>>,
+    ) {
+        message_events.send(DispatchedMessageEvent {
+            receiver: telegram.receiver,
+            message: telegram.message,
+        });
+    }
+
+    // a None receiver is broadcast to every receiver of the message type
+    pub fn dispatch_message(&mut self, receiver: Option<Entity>, message: T) {
+        // we always defer so that entities sending messages (as events)
+        // in response to events can work
+        self.defer_dispatch_message(receiver, message, 0.0);
+    }
+
+    pub fn defer_dispatch_message(
+        &mut self,
+        receiver: Option<Entity>,
+        message: T,
+        delay_seconds: f64,
+    ) {
+        self.push(
+            self.now + (delay_seconds * 1000.0) as i64,
+            receiver,
+            message,
+        );
+    }
+
+    fn push(&mut self, dispatch_time: i64, receiver: Option<Entity>, message: T) {
+        let telegram = Telegram::new(dispatch_time, self.next_sequence, receiver, message);
+        self.next_sequence += 1;
+
+        self.queue.push(telegram);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct TestMessage(usize);
+
+    impl MessageEvent for TestMessage {}
+
+    fn messages(dispatcher: &mut MessageDispatcher<TestMessage>, now: i64) -> Vec<usize> {
+        std::iter::from_fn(|| dispatcher.pop_due(now))
+            .map(|telegram| telegram.message.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_pops_earliest_first() {
+        let mut dispatcher = MessageDispatcher::default();
+
+        dispatcher.push(300, None, TestMessage(3));
+        dispatcher.push(100, None, TestMessage(1));
+        dispatcher.push(200, None, TestMessage(2));
+
+        assert_eq!(messages(&mut dispatcher, 150), vec![1]);
+        assert_eq!(messages(&mut dispatcher, 300), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_same_time_is_fifo() {
+        let mut dispatcher = MessageDispatcher::default();
+
+        for i in 0..10 {
+            dispatcher.push(100, None, TestMessage(i));
+        }
+
+        assert_eq!(messages(&mut dispatcher, 100), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_deferred_from_last_dispatch() {
+        let mut dispatcher = MessageDispatcher {
+            now: 1000,
+            ..Default::default()
+        };
+
+        dispatcher.defer_dispatch_message(None, TestMessage(1), 0.5);
+        dispatcher.dispatch_message(None, TestMessage(0));
+
+        assert_eq!(messages(&mut dispatcher, 1000), vec![0]);
+        assert!(messages(&mut dispatcher, 1499).is_empty());
+        assert_eq!(messages(&mut dispatcher, 1500), vec![1]);
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let player = Entity::from_raw(1);
+        let other = Entity::from_raw(2);
+
+        let direct = DispatchedMessageEvent {
+            receiver: Some(player),
+            message: TestMessage(0),
+        };
+        assert!(direct.is_for(player));
+        assert!(!direct.is_for(other));
+
+        let broadcast = DispatchedMessageEvent {
+            receiver: None,
+            message: TestMessage(0),
+        };
+        assert!(broadcast.is_for(player));
+        assert!(broadcast.is_for(other));
+    }
+}
@@ -0,0 +1,130 @@
+// uniform grid over the world bounds for fast neighbor queries
+// rebuilt from scratch every physics step
+
+use bevy::prelude::*;
+
+#[derive(Debug, Clone)]
+pub struct CellSpacePartition {
+    min: Vec2,
+    cell_size: Vec2,
+    num_cells_x: usize,
+    num_cells_y: usize,
+
+    cells: Vec<Vec<(Entity, Vec2)>>,
+}
+
+impl CellSpacePartition {
+    pub fn new(min: Vec2, max: Vec2, num_cells_x: usize, num_cells_y: usize) -> Self {
+        let num_cells_x = num_cells_x.max(1);
+        let num_cells_y = num_cells_y.max(1);
+
+        Self {
+            min,
+            cell_size: (max - min) / Vec2::new(num_cells_x as f32, num_cells_y as f32),
+            num_cells_x,
+            num_cells_y,
+            cells: vec![vec![]; num_cells_x * num_cells_y],
+        }
+    }
+
+    #[inline]
+    pub fn num_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn clear(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.clear();
+        }
+    }
+
+    // positions outside of the world bounds go in the nearest edge cell
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        let (x, y) = self.cell_coords(position);
+        let idx = self.cell_index(x, y);
+        self.cells[idx].push((entity, position));
+    }
+
+    // entities within radius of the position
+    pub fn query(&self, position: Vec2, radius: f32) -> impl Iterator<Item = Entity> + '_ {
+        let (min_x, min_y) = self.cell_coords(position - Vec2::splat(radius));
+        let (max_x, max_y) = self.cell_coords(position + Vec2::splat(radius));
+        let radius_squared = radius * radius;
+
+        (min_y..=max_y)
+            .flat_map(move |y| (min_x..=max_x).map(move |x| self.cell_index(x, y)))
+            .flat_map(move |idx| self.cells[idx].iter())
+            .filter(move |(_, p)| p.distance_squared(position) <= radius_squared)
+            .map(|(entity, _)| *entity)
+    }
+
+    fn cell_coords(&self, position: Vec2) -> (usize, usize) {
+        let cell = ((position - self.min) / self.cell_size).floor();
+
+        (
+            (cell.x.max(0.0) as usize).min(self.num_cells_x - 1),
+            (cell.y.max(0.0) as usize).min(self.num_cells_y - 1),
+        )
+    }
+
+    #[inline]
+    fn cell_index(&self, x: usize, y: usize) -> usize {
+        y * self.num_cells_x + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partition() -> CellSpacePartition {
+        CellSpacePartition::new(Vec2::new(-50.0, -50.0), Vec2::new(50.0, 50.0), 10, 10)
+    }
+
+    fn query(partition: &CellSpacePartition, position: Vec2, radius: f32) -> Vec<Entity> {
+        let mut entities = partition.query(position, radius).collect::<Vec<_>>();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn test_query_within_radius() {
+        let mut partition = partition();
+        partition.insert(Entity::from_raw(0), Vec2::new(0.0, 0.0));
+        partition.insert(Entity::from_raw(1), Vec2::new(12.0, 0.0));
+        partition.insert(Entity::from_raw(2), Vec2::new(-5.0, 5.0));
+        partition.insert(Entity::from_raw(3), Vec2::new(40.0, 40.0));
+
+        assert_eq!(
+            query(&partition, Vec2::ZERO, 10.0),
+            vec![Entity::from_raw(0), Entity::from_raw(2)]
+        );
+        assert_eq!(
+            query(&partition, Vec2::new(6.0, 0.0), 6.0),
+            vec![Entity::from_raw(0), Entity::from_raw(1)]
+        );
+        assert!(query(&partition, Vec2::new(-40.0, -40.0), 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_out_of_bounds_is_clamped() {
+        let mut partition = partition();
+        partition.insert(Entity::from_raw(0), Vec2::new(100.0, -100.0));
+
+        assert_eq!(
+            query(&partition, Vec2::new(95.0, -95.0), 10.0),
+            vec![Entity::from_raw(0)]
+        );
+        assert!(query(&partition, Vec2::new(45.0, -45.0), 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut partition = partition();
+        partition.insert(Entity::from_raw(0), Vec2::ZERO);
+        partition.clear();
+
+        assert_eq!(partition.num_cells(), 100);
+        assert!(query(&partition, Vec2::ZERO, 10.0).is_empty());
+    }
+}
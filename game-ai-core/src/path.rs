@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+
+use crate::physics::Kinematic;
+use crate::steering::{arrive_force, seek_force, Deceleration};
+
+// waypoints to follow, optionally looping back to the start
+#[derive(Debug, Default, Clone, Inspectable)]
+pub struct WaypointPath {
+    pub waypoints: Vec<Vec2>,
+    pub looped: bool,
+
+    current: usize,
+}
+
+impl WaypointPath {
+    pub fn new(waypoints: Vec<Vec2>, looped: bool) -> Self {
+        Self {
+            waypoints,
+            looped,
+            current: 0,
+        }
+    }
+
+    pub fn current_waypoint(&self) -> Option<Vec2> {
+        self.waypoints.get(self.current).copied()
+    }
+
+    // the current waypoint and everything after it
+    pub fn remaining_waypoints(&self) -> &[Vec2] {
+        &self.waypoints[self.current.min(self.waypoints.len())..]
+    }
+
+    pub fn is_last_waypoint(&self) -> bool {
+        !self.looped && self.current + 1 >= self.waypoints.len()
+    }
+
+    pub fn push(&mut self, waypoint: Vec2) {
+        self.waypoints.push(waypoint);
+    }
+
+    // moves on to the next waypoint once the current one is within seek distance
+    pub fn update(&mut self, position: Vec2, seek_distance: f32) {
+        if self.is_last_waypoint() {
+            return;
+        }
+
+        if let Some(waypoint) = self.current_waypoint() {
+            if position.distance_squared(waypoint) < seek_distance * seek_distance {
+                self.current = (self.current + 1) % self.waypoints.len();
+            }
+        }
+    }
+
+    // seeks each waypoint in turn, arriving at the last one unless the path loops
+    pub fn force(&self, transform: &Transform, physical: &impl Kinematic) -> Vec2 {
+        match self.current_waypoint() {
+            Some(waypoint) if self.is_last_waypoint() => {
+                arrive_force(waypoint, transform, physical, Deceleration::Normal)
+            }
+            Some(waypoint) => seek_force(waypoint, transform, physical),
+            None => Vec2::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::physics::Physical;
+
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let mut path = WaypointPath::new(vec![Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0)], false);
+        assert!(!path.is_last_waypoint());
+
+        path.update(Vec2::ZERO, 2.0);
+        assert_eq!(path.current_waypoint(), Some(Vec2::new(10.0, 0.0)));
+
+        path.update(Vec2::new(9.0, 0.0), 2.0);
+        assert_eq!(path.current_waypoint(), Some(Vec2::new(20.0, 0.0)));
+        assert_eq!(path.remaining_waypoints(), &[Vec2::new(20.0, 0.0)]);
+        assert!(path.is_last_waypoint());
+
+        // stays on the last waypoint
+        path.update(Vec2::new(20.0, 0.0), 2.0);
+        assert_eq!(path.current_waypoint(), Some(Vec2::new(20.0, 0.0)));
+    }
+
+    #[test]
+    fn test_looped() {
+        let mut path = WaypointPath::new(vec![Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0)], true);
+
+        path.update(Vec2::new(10.0, 0.0), 2.0);
+        path.update(Vec2::new(20.0, 0.0), 2.0);
+        assert_eq!(path.current_waypoint(), Some(Vec2::new(10.0, 0.0)));
+        assert!(!path.is_last_waypoint());
+    }
+
+    #[test]
+    fn test_force() {
+        let physical = Physical {
+            max_speed: 10.0,
+            ..Default::default()
+        };
+        let transform = Transform::default();
+
+        // seeks at full speed towards waypoints along the way
+        let mut path = WaypointPath::new(vec![Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0)], false);
+        assert_eq!(path.force(&transform, &physical), Vec2::new(10.0, 0.0));
+
+        // but slows down for the last one
+        path.update(Vec2::new(1.0, 0.0), 2.0);
+        let force = path.force(&transform, &physical);
+        assert!(force.length() < 10.0);
+
+        assert_eq!(
+            WaypointPath::default().force(&transform, &physical),
+            Vec2::ZERO
+        );
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::VecDeque;
+
+use bevy::ecs::query::WorldQuery;
+use bevy::math::Mat2;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+
+use crate::util::*;
+
+// 50hz, the same as Unity
+pub const PHYSICS_STEP: f32 = 0.02;
+
+// what steering needs to know about a physical body,
+// so that it works with bodies that don't use Physical
+pub trait Kinematic {
+    fn velocity(&self) -> Vec2;
+    fn max_speed(&self) -> f32;
+    fn max_force(&self) -> f32;
+}
+
+#[derive(Debug, Component, Inspectable)]
+pub struct Physical {
+    pub acceleration: Vec2,
+    pub velocity: Vec2,
+
+    // local coordinate system
+    pub heading: Vec2,
+    pub side: Vec2,
+
+    // heading averaged over the last few updates, for rendering
+    pub smoothed_heading: Vec2,
+    // 0 disables smoothing
+    pub heading_smoothing_samples: usize,
+    #[inspectable(ignore)]
+    pub heading_history: VecDeque<Vec2>,
+
+    pub mass: f32,
+    pub max_speed: f32,
+    pub max_force: f32,
+    // radians per second
+    pub max_turn_rate: f32,
+
+    // if set, velocity can only rotate by max_turn_rate per second
+    pub limit_turn_rate: bool,
+}
+
+impl Default for Physical {
+    fn default() -> Self {
+        let heading = Vec2::new(0.0, 1.0);
+        let side = heading.perp();
+
+        Self {
+            acceleration: Vec2::default(),
+            velocity: Vec2::default(),
+            heading,
+            side,
+
+            smoothed_heading: heading,
+            heading_smoothing_samples: 0,
+            heading_history: VecDeque::new(),
+
+            mass: 1.0,
+            max_speed: f32::MAX,
+            max_force: f32::MAX,
+            max_turn_rate: std::f32::consts::PI,
+
+            limit_turn_rate: false,
+        }
+    }
+}
+
+impl Kinematic for Physical {
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    fn max_speed(&self) -> f32 {
+        self.max_speed
+    }
+
+    fn max_force(&self) -> f32 {
+        self.max_force
+    }
+}
+
+impl Physical {
+    pub fn speed(&self) -> f32 {
+        self.velocity.length()
+    }
+
+    pub fn stop(&mut self) {
+        self.acceleration = Vec2::ZERO;
+        self.velocity = Vec2::ZERO;
+    }
+
+    pub fn teleport(&mut self, transform: &mut Transform, position: Vec2) {
+        transform.translation = position.extend(transform.translation.z);
+
+        self.acceleration = Vec2::ZERO;
+        self.velocity = Vec2::ZERO;
+
+        self.heading_history.clear();
+        self.smoothed_heading = self.heading;
+    }
+
+    // turns towards the target, assumes it's called once per physics step
+    pub fn track(&mut self, transform: &Transform, target: Vec2) {
+        let position = transform.translation.truncate();
+        let to_target = target - position;
+
+        let dot = self.heading.dot(to_target).clamp(-1.0, 1.0);
+        let mut angle = dot.acos();
+
+        // if we're already facing the target, we're done
+        if angle < 0.00001 {
+            return;
+        }
+
+        // clamp the turn rate
+        let max_angle = self.max_turn_rate * PHYSICS_STEP;
+        if angle > max_angle {
+            angle = max_angle;
+        }
+
+        let rotation = Mat2::from_angle(angle * self.heading.sign(to_target));
+        self.heading = rotation.mul_vec2(self.heading);
+        self.velocity = rotation.mul_vec2(self.velocity);
+
+        self.side = self.heading.perp();
+
+        self.update_smoothed_heading();
+    }
+
+    // where we'll be after dt, slowing down under constant friction
+    pub fn future_position(&self, friction: f32, transform: &Transform, dt: f32) -> Vec2 {
+        // x = ut + 1/2(-a)t^2
+        // x = distance, a = friction, u = starting velocity
+
+        let ut = self.velocity * dt;
+        let half_a_t_squared = 0.5 * -friction * dt * dt;
+
+        let scalar_to_vector = half_a_t_squared * self.velocity.normalize_or_zero();
+
+        transform.translation.truncate() + ut + scalar_to_vector
+    }
+
+    // how long it takes to get from a to b after being hit with force,
+    // slowing down under constant friction
+    // returns a negative time if b can't be reached
+    pub fn time_to_cover_distance(&self, friction: f32, a: Vec2, b: Vec2, force: f32) -> f32 {
+        let speed = force / self.mass;
+
+        // v^2 = u^2 + 2(-a)x
+
+        let distance = a.distance(b);
+        let term = speed * speed + 2.0 * distance * -friction;
+
+        // if u^2 + 2(-a)x is negative, then we can't reach point b
+        if term <= 0.0 {
+            return -1.0;
+        }
+
+        let v = term.sqrt();
+
+        // t = (v-u) / a
+        (v - speed) / -friction
+    }
+
+    pub fn apply_force(&mut self, force: Vec2) {
+        let force = if self.mass > 0.0 {
+            force / self.mass
+        } else {
+            force
+        };
+
+        self.acceleration += force.clamp_length_max(self.max_force);
+    }
+
+    pub fn update(&mut self, transform: &mut Transform) {
+        self.integrate();
+        self.sync(transform);
+    }
+
+    // the velocity half of update(), for when collisions
+    // need to be checked before the transform is moved
+    pub fn integrate(&mut self) {
+        // https://github.com/bevyengine/bevy/issues/2041
+        let dt = PHYSICS_STEP;
+
+        // semi-implicit euler integration
+        let velocity = self.velocity + self.acceleration * dt;
+        self.velocity = if self.limit_turn_rate {
+            self.clamp_turn(velocity, self.max_turn_rate * dt)
+        } else {
+            velocity
+        };
+        self.velocity = self.velocity.clamp_length_max(self.max_speed);
+
+        // update local coordinate system
+        if self.velocity.length_squared() > f32::EPSILON {
+            self.heading = self.velocity.normalize_or_zero();
+            self.side = self.heading.perp();
+        }
+
+        self.update_smoothed_heading();
+
+        self.acceleration = Vec2::ZERO;
+    }
+
+    // the position half of update()
+    pub fn sync(&self, transform: &mut Transform) {
+        // https://github.com/bevyengine/bevy/issues/2041
+        let dt = PHYSICS_STEP;
+
+        transform.translation += (self.velocity * dt).extend(0.0);
+    }
+
+    // rotates the velocity at most max_angle away from the current heading
+    fn clamp_turn(&self, velocity: Vec2, max_angle: f32) -> Vec2 {
+        let speed = velocity.length();
+        if speed < f32::EPSILON {
+            return velocity;
+        }
+
+        let angle = self.heading.angle_between(velocity);
+        if angle.abs() <= max_angle {
+            return velocity;
+        }
+
+        Mat2::from_angle(max_angle * angle.signum()).mul_vec2(self.heading) * speed
+    }
+
+    fn update_smoothed_heading(&mut self) {
+        if self.heading_smoothing_samples == 0 {
+            self.heading_history.clear();
+            self.smoothed_heading = self.heading;
+            return;
+        }
+
+        self.heading_history.push_back(self.heading);
+        while self.heading_history.len() > self.heading_smoothing_samples {
+            self.heading_history.pop_front();
+        }
+
+        let heading = self
+            .heading_history
+            .iter()
+            .sum::<Vec2>()
+            .normalize_or_zero();
+        self.smoothed_heading = if heading == Vec2::ZERO {
+            self.heading
+        } else {
+            heading
+        };
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(derive(Debug))]
+pub struct PhysicalQuery {
+    pub transform: &'static Transform,
+    pub physical: &'static Physical,
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct PhysicalQueryMut {
+    pub transform: &'static Transform,
+    pub physical: &'static mut Physical,
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct PhysicalQueryUpdateMut {
+    pub transform: &'static mut Transform,
+    pub physical: &'static mut Physical,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_unbounded() {
+        let mut physical = Physical::default();
+        let mut transform = Transform::default();
+
+        // unit mass and no speed or force limits
+        physical.apply_force(Vec2::new(1000.0, 0.0));
+        assert_eq!(physical.acceleration, Vec2::new(1000.0, 0.0));
+
+        physical.update(&mut transform);
+        assert_eq!(physical.velocity, Vec2::new(1000.0 * PHYSICS_STEP, 0.0));
+        assert_eq!(physical.heading, Vec2::X);
+    }
+
+    #[test]
+    fn test_apply_force_is_clamped() {
+        let mut physical = Physical {
+            mass: 2.0,
+            max_force: 1.0,
+            ..Default::default()
+        };
+
+        physical.apply_force(Vec2::new(10.0, 0.0));
+        assert_eq!(physical.acceleration, Vec2::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn test_update() {
+        let mut physical = Physical {
+            max_speed: 10.0,
+            ..Default::default()
+        };
+        let mut transform = Transform::default();
+
+        physical.apply_force(Vec2::new(1000.0, 0.0));
+        physical.update(&mut transform);
+
+        // speed is clamped and the acceleration is used up
+        assert_eq!(physical.velocity, Vec2::new(10.0, 0.0));
+        assert_eq!(physical.acceleration, Vec2::ZERO);
+        assert_eq!(
+            transform.translation,
+            Vec3::new(10.0 * PHYSICS_STEP, 0.0, 0.0)
+        );
+
+        // we face the way we're going
+        assert_eq!(physical.heading, Vec2::X);
+        assert_eq!(physical.side, Vec2::Y);
+    }
+
+    #[test]
+    fn test_integrate_then_sync() {
+        let mut physical = Physical::default();
+        let mut transform = Transform::default();
+
+        // integrating only changes the velocity
+        physical.apply_force(Vec2::new(10.0, 0.0) / PHYSICS_STEP);
+        physical.integrate();
+        assert_eq!(physical.velocity, Vec2::new(10.0, 0.0));
+        assert_eq!(transform.translation, Vec3::ZERO);
+
+        physical.sync(&mut transform);
+        assert_eq!(
+            transform.translation,
+            Vec3::new(10.0 * PHYSICS_STEP, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_track_clamps_turn_rate() {
+        let mut physical = Physical {
+            heading: Vec2::X,
+            side: Vec2::Y,
+            max_turn_rate: std::f32::consts::FRAC_PI_4 / PHYSICS_STEP,
+            ..Default::default()
+        };
+
+        // target is directly above us, so we can only turn part way
+        physical.track(&Transform::default(), Vec2::new(0.0, 10.0));
+        assert!(physical.heading.distance(Vec2::new(1.0, 1.0).normalize()) < 0.0001);
+        assert_eq!(physical.smoothed_heading, physical.heading);
+    }
+
+    #[test]
+    fn test_update_clamps_turn_rate() {
+        let mut physical = Physical {
+            velocity: Vec2::X,
+            heading: Vec2::X,
+            side: Vec2::Y,
+            max_turn_rate: std::f32::consts::FRAC_PI_2 / PHYSICS_STEP,
+            limit_turn_rate: true,
+            ..Default::default()
+        };
+        let mut transform = Transform::default();
+
+        // try to turn back and to the left, but only get a quarter turn
+        physical.apply_force(Vec2::new(-10.0, 10.0) / PHYSICS_STEP);
+        physical.update(&mut transform);
+
+        assert!(physical.heading.distance(Vec2::Y) < 0.0001);
+
+        // speed is unaffected
+        let speed = Vec2::new(-9.0, 10.0).length();
+        assert!((physical.speed() - speed).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_smoothed_heading() {
+        let mut physical = Physical {
+            heading_smoothing_samples: 2,
+            ..Default::default()
+        };
+        let mut transform = Transform::default();
+
+        physical.velocity = Vec2::X;
+        physical.update(&mut transform);
+        assert_eq!(physical.smoothed_heading, Vec2::X);
+
+        // averaged with the previous heading
+        physical.velocity = Vec2::Y;
+        physical.update(&mut transform);
+        assert!(
+            physical
+                .smoothed_heading
+                .distance(Vec2::new(1.0, 1.0).normalize())
+                < 0.0001
+        );
+
+        // the first heading drops out
+        physical.update(&mut transform);
+        assert_eq!(physical.smoothed_heading, Vec2::Y);
+    }
+
+    #[test]
+    fn test_time_to_cover_distance() {
+        let physical = Physical::default();
+
+        // u = 10, a = -1, x = 60 => v^2 < 0, so we never get there
+        let time = physical.time_to_cover_distance(1.0, Vec2::ZERO, Vec2::new(60.0, 0.0), 10.0);
+        assert!(time < 0.0);
+
+        // u = 10, a = -1, x = 42 => v = 4, t = 6
+        let time = physical.time_to_cover_distance(1.0, Vec2::ZERO, Vec2::new(42.0, 0.0), 10.0);
+        assert!((time - 6.0).abs() < 0.0001);
+    }
+}
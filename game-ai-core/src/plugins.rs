@@ -0,0 +1,29 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::messaging::{DispatchedMessageEvent, MessageEvent};
+
+// registers the dispatched message event for a message type
+// the MessageDispatcher<T> resource and systems::messaging::update::<T>
+// are left to the app, so that it can choose when messages are dispatched
+pub struct MessagingPlugin<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Default for MessagingPlugin<T> {
+    fn default() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Plugin for MessagingPlugin<T>
+where
+    T: MessageEvent + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.add_event::<DispatchedMessageEvent<T>>();
+    }
+}
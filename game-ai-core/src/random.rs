@@ -0,0 +1,105 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+// seedable rng so that a run can be reproduced from its seed
+#[derive(Debug)]
+pub struct Random {
+    seed: u64,
+    rng: StdRng,
+
+    streams: HashMap<u64, Random>,
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // systems that share the resource run in no particular order,
+    // so each one draws from its own stream seeded from the run seed
+    pub fn stream(&mut self, key: impl Hash) -> &mut Random {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let seed = self.seed;
+        self.streams
+            .entry(key)
+            .or_insert_with(|| Random::new(seed ^ key))
+    }
+}
+
+impl RngCore for Random {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(random: &mut Random) -> Vec<u32> {
+        (0..4).map(|_| random.gen()).collect()
+    }
+
+    #[test]
+    fn test_same_seed_repeats() {
+        assert_eq!(draw(&mut Random::new(42)), draw(&mut Random::new(42)));
+        assert_ne!(draw(&mut Random::new(42)), draw(&mut Random::new(43)));
+    }
+
+    #[test]
+    fn test_streams_are_independent_of_order() {
+        let mut a = Random::new(42);
+        let a_first = draw(a.stream("first"));
+        let a_second = draw(a.stream("second"));
+
+        let mut b = Random::new(42);
+        let b_second = draw(b.stream("second"));
+        let b_first = draw(b.stream("first"));
+
+        assert_eq!(a_first, b_first);
+        assert_eq!(a_second, b_second);
+        assert_ne!(a_first, a_second);
+    }
+
+    #[test]
+    fn test_streams_continue() {
+        let mut random = Random::new(42);
+        let first = draw(random.stream("stream"));
+        let second = draw(random.stream("stream"));
+
+        assert_ne!(first, second);
+    }
+}
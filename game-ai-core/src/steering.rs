@@ -0,0 +1,274 @@
+use bevy::ecs::query::WorldQuery;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+
+use crate::physics::*;
+use crate::util::*;
+
+pub trait SteeringBehavior: std::fmt::Debug + Component {}
+
+#[derive(Debug, Default, Component, Inspectable)]
+#[component(storage = "SparseSet")]
+pub struct Steering {
+    pub target: Vec2,
+    pub accumulated_force: Vec2,
+}
+
+impl Steering {
+    pub fn is_at_target(&self, range_squared: f32, transform: &Transform) -> bool {
+        transform
+            .translation
+            .truncate()
+            .distance_squared(self.target)
+            < range_squared
+    }
+
+    // forces are accumulated in priority order,
+    // anything past the physical's max force is dropped
+    pub fn accumulate_force(&mut self, physical: &impl Kinematic, force: Vec2, weight: f32) {
+        let force = force * weight;
+
+        let mag_so_far = self.accumulated_force.length();
+        let mag_remain = physical.max_force() - mag_so_far;
+        if mag_remain <= 0.0 {
+            return;
+        }
+
+        let mut to_add = force.length();
+        if to_add > mag_remain {
+            to_add = mag_remain;
+        }
+
+        self.accumulated_force += force.normalize_or_zero() * to_add;
+    }
+
+    pub fn take_force(&mut self) -> Vec2 {
+        std::mem::take(&mut self.accumulated_force)
+    }
+
+    pub fn update(&mut self, physical: &mut Physical) {
+        physical.apply_force(self.take_force());
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct SteeringQueryMut {
+    pub steering: &'static mut Steering,
+    pub physical: &'static mut Physical,
+}
+
+#[derive(Debug, Default, Component)]
+pub struct SteeringTargetDebug;
+
+pub fn seek_force(target: Vec2, transform: &Transform, physical: &impl Kinematic) -> Vec2 {
+    let position = transform.translation.truncate();
+
+    let desired_velocity = (target - position).normalize_or_zero() * physical.max_speed();
+    desired_velocity - physical.velocity()
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+#[component(storage = "SparseSet")]
+pub struct Seek;
+
+impl SteeringBehavior for Seek {}
+
+impl Seek {
+    pub fn force(
+        &self,
+        steering: &Steering,
+        transform: &Transform,
+        physical: &impl Kinematic,
+    ) -> Vec2 {
+        seek_force(steering.target, transform, physical)
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct SeekQueryMut {
+    pub seek: &'static Seek,
+    pub steering: &'static mut Steering,
+}
+
+#[derive(Debug, Default, Clone, Copy, Inspectable)]
+pub enum Deceleration {
+    Slow = 3,
+    #[default]
+    Normal = 2,
+    Fast = 1,
+}
+
+pub fn arrive_force(
+    target: Vec2,
+    transform: &Transform,
+    physical: &impl Kinematic,
+    deceleration: Deceleration,
+) -> Vec2 {
+    let position = transform.translation.truncate();
+    let deceleration = deceleration as i32;
+
+    let to_target = target - position;
+
+    let dist = to_target.length();
+    if dist > 0.0 {
+        // fine tweaking of deceleration
+        let deceleration_tweaker = 0.3;
+
+        let speed = (dist / (deceleration as f32 * deceleration_tweaker)).min(physical.max_speed());
+        let desired_velocity = to_target * speed / dist;
+        return desired_velocity - physical.velocity();
+    }
+
+    Vec2::ZERO
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+#[component(storage = "SparseSet")]
+pub struct Arrive {
+    pub deceleration: Deceleration,
+}
+
+impl SteeringBehavior for Arrive {}
+
+impl Arrive {
+    pub fn force(
+        &self,
+        steering: &Steering,
+        transform: &Transform,
+        physical: &impl Kinematic,
+    ) -> Vec2 {
+        arrive_force(steering.target, transform, physical, self.deceleration)
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct ArriveQueryMut {
+    pub arrive: &'static Arrive,
+    pub steering: &'static mut Steering,
+}
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct ObstacleAvoidance {
+    pub box_length: f32,
+}
+
+#[derive(Debug, Default, Component)]
+pub struct ObstacleAvoidanceDebug;
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct WallAvoidance {
+    pub feelers: [Vec2; 3],
+}
+
+impl WallAvoidance {
+    pub fn create_feelers(&mut self, position: Vec2, heading: Vec2, feeler_length: f32) {
+        // straight ahead
+        self.feelers[0] = position + feeler_length * heading;
+
+        // left
+        let temp = heading.rotate_origin(std::f32::consts::FRAC_PI_2 * 3.5);
+        self.feelers[1] = position + feeler_length * 0.5 * temp;
+
+        // right
+        let temp = heading.rotate_origin(std::f32::consts::FRAC_2_PI * 0.5);
+        self.feelers[2] = position + feeler_length * 0.5 * temp;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn physical(max_speed: f32, max_force: f32) -> Physical {
+        Physical {
+            max_speed,
+            max_force,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_accumulate_force_is_capped() {
+        let physical = physical(10.0, 5.0);
+        let mut steering = Steering::default();
+
+        steering.accumulate_force(&physical, Vec2::new(3.0, 0.0), 1.0);
+        assert_eq!(steering.accumulated_force, Vec2::new(3.0, 0.0));
+
+        // only what's left of the max force is added
+        steering.accumulate_force(&physical, Vec2::new(0.0, 4.0), 1.0);
+        assert_eq!(steering.accumulated_force, Vec2::new(3.0, 2.0));
+
+        // weights are applied before capping
+        let mut steering = Steering::default();
+        steering.accumulate_force(&physical, Vec2::new(1.0, 0.0), 2.0);
+        assert_eq!(steering.accumulated_force, Vec2::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn test_take_force() {
+        let mut steering = Steering {
+            accumulated_force: Vec2::new(1.0, 2.0),
+            ..Default::default()
+        };
+
+        assert_eq!(steering.take_force(), Vec2::new(1.0, 2.0));
+        assert_eq!(steering.accumulated_force, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_is_at_target() {
+        let steering = Steering {
+            target: Vec2::new(3.0, 4.0),
+            ..Default::default()
+        };
+
+        assert!(!steering.is_at_target(25.0, &Transform::default()));
+        assert!(steering.is_at_target(25.1, &Transform::default()));
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut physical = physical(10.0, f32::MAX);
+        physical.velocity = Vec2::new(0.0, 10.0);
+
+        // full speed at the target, cancelling out our current velocity
+        let force = seek_force(Vec2::new(100.0, 0.0), &Transform::default(), &physical);
+        assert_eq!(force, Vec2::new(10.0, -10.0));
+    }
+
+    #[test]
+    fn test_arrive() {
+        let physical = physical(10.0, f32::MAX);
+
+        // far away is full speed
+        let force = arrive_force(
+            Vec2::new(100.0, 0.0),
+            &Transform::default(),
+            &physical,
+            Deceleration::Normal,
+        );
+        assert_eq!(force, Vec2::new(10.0, 0.0));
+
+        // close by slows down
+        let force = arrive_force(
+            Vec2::new(1.2, 0.0),
+            &Transform::default(),
+            &physical,
+            Deceleration::Fast,
+        );
+        assert!((force.x - 4.0).abs() < 0.0001);
+
+        // and there is stopped
+        let force = arrive_force(
+            Vec2::ZERO,
+            &Transform::default(),
+            &physical,
+            Deceleration::Normal,
+        );
+        assert_eq!(force, Vec2::ZERO);
+    }
+}
@@ -1,13 +1,14 @@
 use bevy::prelude::*;
 
-use crate::events::messaging::DispatchedMessageEvent;
-use crate::resources::messaging::{MessageDispatcher, MessageEvent};
+use crate::messaging::{DispatchedMessageEvent, MessageDispatcher, MessageEvent};
 
 pub fn update<T>(
+    time: Res<Time>,
     mut message_dispatcher: ResMut<MessageDispatcher<T>>,
     mut message_events: EventWriter<DispatchedMessageEvent<T>>,
 ) where
     T: MessageEvent + Send + Sync + 'static,
 {
-    message_dispatcher.dispatch_deferred_messages(&mut message_events);
+    message_dispatcher
+        .dispatch_deferred_messages(time.seconds_since_startup(), &mut message_events);
 }
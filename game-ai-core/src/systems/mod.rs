@@ -0,0 +1,2 @@
+pub mod messaging;
+pub mod steering;
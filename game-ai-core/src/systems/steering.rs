@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::steering::*;
+
+// apply the accumulated steering forces
+pub fn update(mut steering_behaviors: Query<SteeringQueryMut>) {
+    for mut steering in steering_behaviors.iter_mut() {
+        steering.steering.update(&mut steering.physical);
+    }
+}
@@ -0,0 +1,326 @@
+use bevy::ecs::query::{QueryItem, QuerySingleError, ROQueryItem, WorldQuery};
+use bevy::math::Mat2;
+use bevy::prelude::*;
+
+pub fn point_to_world_space(point: Vec2, heading: Vec2, side: Vec2, position: Vec2) -> Vec2 {
+    // rotate, then translate
+    let rotation = Mat3::from_cols(heading.extend(0.0), side.extend(0.0), Vec3::Z);
+    let transform = Mat3::from_translation(position) * rotation;
+
+    transform.transform_point2(point)
+}
+
+pub fn vector_to_world_space(vector: Vec2, heading: Vec2, side: Vec2) -> Vec2 {
+    // rotate
+    let transform = Mat3::from_cols(heading.extend(0.0), side.extend(0.0), Vec3::Z);
+
+    transform.transform_point2(vector)
+}
+
+pub fn point_to_local_space(point: Vec2, heading: Vec2, side: Vec2, position: Vec2) -> Vec2 {
+    let tx = -position.dot(heading);
+    let ty = -position.dot(side);
+
+    let transform = Mat3::from_cols(
+        Vec3::new(heading.x, side.x, 0.0),
+        Vec3::new(heading.y, side.y, 0.0),
+        Vec3::new(tx, ty, 1.0),
+    );
+
+    transform.transform_point2(point)
+}
+
+pub fn rotate_around_origin(v: Vec2, angle: f32) -> Vec2 {
+    let transform = Mat2::from_angle(angle);
+    transform.mul_vec2(v)
+}
+
+pub fn get_tangent_points(c: Vec2, r: f32, p: Vec2) -> Option<(Vec2, Vec2)> {
+    let pmc = p - c;
+    let sqrlen = pmc.length_squared();
+    let rsqr = r * r;
+
+    if sqrlen <= rsqr {
+        return None;
+    }
+
+    let invsqrlen = 1.0 / sqrlen;
+    let root = (sqrlen - rsqr).abs().sqrt();
+
+    let t1 = Vec2::new(
+        c.x + r * (r * pmc.x - pmc.y * root) * invsqrlen,
+        c.y + r * (r * pmc.y + pmc.x * root) * invsqrlen,
+    );
+    let t2 = Vec2::new(
+        c.x + r * (r * pmc.x + pmc.y * root) * invsqrlen,
+        c.y + r * (r * pmc.y - pmc.x * root) * invsqrlen,
+    );
+
+    Some((t1, t2))
+}
+
+// segment ab against segment cd,
+// returns the distance along ab and the point of intersection
+pub fn line_intersection(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Option<(f32, Vec2)> {
+    let rt = (a.y - c.y) * (d.x - c.x) - (a.x - c.x) * (d.y - c.y);
+    let rb = (b.x - a.x) * (d.y - c.y) - (b.y - a.y) * (d.x - c.x);
+
+    let st = (a.y - c.y) * (b.x - a.x) - (a.x - c.x) * (b.y - a.y);
+    let sb = (b.x - a.x) * (d.y - c.y) - (b.y - a.y) * (d.x - c.x);
+
+    if (rb == 0.0) || (sb == 0.0) {
+        //lines are parallel
+        return None;
+    }
+
+    let r = rt / rb;
+    let s = st / sb;
+
+    if (r > 0.0) && (r < 1.0) && (s > 0.0) && (s < 1.0) {
+        let dist = a.distance(b) * r;
+        let point = a + r * (b - a);
+
+        return Some((dist, point));
+    }
+
+    None
+}
+
+// shortest distance from p to the segment ab
+pub fn distance_to_line_segment(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    let ab = b - a;
+
+    let length_squared = ab.length_squared();
+    if length_squared <= f32::EPSILON {
+        return p.distance(a);
+    }
+
+    // project p onto ab, clamped to the ends of the segment
+    let t = ((p - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+// shortest distance between the segments ab and cd
+pub fn distance_between_line_segments(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f32 {
+    if line_intersection(a, b, c, d).is_some() {
+        return 0.0;
+    }
+
+    distance_to_line_segment(a, b, c)
+        .min(distance_to_line_segment(a, b, d))
+        .min(distance_to_line_segment(c, d, a))
+        .min(distance_to_line_segment(c, d, b))
+}
+
+pub trait OptionalSingle<'s, Q>
+where
+    Q: WorldQuery,
+{
+    fn optional_single(&self) -> Option<ROQueryItem<'_, Q>>;
+    fn optional_single_mut(&mut self) -> Option<QueryItem<'_, Q>>;
+}
+
+impl<'w, 's, Q, F> OptionalSingle<'s, Q> for Query<'w, 's, Q, F>
+where
+    Q: WorldQuery,
+    F: WorldQuery,
+{
+    fn optional_single(&self) -> Option<ROQueryItem<'_, Q>> {
+        match self.get_single() {
+            Ok(item) => Some(item),
+            Err(QuerySingleError::NoEntities(_)) => None,
+            Err(QuerySingleError::MultipleEntities(_)) => {
+                panic!("multiple items from optional single query")
+            }
+        }
+    }
+
+    fn optional_single_mut(&mut self) -> Option<QueryItem<'_, Q>> {
+        match self.get_single_mut() {
+            Ok(item) => Some(item),
+            Err(QuerySingleError::NoEntities(_)) => None,
+            Err(QuerySingleError::MultipleEntities(_)) => {
+                panic!("multiple items from optional single query")
+            }
+        }
+    }
+}
+
+pub trait Vec2Utils {
+    fn sign(&self, v2: Vec2) -> f32;
+
+    fn rotate_origin(&self, angle: f32) -> Vec2;
+}
+
+impl Vec2Utils for Vec2 {
+    fn sign(&self, v2: Vec2) -> f32 {
+        if self.y * v2.x > self.x * v2.y {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    fn rotate_origin(&self, angle: f32) -> Vec2 {
+        rotate_around_origin(*self, angle)
+    }
+}
+
+pub trait TransformUtils {
+    fn set_world_translation(&mut self, global_transform: &GlobalTransform, world_position: Vec3);
+}
+
+impl TransformUtils for Transform {
+    fn set_world_translation(&mut self, global_transform: &GlobalTransform, world_position: Vec3) {
+        let parent_position = global_transform.translation() - self.translation;
+
+        let local_position = world_position - parent_position;
+        if self.translation.distance_squared(local_position) > f32::EPSILON * f32::EPSILON {
+            self.translation = local_position;
+        }
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(mutable, derive(Debug))]
+pub struct TransformQueryMut {
+    pub global_transform: &'static GlobalTransform,
+    pub transform: &'static mut Transform,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 0.0001, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_local_world_space_round_trip() {
+        let heading = Vec2::new(1.0, 1.0).normalize();
+        let side = heading.perp();
+        let position = Vec2::new(10.0, -5.0);
+
+        let point = Vec2::new(3.0, 4.0);
+        let local = point_to_local_space(point, heading, side, position);
+        assert_near(point_to_world_space(local, heading, side, position), point);
+
+        // straight ahead in local space is along the heading
+        assert_near(
+            point_to_world_space(Vec2::X, heading, side, position),
+            position + heading,
+        );
+        assert_near(vector_to_world_space(Vec2::Y, heading, side), side);
+    }
+
+    #[test]
+    fn test_point_to_world_space_rotates_then_translates() {
+        // facing up, a point one unit ahead of us ends up just above us
+        let heading = Vec2::Y;
+        let side = heading.perp();
+        let position = Vec2::new(10.0, 0.0);
+
+        assert_near(
+            point_to_world_space(Vec2::X, heading, side, position),
+            Vec2::new(10.0, 1.0),
+        );
+        assert_near(
+            point_to_world_space(Vec2::ZERO, heading, side, position),
+            position,
+        );
+    }
+
+    #[test]
+    fn test_rotate_around_origin() {
+        assert_near(
+            rotate_around_origin(Vec2::X, std::f32::consts::FRAC_PI_2),
+            Vec2::Y,
+        );
+        assert_near(Vec2::Y.rotate_origin(std::f32::consts::PI), -Vec2::Y);
+    }
+
+    #[test]
+    fn test_sign() {
+        // counter-clockwise is positive
+        assert_eq!(Vec2::X.sign(Vec2::Y), 1.0);
+        assert_eq!(Vec2::X.sign(-Vec2::Y), -1.0);
+    }
+
+    #[test]
+    fn test_line_intersection() {
+        let (dist, point) = line_intersection(
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, -1.0),
+            Vec2::new(0.0, 1.0),
+        )
+        .unwrap();
+        assert!((dist - 1.0).abs() < 0.0001);
+        assert_near(point, Vec2::ZERO);
+
+        // parallel
+        assert!(line_intersection(Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::new(1.0, 1.0)).is_none());
+
+        // would intersect if the segment was longer
+        assert!(line_intersection(
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(0.0, 2.0),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_distance_to_line_segment() {
+        let a = Vec2::new(-1.0, 0.0);
+        let b = Vec2::new(1.0, 0.0);
+
+        // beside the segment
+        assert!((distance_to_line_segment(a, b, Vec2::new(0.5, 2.0)) - 2.0).abs() < 0.0001);
+
+        // past the ends is the distance to the closest end
+        assert!((distance_to_line_segment(a, b, Vec2::new(4.0, 4.0)) - 5.0).abs() < 0.0001);
+
+        // zero length segments are points
+        assert!((distance_to_line_segment(a, a, Vec2::new(-1.0, 3.0)) - 3.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_distance_between_line_segments() {
+        let a = Vec2::new(-1.0, 0.0);
+        let b = Vec2::new(1.0, 0.0);
+
+        // crossing
+        let d = distance_between_line_segments(a, b, Vec2::new(0.0, -1.0), Vec2::new(0.0, 1.0));
+        assert_eq!(d, 0.0);
+
+        // parallel
+        let d = distance_between_line_segments(a, b, Vec2::new(-1.0, 2.0), Vec2::new(1.0, 2.0));
+        assert!((d - 2.0).abs() < 0.0001);
+
+        // end to end
+        let d = distance_between_line_segments(a, b, Vec2::new(4.0, 4.0), Vec2::new(4.0, 10.0));
+        assert!((d - 5.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_tangent_points() {
+        let center = Vec2::ZERO;
+        let radius = 1.0;
+        let point = Vec2::new(2.0, 0.0);
+
+        let (t1, t2) = get_tangent_points(center, radius, point).unwrap();
+        for t in [t1, t2] {
+            // on the circle
+            assert!((t.length() - radius).abs() < 0.0001);
+
+            // and perpendicular to the radius
+            assert!((t - center).dot(point - t).abs() < 0.0001);
+        }
+
+        // no tangents from inside the circle
+        assert!(get_tangent_points(center, radius, Vec2::new(0.5, 0.0)).is_none());
+    }
+}
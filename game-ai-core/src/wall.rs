@@ -0,0 +1,67 @@
+use bevy::ecs::query::WorldQuery;
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+
+// a wall segment relative to its transform
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct Wall {
+    pub from: Vec2,
+    pub to: Vec2,
+
+    // the wall normal
+    normal: Vec2,
+}
+
+impl Wall {
+    pub fn new(from: Vec2, to: Vec2) -> Self {
+        let temp = (to - from).normalize_or_zero();
+        let normal = Vec2::new(-temp.y, temp.x);
+
+        Self { from, to, normal }
+    }
+
+    pub fn from(&self, position: Vec2) -> Vec2 {
+        position + self.from
+    }
+
+    pub fn to(&self, position: Vec2) -> Vec2 {
+        position + self.to
+    }
+
+    pub fn normal(&self) -> Vec2 {
+        self.normal
+    }
+}
+
+#[derive(WorldQuery)]
+#[world_query(derive(Debug))]
+pub struct WallQuery {
+    pub wall: &'static Wall,
+    pub transform: &'static Transform,
+}
+
+#[derive(Debug, Default, Component)]
+pub struct WallDebug;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_is_left_of_the_wall() {
+        let wall = Wall::new(Vec2::new(0.0, -10.0), Vec2::new(0.0, 10.0));
+        assert_eq!(wall.normal(), Vec2::new(-1.0, 0.0));
+
+        let wall = Wall::new(Vec2::new(-10.0, 0.0), Vec2::new(10.0, 0.0));
+        assert_eq!(wall.normal(), Vec2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_world_position() {
+        let wall = Wall::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0));
+        let position = Vec2::new(5.0, 5.0);
+
+        assert_eq!(wall.from(position), Vec2::new(4.0, 5.0));
+        assert_eq!(wall.to(position), Vec2::new(6.0, 5.0));
+    }
+}
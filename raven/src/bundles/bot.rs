@@ -1,13 +1,18 @@
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
+use game_ai_core::random::Random;
 
 use crate::bundles::actor::*;
 use crate::bundles::agent::*;
 use crate::components::bot::*;
+use crate::components::brain::*;
 use crate::components::collision::*;
 use crate::components::inventory::*;
+use crate::components::memory::*;
 use crate::components::physics::*;
+use crate::components::planner::*;
 use crate::components::spawnpoint::*;
+use crate::components::targeting::*;
 use crate::components::weapon::*;
 use crate::components::*;
 use crate::game::{BOT_RADIUS, BOT_SORT};
@@ -18,19 +23,26 @@ pub struct BotBundle {
     pub actor: ActorBundle,
 
     pub physical: Physical,
+    pub physical_cache: PhysicalCache,
     pub bounds: Bounds,
 
     #[bundle]
     pub agent: AgentBundle,
+    pub planner: PathPlanner,
 
     pub bot: Bot,
+    pub brain: Brain,
+    pub memory: SensoryMemory,
+    pub targeting: Targeting,
     pub inventory: Inventory,
     pub equipped_weapon: EquippedWeapon,
+    pub weapon_selector: WeaponSelector,
 }
 
 impl BotBundle {
     pub fn spawn_at_spawnpoint(
         commands: &mut Commands,
+        random: &mut Random,
         name: impl Into<String>,
         color: Color,
         health: usize,
@@ -40,11 +52,12 @@ impl BotBundle {
             .spawnpoint
             .get_spawn_position(spawnpoint.transform);
 
-        Self::spawn_at_position(commands, name, color, health, position)
+        Self::spawn_at_position(commands, random, name, color, health, position)
     }
 
     pub fn spawn_at_position(
         commands: &mut Commands,
+        random: &mut Random,
         name: impl Into<String>,
         color: Color,
         health: usize,
@@ -70,11 +83,17 @@ impl BotBundle {
                 max_speed: 100.0,
                 ..Default::default()
             },
+            physical_cache: PhysicalCache::default(),
             bounds: Bounds::Circle(Vec2::ZERO, BOT_RADIUS),
             agent: AgentBundle::default(),
+            planner: PathPlanner::default(),
             bot: Bot::new(color, health),
+            brain: Brain::new(random),
+            memory: SensoryMemory::default(),
+            targeting: Targeting::default(),
             inventory: Inventory::default(),
             equipped_weapon: EquippedWeapon::default(),
+            weapon_selector: WeaponSelector::default(),
         });
 
         bundle.with_children(|parent| {
@@ -110,6 +129,22 @@ impl BotBundle {
                 .insert(PossessedBotVisual)
                 .insert(Name::new("Possessed"));
 
+            parent
+                .spawn_bundle(GeometryBuilder::build_as(
+                    &shapes::Circle {
+                        radius: BOT_RADIUS * 1.5,
+                        ..Default::default()
+                    },
+                    DrawMode::Fill(FillMode {
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                        options: FillOptions::default(),
+                    }),
+                    Transform::from_translation(Vec3::new(0.0, 0.0, 0.5)),
+                ))
+                .insert(Visibility { is_visible: false })
+                .insert(InvulnerableBotVisual)
+                .insert(Name::new("Invulnerable"));
+
             parent
                 .spawn_bundle(GeometryBuilder::build_as(
                     &shapes::Circle {
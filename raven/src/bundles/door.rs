@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::bundles::trigger::*;
+use crate::bundles::wall::*;
+use crate::components::door::*;
+use crate::components::trigger::*;
+use crate::game::DOOR_SWITCH_TRIGGER_RADIUS;
+
+#[derive(Debug, Bundle)]
+pub struct DoorBundle {
+    pub name: Name,
+
+    pub door: Door,
+}
+
+impl DoorBundle {
+    // doors start closed, so they're spawned on top of a wall
+    pub fn spawn(
+        commands: &mut Commands,
+        position: Vec2,
+        from: Vec2,
+        to: Vec2,
+        switches: impl IntoIterator<Item = Vec2>,
+    ) -> Entity {
+        info!("spawning door at {} ({} to {})", position, from, to);
+
+        let entity = WallBundle::spawn(commands, position, from, to);
+        commands.entity(entity).insert_bundle(DoorBundle {
+            name: Name::new("Door"),
+            door: Door::new(from, to),
+        });
+
+        for switch in switches {
+            TriggerBundle::spawn(
+                commands,
+                Trigger::DoorSwitch(entity),
+                switch,
+                DOOR_SWITCH_TRIGGER_RADIUS,
+            );
+        }
+
+        entity
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::bundles::actor::*;
+use crate::components::effect::*;
+use crate::components::projectile::*;
+use crate::components::*;
+use crate::game::{EFFECT_SORT, SLUG_RADIUS};
+
+#[derive(Debug, Bundle)]
+pub struct EffectBundle {
+    #[bundle]
+    pub actor: ActorBundle,
+
+    pub effect: Effect,
+}
+
+impl EffectBundle {
+    fn spawn(
+        commands: &mut Commands,
+        name: impl Into<String>,
+        position: Vec2,
+        effect: Effect,
+        model: impl Bundle,
+    ) -> Entity {
+        let mut transform = Transform::from_translation(position.extend(EFFECT_SORT));
+        transform.scale = Vec2::splat(effect.get_scale()).extend(1.0);
+
+        let mut bundle = commands.spawn_bundle(EffectBundle {
+            actor: ActorBundle {
+                name: Name::new(name.into()),
+                spatial: SpatialBundle::from_transform(transform),
+                ..Default::default()
+            },
+            effect,
+        });
+
+        bundle.with_children(|parent| {
+            parent
+                .spawn_bundle(model)
+                .insert(Model)
+                .insert(Name::new("Model"));
+        });
+
+        bundle.id()
+    }
+
+    // expands out to the explosion radius
+    pub fn spawn_explosion(commands: &mut Commands, position: Vec2, radius: f32) -> Entity {
+        info!("spawning explosion at {}", position);
+
+        Self::spawn(
+            commands,
+            "Explosion",
+            position,
+            Effect::new(ROCKET_EXPLOSION_SECONDS, 0.1, 1.0),
+            GeometryBuilder::build_as(
+                &shapes::Circle {
+                    radius,
+                    ..Default::default()
+                },
+                DrawMode::Fill(FillMode {
+                    color: Color::ORANGE,
+                    options: FillOptions::default(),
+                }),
+                Transform::default(),
+            ),
+        )
+    }
+
+    // the path of a railgun slug
+    pub fn spawn_trace(commands: &mut Commands, from: Vec2, to: Vec2) -> Entity {
+        Self::spawn(
+            commands,
+            "Slug Trace",
+            from,
+            Effect::new(SLUG_TRACE_SECONDS, 1.0, 1.0),
+            GeometryBuilder::build_as(
+                &shapes::Line(Vec2::ZERO, to - from),
+                DrawMode::Stroke(StrokeMode::new(Color::PURPLE, SLUG_RADIUS * 2.0)),
+                Transform::default(),
+            ),
+        )
+    }
+}
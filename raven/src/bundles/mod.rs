@@ -2,6 +2,8 @@ pub mod actor;
 pub mod agent;
 pub mod bot;
 pub mod corpse;
+pub mod door;
+pub mod effect;
 pub mod projectile;
 pub mod spawnpoint;
 pub mod trigger;
@@ -13,9 +13,11 @@ pub struct ProjectileBundle {
     pub actor: ActorBundle,
 
     pub physical: Physical,
+    pub physical_cache: PhysicalCache,
     pub bounds: Bounds,
 
     pub projectile: Projectile,
+    pub origin: ProjectileOrigin,
 }
 
 impl ProjectileBundle {
@@ -48,13 +50,15 @@ impl ProjectileBundle {
                 max_speed: 150.0,
                 ..Default::default()
             },
+            physical_cache: PhysicalCache::default(),
             bounds: projectile.get_bounds(),
             projectile: projectile.clone(),
+            origin: ProjectileOrigin(position),
         });
 
         bundle.with_children(|parent| {
             let mut model = parent.spawn();
-            projectile.spawn_model(&mut model);
+            projectile.spawn_model(&mut model, direction);
             model.insert(Model).insert(Name::new("Model"));
         });
 
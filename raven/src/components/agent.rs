@@ -16,21 +16,17 @@ impl Agent {
         commands.entity(entity).remove::<Seek>();
     }
 
-    pub fn arrive_on(&self, commands: &mut Commands, entity: Entity) {
-        commands.entity(entity).insert(Arrive::default());
+    // following a path replaces seeking and arriving
+    pub fn follow_path_on(&self, commands: &mut Commands, entity: Entity, follow_path: FollowPath) {
+        commands
+            .entity(entity)
+            .insert(follow_path)
+            .remove::<Seek>()
+            .remove::<Arrive>();
     }
 
-    pub fn arrive_on_with_deceleration(
-        &self,
-        commands: &mut Commands,
-        entity: Entity,
-        deceleration: Deceleration,
-    ) {
-        commands.entity(entity).insert(Arrive { deceleration });
-    }
-
-    pub fn arrive_off(&self, commands: &mut Commands, entity: Entity) {
-        commands.entity(entity).remove::<Arrive>();
+    pub fn follow_path_off(&self, commands: &mut Commands, entity: Entity) {
+        commands.entity(entity).remove::<FollowPath>();
     }
 }
 
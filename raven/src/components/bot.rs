@@ -4,7 +4,9 @@ use bevy_inspector_egui::prelude::*;
 
 use crate::bundles::corpse::*;
 use crate::components::inventory::*;
+use crate::components::physics::*;
 use crate::components::weapon::*;
+use crate::game::{BOT_RESPAWN_SECONDS, BOT_SPAWN_INVULNERABLE_SECONDS};
 
 // TODO: pull bot parameters from a config
 
@@ -16,6 +18,14 @@ pub struct Bot {
     pub max_health: usize,
     pub current_health: usize,
     pub invulnerable: bool,
+
+    // respawns the bot when it finishes
+    #[inspectable(ignore)]
+    respawn_timer: Option<Timer>,
+
+    // ends spawn invulnerability when it finishes
+    #[inspectable(ignore)]
+    invulnerable_timer: Option<Timer>,
 }
 
 impl Bot {
@@ -25,6 +35,8 @@ impl Bot {
             max_health: health,
             current_health: health,
             invulnerable: false,
+            respawn_timer: None,
+            invulnerable_timer: None,
         }
     }
 
@@ -272,6 +284,9 @@ impl Bot {
         }
 
         self.current_health = 0;
+        self.invulnerable = false;
+        self.invulnerable_timer = None;
+        inventory.reset();
 
         let position = transform.translation.truncate();
 
@@ -282,33 +297,65 @@ impl Bot {
             position,
         );
 
-        self.respawn(commands, entity, inventory, name);
+        // dead bots are out of play until they respawn
+        commands
+            .entity(entity)
+            .insert(Visibility { is_visible: false });
+
+        self.respawn_timer = Some(Timer::from_seconds(BOT_RESPAWN_SECONDS, false));
+    }
+
+    // returns true if the bot is ready to respawn
+    pub fn update(&mut self, dt: f32, name: impl AsRef<str>) -> bool {
+        let dt = std::time::Duration::from_secs_f32(dt);
+
+        if let Some(timer) = self.invulnerable_timer.as_mut() {
+            timer.tick(dt);
+
+            if timer.just_finished() {
+                info!("[{}]: no longer invulnerable", name.as_ref());
+
+                self.invulnerable = false;
+                self.invulnerable_timer = None;
+            }
+        }
+
+        match self.respawn_timer.as_mut() {
+            Some(timer) => timer.tick(dt).finished(),
+            None => false,
+        }
     }
 
-    // TODO: we need to respawn on the *next* frame
-    // but we should also have bots be invincible on spawn for a little bit
     pub fn respawn(
         &mut self,
         commands: &mut Commands,
         entity: Entity,
-        inventory: &mut Inventory,
+        physical: &mut Physical,
+        transform: &mut Transform,
+        visibility: &mut Visibility,
+        position: Vec2,
         name: impl AsRef<str>,
     ) {
-        if self.invulnerable {
-            warn!("[{}]: unkillable but reborn!", name.as_ref());
-        } else if self.is_alive() {
+        if self.is_alive() {
             warn!("[{}] reborn!", name.as_ref());
         }
 
+        info!("[{}]: respawning at {}", name.as_ref(), position);
+
         self.current_health = self.max_health;
-        inventory.reset();
+        self.respawn_timer = None;
+
+        // bots are invulnerable for a little bit after spawning
+        self.invulnerable = true;
+        self.invulnerable_timer = Some(Timer::from_seconds(BOT_SPAWN_INVULNERABLE_SECONDS, false));
 
         commands
             .entity(entity)
             .insert(Inventory::default())
             .insert(EquippedWeapon::default());
 
-        warn!("TODO: respawn {}", name.as_ref());
+        physical.teleport(transform, position);
+        visibility.is_visible = true;
     }
 }
 
@@ -339,3 +386,6 @@ pub struct PossessedBot;
 
 #[derive(Debug, Default, Component)]
 pub struct PossessedBotVisual;
+
+#[derive(Debug, Default, Component)]
+pub struct InvulnerableBotVisual;
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use game_ai_core::random::Random;
+
+use crate::game::goals::*;
+use crate::game::THINK_ARBITRATE_SECONDS;
+
+// path planner results waiting to be handled by the goals
+#[derive(Debug)]
+pub enum PathResult {
+    Ready(Vec<Vec2>),
+    NoPath,
+}
+
+// drives bots that aren't possessed
+#[derive(Debug, Component)]
+pub struct Brain {
+    pub think: Think,
+
+    arbitrate: Timer,
+
+    path_result: Option<PathResult>,
+}
+
+impl Brain {
+    pub fn new(random: &mut Random) -> Self {
+        Self {
+            think: Think::new(random),
+            arbitrate: Timer::from_seconds(THINK_ARBITRATE_SECONDS, true),
+            path_result: None,
+        }
+    }
+
+    pub fn path_ready(&mut self, waypoints: Vec<Vec2>) {
+        self.path_result = Some(PathResult::Ready(waypoints));
+    }
+
+    pub fn no_path_available(&mut self) {
+        self.path_result = Some(PathResult::NoPath);
+    }
+
+    pub fn take_path_result(&mut self) -> Option<PathResult> {
+        self.path_result.take()
+    }
+
+    // true if it's time to pick a new goal
+    pub fn should_arbitrate(&mut self, dt: f32) -> bool {
+        self.arbitrate.tick(Duration::from_secs_f32(dt));
+
+        self.arbitrate.just_finished() || !self.think.has_goal()
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::components::wall::*;
+use crate::game::DOOR_OPEN_SECONDS;
+
+// a door is a wall that can be opened by its switches
+// it has no Wall component (and so no collision) while it's open
+#[derive(Debug, Component)]
+pub struct Door {
+    from: Vec2,
+    to: Vec2,
+
+    // closes the door when it finishes
+    timer: Option<Timer>,
+}
+
+impl Door {
+    pub fn new(from: Vec2, to: Vec2) -> Self {
+        Self {
+            from,
+            to,
+            timer: None,
+        }
+    }
+
+    pub fn open(&mut self, commands: &mut Commands, entity: Entity, visibility: &mut Visibility) {
+        // re-opening just keeps the door open for longer
+        self.timer = Some(Timer::from_seconds(DOOR_OPEN_SECONDS, false));
+
+        commands.entity(entity).remove::<Wall>();
+        visibility.is_visible = false;
+    }
+
+    pub fn close(&mut self, commands: &mut Commands, entity: Entity, visibility: &mut Visibility) {
+        self.timer = None;
+
+        commands
+            .entity(entity)
+            .insert(Wall::new(self.from, self.to));
+        visibility.is_visible = true;
+    }
+
+    pub fn update(
+        &mut self,
+        commands: &mut Commands,
+        entity: Entity,
+        visibility: &mut Visibility,
+        dt: f32,
+    ) {
+        if let Some(timer) = self.timer.as_mut() {
+            timer.tick(std::time::Duration::from_secs_f32(dt));
+
+            if timer.just_finished() {
+                info!("closing door");
+
+                self.close(commands, entity, visibility);
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+// a short lived visual that scales and fades out over its lifetime
+#[derive(Debug, Component)]
+pub struct Effect {
+    pub timer: Timer,
+
+    pub start_scale: f32,
+    pub end_scale: f32,
+}
+
+impl Effect {
+    pub fn new(seconds: f32, start_scale: f32, end_scale: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, false),
+            start_scale,
+            end_scale,
+        }
+    }
+
+    pub fn get_scale(&self) -> f32 {
+        self.start_scale + (self.end_scale - self.start_scale) * self.timer.percent()
+    }
+
+    pub fn get_alpha(&self) -> f32 {
+        self.timer.percent_left()
+    }
+}
@@ -46,6 +46,19 @@ impl Inventory {
         *self.ammo.get(&ammo).unwrap()
     }
 
+    // ammo / max ammo, 0 if the weapon isn't held
+    pub fn get_weapon_strength(&self, weapon: Weapon) -> f32 {
+        if !self.has_weapon(weapon) {
+            return 0.0;
+        }
+
+        if weapon == Weapon::Blaster {
+            return 1.0;
+        }
+
+        self.get_ammo_amount(weapon) as f32 / weapon.get_ammo().get_max_amount() as f32
+    }
+
     pub fn increase_ammo(&mut self, weapon: Weapon, amount: usize) {
         self.weapons.insert(weapon, true);
 
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+
+use crate::game::{BOT_FOV_DEGREES, MEMORY_SPAN_SECONDS};
+
+// what a bot remembers about an opponent
+// times are in seconds since startup
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryRecord {
+    pub time_last_sensed: f32,
+    pub time_last_visible: f32,
+    pub last_sensed_position: Vec2,
+
+    // in the bot's field of view and line of sight
+    pub within_fov: bool,
+
+    // in the bot's line of sight
+    pub shootable: bool,
+}
+
+impl Default for MemoryRecord {
+    fn default() -> Self {
+        Self {
+            time_last_sensed: f32::NEG_INFINITY,
+            time_last_visible: 0.0,
+            last_sensed_position: Vec2::ZERO,
+            within_fov: false,
+            shootable: false,
+        }
+    }
+}
+
+#[derive(Debug, Component, Inspectable)]
+pub struct SensoryMemory {
+    // seconds an opponent is remembered for after it was last sensed
+    pub memory_span: f32,
+
+    // field of view in degrees
+    pub fov: f32,
+
+    // the last direction the bot moved in
+    pub facing: Vec2,
+
+    #[inspectable(ignore)]
+    records: HashMap<Entity, MemoryRecord>,
+}
+
+impl Default for SensoryMemory {
+    fn default() -> Self {
+        Self {
+            memory_span: MEMORY_SPAN_SECONDS,
+            fov: BOT_FOV_DEGREES,
+            facing: Vec2::Y,
+            records: HashMap::new(),
+        }
+    }
+}
+
+impl SensoryMemory {
+    pub fn get_record(&self, entity: Entity) -> Option<&MemoryRecord> {
+        self.records.get(&entity)
+    }
+
+    pub fn remove_bot(&mut self, entity: Entity) {
+        self.records.remove(&entity);
+    }
+
+    pub fn update_facing(&mut self, velocity: Vec2) {
+        if velocity.length_squared() > f32::EPSILON {
+            self.facing = velocity.normalize();
+        }
+    }
+
+    fn is_in_fov(&self, position: Vec2, other: Vec2) -> bool {
+        let direction = (other - position).normalize_or_zero();
+        self.facing.dot(direction) >= (self.fov.to_radians() / 2.0).cos()
+    }
+
+    // opponents are (entity, position, in line of sight)
+    pub fn update_vision(&mut self, now: f32, position: Vec2, opponents: &[(Entity, Vec2, bool)]) {
+        for (entity, opponent_position, line_of_sight) in opponents.iter().copied() {
+            let in_fov = line_of_sight && self.is_in_fov(position, opponent_position);
+            let record = self.records.entry(entity).or_default();
+
+            record.shootable = line_of_sight;
+            if !in_fov {
+                record.within_fov = false;
+                continue;
+            }
+
+            record.within_fov = true;
+            record.time_last_sensed = now;
+            record.time_last_visible = now;
+            record.last_sensed_position = opponent_position;
+        }
+    }
+
+    pub fn update_with_sound(&mut self, now: f32, entity: Entity, position: Vec2) {
+        let record = self.records.entry(entity).or_default();
+
+        record.time_last_sensed = now;
+        record.last_sensed_position = position;
+    }
+
+    // opponents sensed within the memory span
+    pub fn get_recently_sensed_opponents(
+        &self,
+        now: f32,
+    ) -> impl Iterator<Item = (Entity, &MemoryRecord)> {
+        let memory_span = self.memory_span;
+        self.records
+            .iter()
+            .filter(move |(_, record)| now - record.time_last_sensed <= memory_span)
+            .map(|(entity, record)| (*entity, record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vision() {
+        let mut memory = SensoryMemory::default();
+        let ahead = Entity::from_raw(1);
+        let behind = Entity::from_raw(2);
+        let hidden = Entity::from_raw(3);
+
+        memory.update_vision(
+            1.0,
+            Vec2::ZERO,
+            &[
+                (ahead, Vec2::new(0.0, 10.0), true),
+                (behind, Vec2::new(0.0, -10.0), true),
+                (hidden, Vec2::new(5.0, 10.0), false),
+            ],
+        );
+
+        let record = memory.get_record(ahead).unwrap();
+        assert!(record.within_fov && record.shootable);
+        assert_eq!(record.last_sensed_position, Vec2::new(0.0, 10.0));
+
+        // behind can be shot but it hasn't been seen
+        let record = memory.get_record(behind).unwrap();
+        assert!(!record.within_fov && record.shootable);
+
+        let record = memory.get_record(hidden).unwrap();
+        assert!(!record.within_fov && !record.shootable);
+
+        let sensed = memory
+            .get_recently_sensed_opponents(1.0)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        assert_eq!(sensed, vec![ahead]);
+
+        memory.update_vision(3.0, Vec2::ZERO, &[(ahead, Vec2::new(0.0, 20.0), true)]);
+        let record = memory.get_record(ahead).unwrap();
+        assert_eq!(record.time_last_visible, 3.0);
+        assert_eq!(record.last_sensed_position, Vec2::new(0.0, 20.0));
+    }
+
+    #[test]
+    fn sound_and_memory_span() {
+        let mut memory = SensoryMemory::default();
+        let entity = Entity::from_raw(1);
+
+        memory.update_with_sound(1.0, entity, Vec2::new(5.0, 5.0));
+
+        let record = memory.get_record(entity).unwrap();
+        assert!(!record.within_fov);
+        assert_eq!(record.last_sensed_position, Vec2::new(5.0, 5.0));
+
+        assert_eq!(memory.get_recently_sensed_opponents(1.0).count(), 1);
+        assert_eq!(
+            memory
+                .get_recently_sensed_opponents(1.0 + memory.memory_span + 0.1)
+                .count(),
+            0
+        );
+    }
+}
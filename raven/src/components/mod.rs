@@ -1,14 +1,20 @@
 pub mod actor;
 pub mod agent;
 pub mod bot;
+pub mod brain;
 pub mod camera;
 pub mod collision;
 pub mod corpse;
+pub mod door;
+pub mod effect;
 pub mod inventory;
+pub mod memory;
 pub mod physics;
+pub mod planner;
 pub mod projectile;
 pub mod spawnpoint;
 pub mod steering;
+pub mod targeting;
 pub mod trigger;
 pub mod wall;
 pub mod weapon;
@@ -17,3 +23,9 @@ use bevy::prelude::*;
 
 #[derive(Debug, Default, Component)]
 pub struct Model;
+
+#[derive(Debug, Default, Component)]
+pub struct NavGraphDebug;
+
+#[derive(Debug, Default, Component)]
+pub struct SearchDebug;
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+
+use crate::components::steering::*;
+use crate::resources::path::*;
+
+#[derive(Debug, Default, Component, Inspectable)]
+pub struct PathPlanner {
+    // the destination of the pending request, if any
+    pub destination: Option<Vec2>,
+
+    pub smoothing: PathSmoothing,
+}
+
+impl PathPlanner {
+    pub fn request_path_to_position(
+        &mut self,
+        manager: &mut PathManager,
+        entity: Entity,
+        position: Vec2,
+        destination: Vec2,
+        name: impl AsRef<str>,
+    ) {
+        info!(
+            "[{}]: requesting path to {} ...",
+            name.as_ref(),
+            destination
+        );
+
+        self.destination = Some(destination);
+        manager.request(entity, position, destination);
+    }
+
+    pub fn cancel(&mut self, manager: &mut PathManager, entity: Entity) {
+        self.destination = None;
+        manager.cancel(entity);
+    }
+
+    // returns the smoothed path to follow
+    pub fn path_ready(
+        &mut self,
+        position: Vec2,
+        path: Vec<Vec2>,
+        can_walk: impl Fn(Vec2, Vec2) -> bool,
+        name: impl AsRef<str>,
+    ) -> FollowPath {
+        let mut follow_path = FollowPath::new(path, false);
+        follow_path.smooth(position, self.smoothing, can_walk);
+
+        info!(
+            "[{}]: path ready with {} waypoints",
+            name.as_ref(),
+            follow_path.path.waypoints.len()
+        );
+
+        self.destination = None;
+        follow_path
+    }
+
+    pub fn no_path_available(&mut self, name: impl AsRef<str>) {
+        if let Some(destination) = self.destination.take() {
+            warn!("[{}]: no path available to {}", name.as_ref(), destination);
+        }
+    }
+}
@@ -2,19 +2,31 @@ use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
 
+use crate::bundles::effect::*;
 use crate::bundles::trigger::*;
 use crate::components::bot::*;
 use crate::components::collision::*;
 use crate::components::inventory::*;
 use crate::components::physics::*;
 use crate::components::trigger::*;
-use crate::game::{BOLT_RADIUS, PELLET_RADIUS, PHYSICS_STEP, ROCKET_RADIUS, SLUG_RADIUS};
+use crate::game::{
+    BOLT_LENGTH, BOLT_RADIUS, PELLET_RADIUS, PHYSICS_STEP, ROCKET_LENGTH, ROCKET_RADIUS,
+    SLUG_RADIUS,
+};
 
 // TODO: pull projectile parameters from a config
 
 pub const PELLET_SPREAD: f32 = 7.0;
 pub const NUMBER_OF_PELLETS: usize = 10;
+pub const PELLET_RANGE: f32 = 30.0;
 pub const ROCKET_EXPLOSION_RADIUS: f32 = 10.0;
+pub const ROCKET_EXPLOSION_SECONDS: f32 = 0.5;
+pub const SLUG_RANGE: f32 = 1000.0;
+pub const SLUG_TRACE_SECONDS: f32 = 0.3;
+
+// where a projectile was fired from, for range limits
+#[derive(Debug, Default, Component)]
+pub struct ProjectileOrigin(pub Vec2);
 
 #[derive(Debug, Clone, PartialEq, Eq, Component)]
 pub enum Projectile {
@@ -61,6 +73,19 @@ impl Projectile {
         }
     }
 
+    // slugs are traced along their ray instead of moving
+    pub fn is_hit_scan(&self) -> bool {
+        matches!(self, Self::Slug(_))
+    }
+
+    pub fn get_range(&self) -> Option<f32> {
+        match self {
+            Self::Pellet(_) => Some(PELLET_RANGE),
+            Self::Slug(_) => Some(SLUG_RANGE),
+            _ => None,
+        }
+    }
+
     pub fn get_bounds(&self) -> Bounds {
         // TODO: not all of these shapes are correct
         match self {
@@ -80,8 +105,25 @@ impl Projectile {
         }
     }
 
-    pub fn on_impact<'w, B>(&self, commands: &mut Commands, entity: Entity, hit: Vec2, bots: B)
-    where
+    // rocket explosion damage falls off with distance from the hit
+    pub fn get_splash_damage(&self, distance: f32) -> usize {
+        match self {
+            Self::Rocket(_) => {
+                let falloff = 1.0 - (distance / ROCKET_EXPLOSION_RADIUS).clamp(0.0, 1.0);
+                (self.get_damage() as f32 * falloff).round() as usize
+            }
+            _ => 0,
+        }
+    }
+
+    // hit_bot is the bot that was hit directly, if any
+    pub fn on_impact<'w, B>(
+        &self,
+        commands: &mut Commands,
+        hit: Vec2,
+        hit_bot: Option<Entity>,
+        bots: B,
+    ) where
         B: Iterator<
             Item = (
                 Entity,
@@ -103,45 +145,49 @@ impl Projectile {
             let explosion_bounds = Bounds::Circle(Vec2::ZERO, ROCKET_EXPLOSION_RADIUS);
 
             for (bot_entity, mut bot, mut inventory, bot_physical, bot_bounds) in bots {
-                // don't re-hit the initial impact entity
+                // don't re-hit the initial impact bot
                 // note that we are allowing explosions to impact the rocket owner here
-                if bot_entity == entity {
+                if Some(bot_entity) == hit_bot || !bot.bot.is_alive() {
                     continue;
                 }
 
-                let bot_position = bot_physical.physical.cache.position + bot_bounds.center();
+                let bot_position = bot_physical.cache.position + bot_bounds.center();
 
                 if explosion_bounds.bounds_intersects(hit, bot_bounds, bot_position) {
-                    info!("rocket explosion hit bot '{}' at {}!", bot.name, hit);
+                    let distance = (bot_position.distance(hit) - bot_bounds.radius()).max(0.0);
+                    let damage = self.get_splash_damage(distance);
+                    if damage == 0 {
+                        continue;
+                    }
+
+                    info!(
+                        "rocket explosion hit bot '{}' at {} for {}!",
+                        bot.name, hit, damage
+                    );
                     bot.bot.damage(
                         commands,
                         bot_entity,
                         bot_physical.transform,
                         &mut inventory,
                         bot.name,
-                        self.get_damage() / 2,
+                        damage,
                     );
                 }
             }
 
-            // TODO: spawn the explosion visual
+            EffectBundle::spawn_explosion(commands, hit, ROCKET_EXPLOSION_RADIUS);
         }
     }
 
-    pub fn spawn_model(&self, commands: &mut EntityCommands) {
+    pub fn spawn_model(&self, commands: &mut EntityCommands, direction: Vec2) {
         match self {
             Self::Bolt(_) => {
+                // a green bolt of electricity
+                let half_length = direction * BOLT_LENGTH / 2.0;
+
                 commands.insert_bundle(GeometryBuilder::build_as(
-                    // TODO: this is the wrong shape for a bolt
-                    // (a green bolt of electricity)
-                    &shapes::Circle {
-                        radius: BOLT_RADIUS,
-                        ..Default::default()
-                    },
-                    DrawMode::Fill(FillMode {
-                        color: Color::LIME_GREEN,
-                        options: FillOptions::default(),
-                    }),
+                    &shapes::Line(-half_length, half_length),
+                    DrawMode::Stroke(StrokeMode::new(Color::LIME_GREEN, BOLT_RADIUS * 2.0)),
                     Transform::default(),
                 ));
             }
@@ -160,32 +206,41 @@ impl Projectile {
             }
             Self::Rocket(_) => {
                 commands.insert_bundle(GeometryBuilder::build_as(
-                    // TODO: this is the wrong shape for a rocket
-                    &shapes::Circle {
-                        radius: ROCKET_RADIUS,
+                    &shapes::Rectangle {
+                        extents: Vec2::new(ROCKET_LENGTH, ROCKET_RADIUS * 2.0),
                         ..Default::default()
                     },
                     DrawMode::Fill(FillMode {
                         color: Color::ORANGE_RED,
                         options: FillOptions::default(),
                     }),
-                    Transform::default(),
+                    Transform::from_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
                 ));
             }
             Self::Slug(_) => {
-                commands.insert_bundle(GeometryBuilder::build_as(
-                    // TODO: this is the wrong shape for a slug
-                    &shapes::Circle {
-                        radius: SLUG_RADIUS,
-                        ..Default::default()
-                    },
-                    DrawMode::Fill(FillMode {
-                        color: Color::PURPLE,
-                        options: FillOptions::default(),
-                    }),
-                    Transform::default(),
-                ));
+                // slugs are hit-scan, their trace is spawned once they've been traced
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rocket_splash_damage_falls_off() {
+        let rocket = Projectile::Rocket(Entity::from_raw(0));
+
+        assert_eq!(rocket.get_splash_damage(0.0), rocket.get_damage());
+        assert_eq!(
+            rocket.get_splash_damage(ROCKET_EXPLOSION_RADIUS / 2.0),
+            rocket.get_damage() / 2
+        );
+        assert_eq!(rocket.get_splash_damage(ROCKET_EXPLOSION_RADIUS), 0);
+        assert_eq!(rocket.get_splash_damage(ROCKET_EXPLOSION_RADIUS * 2.0), 0);
+
+        let bolt = Projectile::Bolt(Entity::from_raw(0));
+        assert_eq!(bolt.get_splash_damage(0.0), 0);
+    }
+}
@@ -1,159 +1,183 @@
 use bevy::ecs::query::WorldQuery;
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
+use game_ai_core::physics::Kinematic;
 
-use super::physics::*;
+pub use game_ai_core::path::WaypointPath;
+pub use game_ai_core::steering::*;
 
-use crate::util::*;
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Inspectable)]
+pub enum PathSmoothing {
+    None,
 
-pub trait SteeringBehavior: std::fmt::Debug + Component {}
+    // only checks neighboring waypoints
+    #[default]
+    Quick,
 
-#[derive(Debug, Default, Component, Inspectable)]
-#[component(storage = "SparseSet")]
-pub struct Steering {
-    pub target: Vec2,
-    pub accumulated_force: Vec2,
+    // checks every later waypoint
+    Precise,
 }
 
-impl Steering {
-    pub fn is_at_target(&self, range_squared: f32, transform: &Transform) -> bool {
-        transform
-            .translation
-            .truncate()
-            .distance_squared(self.target)
-            < range_squared
-    }
-
-    pub fn accumulate_force(&mut self, physical: &Physical, force: Vec2, weight: f32) {
-        let force = force * weight;
-
-        let mag_so_far = self.accumulated_force.length();
-        let mag_remain = physical.max_force - mag_so_far;
-        if mag_remain <= 0.0 {
-            warn!("too much steering force");
-            return;
+// removes waypoints that can be skipped
+// can_walk should return true if the bot can move unobstructed between two points
+pub fn smooth_path(
+    points: &mut Vec<Vec2>,
+    smoothing: PathSmoothing,
+    can_walk: impl Fn(Vec2, Vec2) -> bool,
+) {
+    match smoothing {
+        PathSmoothing::None => (),
+        PathSmoothing::Quick => {
+            let mut idx = 0;
+            while idx + 2 < points.len() {
+                if can_walk(points[idx], points[idx + 2]) {
+                    points.remove(idx + 1);
+                } else {
+                    idx += 1;
+                }
+            }
         }
-
-        let mut to_add = force.length();
-        if to_add > mag_remain {
-            to_add = mag_remain;
+        PathSmoothing::Precise => {
+            let mut idx = 0;
+            while idx + 2 < points.len() {
+                // skip to the furthest point that can be walked to
+                let mut furthest = points.len() - 1;
+                while furthest > idx + 1 && !can_walk(points[idx], points[furthest]) {
+                    furthest -= 1;
+                }
+
+                points.drain(idx + 1..furthest);
+                idx += 1;
+            }
         }
-
-        self.accumulated_force += force.normalize_or_zero() * to_add;
-    }
-
-    pub fn update_physical(&mut self, physical: &mut Physical) {
-        physical.apply_force(self.accumulated_force * physical.mass);
-        self.accumulated_force = Vec2::ZERO;
     }
 }
 
-#[derive(WorldQuery)]
-#[world_query(mutable, derive(Debug))]
-pub struct SteeringQueryMut {
-    pub steering: &'static mut Steering,
-    pub physical: &'static mut Physical,
+// seeks each waypoint in turn, arriving at the last one unless the path loops
+#[derive(Debug, Default, Component, Inspectable)]
+#[component(storage = "SparseSet")]
+pub struct FollowPath {
+    pub path: WaypointPath,
 }
 
-#[derive(Debug, Default, Component)]
-pub struct SteeringTargetDebug;
+impl SteeringBehavior for FollowPath {}
 
-fn seek_force(target: Vec2, physical: &PhysicalQueryItem) -> Vec2 {
-    let position = physical.transform.translation.truncate();
+impl FollowPath {
+    pub fn new(waypoints: Vec<Vec2>, looped: bool) -> Self {
+        Self {
+            path: WaypointPath::new(waypoints, looped),
+        }
+    }
 
-    let desired_velocity = (target - position).normalize_or_zero() * physical.physical.max_speed;
-    desired_velocity - physical.physical.velocity
-}
+    // smooths the remaining waypoints, starting from the given position
+    pub fn smooth(
+        &mut self,
+        position: Vec2,
+        smoothing: PathSmoothing,
+        can_walk: impl Fn(Vec2, Vec2) -> bool,
+    ) {
+        let mut points = vec![position];
+        if self.path.looped {
+            // every waypoint will be revisited
+            points.extend_from_slice(&self.path.waypoints);
+        } else {
+            points.extend_from_slice(self.path.remaining_waypoints());
+        }
 
-#[derive(Debug, Default, Component, Inspectable)]
-#[component(storage = "SparseSet")]
-pub struct Seek;
+        smooth_path(&mut points, smoothing, can_walk);
 
-impl SteeringBehavior for Seek {}
+        self.path = WaypointPath::new(points.split_off(1), self.path.looped);
+    }
 
-impl Seek {
-    pub fn force(&self, steering: &Steering, physical: &PhysicalQueryItem) -> Vec2 {
-        seek_force(steering.target, physical)
+    pub fn force(&self, transform: &Transform, physical: &impl Kinematic) -> Vec2 {
+        self.path.force(transform, physical)
     }
 }
 
 #[derive(WorldQuery)]
 #[world_query(mutable, derive(Debug))]
-pub struct SeekQueryMut {
-    pub seek: &'static Seek,
+pub struct FollowPathQueryMut {
+    pub follow_path: &'static mut FollowPath,
     pub steering: &'static mut Steering,
 }
 
-#[derive(Debug, Clone, Copy, Inspectable)]
-pub enum Deceleration {
-    Slow = 3,
-    Normal = 2,
-    Fast = 1,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl Default for Deceleration {
-    fn default() -> Self {
-        Self::Normal
+    // a wall at x = 5 from y = -10 to 10
+    fn can_walk(from: Vec2, to: Vec2) -> bool {
+        (from.x < 5.0) == (to.x < 5.0) || from.y.min(to.y) > 10.0
     }
-}
-
-fn arrive_force(target: Vec2, physical: &PhysicalQueryItem, deceleration: Deceleration) -> Vec2 {
-    let position = physical.transform.translation.truncate();
-    let deceleration = deceleration as i32;
-
-    let to_target = target - position;
 
-    let dist = to_target.length();
-    if dist > 0.0 {
-        // fine tweaking of deceleration
-        let deceleration_tweaker = 0.3;
-
-        let speed =
-            (dist / (deceleration as f32 * deceleration_tweaker)).min(physical.physical.max_speed);
-        let desired_velocity = to_target * speed / dist;
-        return desired_velocity - physical.physical.velocity;
+    fn around_the_wall() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 5.0),
+            Vec2::new(0.0, 15.0),
+            Vec2::new(5.0, 15.0),
+            Vec2::new(10.0, 15.0),
+            Vec2::new(10.0, 5.0),
+            Vec2::new(10.0, 0.0),
+        ]
     }
 
-    Vec2::ZERO
-}
-
-#[derive(Debug, Default, Component, Inspectable)]
-#[component(storage = "SparseSet")]
-pub struct Arrive {
-    pub deceleration: Deceleration,
-}
-
-impl SteeringBehavior for Arrive {}
-
-impl Arrive {
-    pub fn force(&self, steering: &Steering, physical: &PhysicalQueryItem) -> Vec2 {
-        arrive_force(steering.target, physical, self.deceleration)
+    #[test]
+    fn smooth_path_none() {
+        let mut points = around_the_wall();
+        smooth_path(&mut points, PathSmoothing::None, can_walk);
+        assert_eq!(points, around_the_wall());
     }
-}
-
-#[derive(WorldQuery)]
-#[world_query(mutable, derive(Debug))]
-pub struct ArriveQueryMut {
-    pub arrive: &'static Arrive,
-    pub steering: &'static mut Steering,
-}
 
-#[derive(Debug, Default, Component, Inspectable)]
-pub struct WallAvoidance {
-    pub feelers: [Vec2; 3],
-}
-
-impl WallAvoidance {
-    pub fn create_feelers(&mut self, position: Vec2, heading: Vec2, feeler_length: f32) {
-        // straight ahead
-        self.feelers[0] = position + feeler_length * heading;
+    #[test]
+    fn smooth_path_quick() {
+        let mut points = around_the_wall();
+        smooth_path(&mut points, PathSmoothing::Quick, can_walk);
+        assert_eq!(
+            points,
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(0.0, 15.0),
+                Vec2::new(10.0, 15.0),
+                Vec2::new(10.0, 0.0),
+            ]
+        );
+    }
 
-        // left
-        let temp = heading.rotate_origin(std::f32::consts::FRAC_PI_2 * 3.5);
-        self.feelers[1] = position + feeler_length * 0.5 * temp;
+    #[test]
+    fn smooth_path_precise() {
+        // a detour that quick smoothing can't see past
+        let mut points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(-5.0, 0.0),
+            Vec2::new(-5.0, 5.0),
+            Vec2::new(0.0, 5.0),
+        ];
+
+        let can_walk = |from: Vec2, to: Vec2| {
+            from.distance(to) <= 5.0 || (from == Vec2::ZERO && to == Vec2::new(0.0, 5.0))
+        };
+
+        let mut quick = points.clone();
+        smooth_path(&mut quick, PathSmoothing::Quick, can_walk);
+        assert_eq!(quick.len(), 4);
+
+        smooth_path(&mut points, PathSmoothing::Precise, can_walk);
+        assert_eq!(points, vec![Vec2::new(0.0, 0.0), Vec2::new(0.0, 5.0)]);
+    }
 
-        // right
-        let temp = heading.rotate_origin(std::f32::consts::FRAC_2_PI * 0.5);
-        self.feelers[2] = position + feeler_length * 0.5 * temp;
+    #[test]
+    fn follow_path_smooth() {
+        let mut path = FollowPath::new(around_the_wall()[1..].to_vec(), false);
+        path.smooth(Vec2::ZERO, PathSmoothing::Quick, can_walk);
+        assert_eq!(
+            path.path.waypoints,
+            vec![
+                Vec2::new(0.0, 15.0),
+                Vec2::new(10.0, 15.0),
+                Vec2::new(10.0, 0.0),
+            ]
+        );
+        assert_eq!(path.path.current_waypoint(), Some(Vec2::new(0.0, 15.0)));
     }
 }
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use super::memory::*;
+
+// the bot being attacked and where it was last sensed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub visible: bool,
+}
+
+// picks the closest opponent in the bot's sensory memory
+#[derive(Debug, Default, Component)]
+pub struct Targeting {
+    target: Option<Entity>,
+}
+
+impl Targeting {
+    pub fn update(
+        &mut self,
+        now: f32,
+        memory: &SensoryMemory,
+        position: Vec2,
+        name: impl AsRef<str>,
+    ) {
+        let closest = memory
+            .get_recently_sensed_opponents(now)
+            .min_by(|(_, a), (_, b)| {
+                position
+                    .distance_squared(a.last_sensed_position)
+                    .total_cmp(&position.distance_squared(b.last_sensed_position))
+            })
+            .map(|(entity, _)| entity);
+
+        if closest != self.target {
+            match closest {
+                Some(entity) => info!("[{}]: targeting {:?}", name.as_ref(), entity),
+                None => info!("[{}]: lost target", name.as_ref()),
+            }
+        }
+
+        self.target = closest;
+    }
+
+    pub fn get_target(&self, memory: &SensoryMemory) -> Option<Target> {
+        let entity = self.target?;
+        let record = memory.get_record(entity)?;
+
+        Some(Target {
+            entity,
+            position: record.last_sensed_position,
+            visible: record.shootable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_remembered_opponent() {
+        let mut memory = SensoryMemory::default();
+        let near = Entity::from_raw(1);
+        let far = Entity::from_raw(2);
+        let forgotten = Entity::from_raw(3);
+
+        memory.update_with_sound(0.0, forgotten, Vec2::new(1.0, 0.0));
+        memory.update_with_sound(10.0, far, Vec2::new(0.0, 20.0));
+        memory.update_vision(10.0, Vec2::ZERO, &[(near, Vec2::new(0.0, 10.0), true)]);
+
+        let mut targeting = Targeting::default();
+        targeting.update(10.0, &memory, Vec2::ZERO, "test");
+
+        let target = targeting.get_target(&memory).unwrap();
+        assert_eq!(target.entity, near);
+        assert_eq!(target.position, Vec2::new(0.0, 10.0));
+        assert!(target.visible);
+    }
+}
@@ -1,10 +1,10 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use game_ai_core::cooldown::*;
 
 use crate::components::bot::*;
 use crate::components::inventory::*;
-use crate::game::cooldown::*;
 use crate::game::weapons::*;
 
 // TODO: pull trigger parameters from a config
@@ -14,6 +14,9 @@ pub enum Trigger {
     Weapon(Weapon, Cooldown),
     Health(Cooldown),
     Sound(Entity, Timer),
+
+    // opens the door entity
+    DoorSwitch(Entity),
 }
 
 impl Trigger {
@@ -22,16 +25,11 @@ impl Trigger {
             Self::Weapon(_, _) => Color::GOLD,
             Self::Health(_) => Color::DARK_GREEN,
             Self::Sound(_, _) => Color::CRIMSON,
+            Self::DoorSwitch(_) => Color::SILVER,
         }
     }
 
-    pub fn trigger(
-        &mut self,
-        entity: Entity,
-        bot: &mut Bot,
-        inventory: &mut Inventory,
-        name: impl AsRef<str>,
-    ) {
+    pub fn trigger(&mut self, bot: &mut Bot, inventory: &mut Inventory, name: impl AsRef<str>) {
         if !bot.is_alive() {
             return;
         }
@@ -61,12 +59,12 @@ impl Trigger {
 
                 cooldown.start();
             }
-            Self::Sound(owner, _) => {
-                if entity == *owner {
-                    return;
-                }
-
-                warn!("[{}] heard a sound!", name.as_ref());
+            Self::Sound(_, _) => {
+                // sounds are heard by the sensory memory system
+            }
+            Self::DoorSwitch(_) => {
+                // doors are opened by the trigger system
+                info!("[{}]: door switch!", name.as_ref());
             }
         }
     }
@@ -88,6 +86,7 @@ impl Trigger {
                     commands.entity(entity).despawn_recursive();
                 }
             }
+            Self::DoorSwitch(_) => (),
         }
     }
 }
@@ -1,14 +1,19 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
+use game_ai_core::cooldown::*;
+use strum::IntoEnumIterator;
 
 use crate::bundles::projectile::*;
 use crate::bundles::trigger::*;
 use crate::components::inventory::*;
 use crate::components::projectile::*;
 use crate::components::trigger::*;
-use crate::game::cooldown::*;
+use crate::game::fuzzy::*;
 use crate::game::weapons::*;
-use crate::game::PHYSICS_STEP;
+use crate::game::{PHYSICS_STEP, WEAPON_SELECTION_SECONDS};
 use crate::util::*;
 
 // TODO: weapon cooldown could be better:
@@ -128,3 +133,80 @@ impl EquippedWeapon {
         );
     }
 }
+
+// picks the most desirable weapon for the distance to the target
+#[derive(Debug, Component, Inspectable)]
+pub struct WeaponSelector {
+    // off while the weapon is being picked by hand
+    pub auto_select: bool,
+
+    #[inspectable(ignore)]
+    modules: HashMap<Weapon, FuzzyModule>,
+
+    #[inspectable(ignore)]
+    timer: Timer,
+}
+
+impl Default for WeaponSelector {
+    fn default() -> Self {
+        Self {
+            auto_select: true,
+            modules: Weapon::iter()
+                .map(|weapon| (weapon, weapon.create_fuzzy_module()))
+                .collect(),
+            timer: Timer::from_seconds(WEAPON_SELECTION_SECONDS, true),
+        }
+    }
+}
+
+impl WeaponSelector {
+    // true if it's time to re-evaluate the weapon
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.timer.tick(Duration::from_secs_f32(dt));
+        self.timer.just_finished()
+    }
+
+    // 0 if the weapon isn't held or is out of ammo
+    pub fn get_desirability(
+        &mut self,
+        weapon: Weapon,
+        inventory: &Inventory,
+        distance: f32,
+    ) -> f32 {
+        if !inventory.has_weapon(weapon) {
+            return 0.0;
+        }
+
+        let ammo = inventory.get_ammo_amount(weapon);
+        if weapon != Weapon::Blaster && ammo < 1 {
+            return 0.0;
+        }
+
+        let module = self.modules.get_mut(&weapon).unwrap();
+        weapon.get_desirability(module, distance, ammo)
+    }
+
+    pub fn get_best_weapon(&mut self, inventory: &Inventory, distance: f32) -> Weapon {
+        let mut best = (Weapon::Blaster, 0.0);
+        for weapon in Weapon::iter() {
+            let desirability = self.get_desirability(weapon, inventory, distance);
+            if desirability > best.1 {
+                best = (weapon, desirability);
+            }
+        }
+        best.0
+    }
+
+    pub fn select_best(
+        &mut self,
+        weapon: &mut EquippedWeapon,
+        inventory: &Inventory,
+        distance: f32,
+        name: impl AsRef<str>,
+    ) {
+        let best = self.get_best_weapon(inventory, distance);
+        if best != weapon.weapon {
+            weapon.select(inventory, best, name);
+        }
+    }
+}
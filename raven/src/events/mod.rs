@@ -1 +1,2 @@
 pub mod debug;
+pub mod path;
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+pub struct PathReadyEvent {
+    pub entity: Entity,
+    pub path: Vec<Vec2>,
+}
+
+pub struct NoPathAvailableEvent {
+    pub entity: Entity,
+}
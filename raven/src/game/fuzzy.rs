@@ -0,0 +1,475 @@
+// Programming Game AI by Example, chapter 10
+
+fn is_equal(a: f32, b: f32) -> bool {
+    (a - b).abs() < f32::EPSILON
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FuzzySetShape {
+    Triangular {
+        peak: f32,
+        left_offset: f32,
+        right_offset: f32,
+    },
+
+    // full membership left of the peak
+    LeftShoulder {
+        peak: f32,
+        left_offset: f32,
+        right_offset: f32,
+    },
+
+    // full membership right of the peak
+    RightShoulder {
+        peak: f32,
+        left_offset: f32,
+        right_offset: f32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct FuzzySet {
+    pub shape: FuzzySetShape,
+
+    // degree of membership of the last fuzzified value
+    dom: f32,
+}
+
+impl FuzzySet {
+    pub fn new(shape: FuzzySetShape) -> Self {
+        Self { shape, dom: 0.0 }
+    }
+
+    // the value that best represents the set, used by defuzzification
+    pub fn representative_value(&self) -> f32 {
+        match self.shape {
+            FuzzySetShape::Triangular { peak, .. } => peak,
+            FuzzySetShape::LeftShoulder {
+                peak, left_offset, ..
+            } => peak - left_offset / 2.0,
+            FuzzySetShape::RightShoulder {
+                peak, right_offset, ..
+            } => peak + right_offset / 2.0,
+        }
+    }
+
+    pub fn calculate_dom(&self, value: f32) -> f32 {
+        match self.shape {
+            FuzzySetShape::Triangular {
+                peak,
+                left_offset,
+                right_offset,
+            } => {
+                // offsets of 0 would divide by 0
+                if (is_equal(right_offset, 0.0) || is_equal(left_offset, 0.0))
+                    && is_equal(peak, value)
+                {
+                    1.0
+                } else if value <= peak && value >= peak - left_offset {
+                    (value - (peak - left_offset)) / left_offset
+                } else if value > peak && value < peak + right_offset {
+                    1.0 - (value - peak) / right_offset
+                } else {
+                    0.0
+                }
+            }
+            FuzzySetShape::LeftShoulder {
+                peak,
+                left_offset,
+                right_offset,
+            } => {
+                if (is_equal(right_offset, 0.0) || is_equal(left_offset, 0.0))
+                    && is_equal(peak, value)
+                {
+                    1.0
+                } else if value >= peak && value < peak + right_offset {
+                    1.0 - (value - peak) / right_offset
+                } else if value < peak && value >= peak - left_offset {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            FuzzySetShape::RightShoulder {
+                peak,
+                left_offset,
+                right_offset,
+            } => {
+                if (is_equal(right_offset, 0.0) || is_equal(left_offset, 0.0))
+                    && is_equal(peak, value)
+                {
+                    1.0
+                } else if value <= peak && value > peak - left_offset {
+                    (value - (peak - left_offset)) / left_offset
+                } else if value > peak && value <= peak + right_offset {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    fn or_with_dom(&mut self, dom: f32) {
+        if dom > self.dom {
+            self.dom = dom;
+        }
+    }
+
+    fn clear_dom(&mut self) {
+        self.dom = 0.0;
+    }
+}
+
+// a set in a module's variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FzSet {
+    variable: usize,
+    set: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FuzzyVariable {
+    pub name: String,
+
+    // index in the owning module
+    index: usize,
+
+    sets: Vec<FuzzySet>,
+
+    min_range: f32,
+    max_range: f32,
+}
+
+impl FuzzyVariable {
+    fn new(name: impl Into<String>, index: usize) -> Self {
+        Self {
+            name: name.into(),
+            index,
+            sets: vec![],
+            min_range: 0.0,
+            max_range: 0.0,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn sets(&self) -> &[FuzzySet] {
+        &self.sets
+    }
+
+    fn add_set(&mut self, shape: FuzzySetShape, min: f32, max: f32) -> FzSet {
+        self.sets.push(FuzzySet::new(shape));
+
+        self.min_range = self.min_range.min(min);
+        self.max_range = self.max_range.max(max);
+
+        FzSet {
+            variable: self.index,
+            set: self.sets.len() - 1,
+        }
+    }
+
+    pub fn add_triangular_set(&mut self, min: f32, peak: f32, max: f32) -> FzSet {
+        self.add_set(
+            FuzzySetShape::Triangular {
+                peak,
+                left_offset: peak - min,
+                right_offset: max - peak,
+            },
+            min,
+            max,
+        )
+    }
+
+    pub fn add_left_shoulder_set(&mut self, min: f32, peak: f32, max: f32) -> FzSet {
+        self.add_set(
+            FuzzySetShape::LeftShoulder {
+                peak,
+                left_offset: peak - min,
+                right_offset: max - peak,
+            },
+            min,
+            max,
+        )
+    }
+
+    pub fn add_right_shoulder_set(&mut self, min: f32, peak: f32, max: f32) -> FzSet {
+        self.add_set(
+            FuzzySetShape::RightShoulder {
+                peak,
+                left_offset: peak - min,
+                right_offset: max - peak,
+            },
+            min,
+            max,
+        )
+    }
+
+    // values outside of the variable's range are clamped
+    pub fn fuzzify(&mut self, value: f32) {
+        let value = value.clamp(self.min_range, self.max_range);
+        for set in self.sets.iter_mut() {
+            set.dom = set.calculate_dom(value);
+        }
+    }
+
+    // the average of each set's representative value, weighted by its membership
+    pub fn defuzzify(&self) -> f32 {
+        let (top, bottom) = self.sets.iter().fold((0.0, 0.0), |(top, bottom), set| {
+            (top + set.representative_value() * set.dom, bottom + set.dom)
+        });
+
+        if is_equal(bottom, 0.0) {
+            return 0.0;
+        }
+
+        top / bottom
+    }
+}
+
+// rule antecedents and consequences
+#[derive(Debug, Clone)]
+pub enum FuzzyTerm {
+    Set(FzSet),
+    And(Vec<FuzzyTerm>),
+    Or(Vec<FuzzyTerm>),
+    Very(Box<FuzzyTerm>),
+    Fairly(Box<FuzzyTerm>),
+}
+
+impl From<FzSet> for FuzzyTerm {
+    fn from(set: FzSet) -> Self {
+        Self::Set(set)
+    }
+}
+
+pub fn fz_and(a: impl Into<FuzzyTerm>, b: impl Into<FuzzyTerm>) -> FuzzyTerm {
+    FuzzyTerm::And(vec![a.into(), b.into()])
+}
+
+pub fn fz_or(a: impl Into<FuzzyTerm>, b: impl Into<FuzzyTerm>) -> FuzzyTerm {
+    FuzzyTerm::Or(vec![a.into(), b.into()])
+}
+
+pub fn fz_very(term: impl Into<FuzzyTerm>) -> FuzzyTerm {
+    FuzzyTerm::Very(Box::new(term.into()))
+}
+
+pub fn fz_fairly(term: impl Into<FuzzyTerm>) -> FuzzyTerm {
+    FuzzyTerm::Fairly(Box::new(term.into()))
+}
+
+impl FuzzyTerm {
+    fn dom(&self, variables: &[FuzzyVariable]) -> f32 {
+        match self {
+            Self::Set(set) => variables[set.variable].sets[set.set].dom,
+            Self::And(terms) => terms
+                .iter()
+                .map(|term| term.dom(variables))
+                .fold(1.0, f32::min),
+            Self::Or(terms) => terms
+                .iter()
+                .map(|term| term.dom(variables))
+                .fold(0.0, f32::max),
+            Self::Very(term) => term.dom(variables).powi(2),
+            Self::Fairly(term) => term.dom(variables).sqrt(),
+        }
+    }
+
+    fn or_with_dom(&self, variables: &mut [FuzzyVariable], dom: f32) {
+        match self {
+            Self::Set(set) => variables[set.variable].sets[set.set].or_with_dom(dom),
+            Self::And(terms) | Self::Or(terms) => {
+                for term in terms {
+                    term.or_with_dom(variables, dom);
+                }
+            }
+            Self::Very(term) => term.or_with_dom(variables, dom.powi(2)),
+            Self::Fairly(term) => term.or_with_dom(variables, dom.sqrt()),
+        }
+    }
+
+    fn clear_dom(&self, variables: &mut [FuzzyVariable]) {
+        match self {
+            Self::Set(set) => variables[set.variable].sets[set.set].clear_dom(),
+            Self::And(terms) | Self::Or(terms) => {
+                for term in terms {
+                    term.clear_dom(variables);
+                }
+            }
+            Self::Very(term) | Self::Fairly(term) => term.clear_dom(variables),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FuzzyRule {
+    pub antecedent: FuzzyTerm,
+    pub consequence: FuzzyTerm,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FuzzyModule {
+    variables: Vec<FuzzyVariable>,
+    rules: Vec<FuzzyRule>,
+}
+
+impl FuzzyModule {
+    pub fn create_variable(&mut self, name: impl Into<String>) -> &mut FuzzyVariable {
+        let index = self.variables.len();
+        self.variables.push(FuzzyVariable::new(name, index));
+        &mut self.variables[index]
+    }
+
+    fn variable_mut(&mut self, name: impl AsRef<str>) -> &mut FuzzyVariable {
+        let name = name.as_ref();
+        self.variables
+            .iter_mut()
+            .find(|variable| variable.name == name)
+            .unwrap_or_else(|| panic!("no fuzzy variable '{}'", name))
+    }
+
+    pub fn add_rule(
+        &mut self,
+        antecedent: impl Into<FuzzyTerm>,
+        consequence: impl Into<FuzzyTerm>,
+    ) {
+        self.rules.push(FuzzyRule {
+            antecedent: antecedent.into(),
+            consequence: consequence.into(),
+        });
+    }
+
+    pub fn fuzzify(&mut self, name: impl AsRef<str>, value: f32) {
+        self.variable_mut(name).fuzzify(value);
+    }
+
+    // fires every rule and defuzzifies the named (consequence) variable
+    pub fn defuzzify(&mut self, name: impl AsRef<str>) -> f32 {
+        for rule in self.rules.iter() {
+            rule.consequence.clear_dom(&mut self.variables);
+        }
+
+        for rule in self.rules.iter() {
+            let dom = rule.antecedent.dom(&self.variables);
+            rule.consequence.or_with_dom(&mut self.variables, dom);
+        }
+
+        self.variable_mut(name).defuzzify()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 0.001, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn triangular_dom() {
+        let set = FuzzySet::new(FuzzySetShape::Triangular {
+            peak: 10.0,
+            left_offset: 10.0,
+            right_offset: 20.0,
+        });
+
+        assert_near(set.calculate_dom(-1.0), 0.0);
+        assert_near(set.calculate_dom(5.0), 0.5);
+        assert_near(set.calculate_dom(10.0), 1.0);
+        assert_near(set.calculate_dom(20.0), 0.5);
+        assert_near(set.calculate_dom(30.0), 0.0);
+        assert_near(set.representative_value(), 10.0);
+    }
+
+    #[test]
+    fn shoulder_dom() {
+        let mut variable = FuzzyVariable::new("Variable", 0);
+        variable.add_left_shoulder_set(0.0, 10.0, 20.0);
+        variable.add_right_shoulder_set(10.0, 20.0, 30.0);
+
+        let left = &variable.sets()[0];
+        assert_near(left.calculate_dom(0.0), 1.0);
+        assert_near(left.calculate_dom(10.0), 1.0);
+        assert_near(left.calculate_dom(15.0), 0.5);
+        assert_near(left.calculate_dom(25.0), 0.0);
+        assert_near(left.representative_value(), 5.0);
+
+        let right = &variable.sets()[1];
+        assert_near(right.calculate_dom(5.0), 0.0);
+        assert_near(right.calculate_dom(15.0), 0.5);
+        assert_near(right.calculate_dom(30.0), 1.0);
+        assert_near(right.representative_value(), 25.0);
+    }
+
+    #[test]
+    fn zero_offset_peak() {
+        let mut variable = FuzzyVariable::new("Variable", 0);
+        variable.add_triangular_set(0.0, 0.0, 10.0);
+
+        let low = &variable.sets()[0];
+        assert_near(low.calculate_dom(0.0), 1.0);
+        assert_near(low.calculate_dom(5.0), 0.5);
+    }
+
+    // a small module: the closer and hungrier, the more desirable the food
+    fn food_module() -> FuzzyModule {
+        let mut module = FuzzyModule::default();
+
+        let distance = module.create_variable("Distance");
+        let close = distance.add_left_shoulder_set(0.0, 10.0, 50.0);
+        let far = distance.add_right_shoulder_set(10.0, 50.0, 100.0);
+
+        let hunger = module.create_variable("Hunger");
+        let hungry = hunger.add_right_shoulder_set(0.0, 100.0, 100.0);
+        let full = hunger.add_left_shoulder_set(0.0, 0.0, 100.0);
+
+        let desirability = module.create_variable("Desirability");
+        let undesirable = desirability.add_left_shoulder_set(0.0, 25.0, 50.0);
+        let desirable = desirability.add_triangular_set(25.0, 50.0, 75.0);
+        let very_desirable = desirability.add_right_shoulder_set(50.0, 75.0, 100.0);
+
+        module.add_rule(fz_and(close, hungry), very_desirable);
+        module.add_rule(fz_and(far, hungry), desirable);
+        module.add_rule(fz_or(full, fz_very(far)), undesirable);
+
+        module
+    }
+
+    #[test]
+    fn module_defuzzify() {
+        let mut module = food_module();
+
+        module.fuzzify("Distance", 10.0);
+        module.fuzzify("Hunger", 100.0);
+        let close = module.defuzzify("Desirability");
+
+        // only very desirable fires
+        assert_near(close, 87.5);
+
+        module.fuzzify("Distance", 100.0);
+        let far = module.defuzzify("Desirability");
+        assert!(far < close);
+
+        module.fuzzify("Hunger", 0.0);
+        assert!(module.defuzzify("Desirability") < far);
+    }
+
+    #[test]
+    fn hedges() {
+        let mut module = food_module();
+        module.fuzzify("Distance", 30.0);
+
+        let variables = &module.variables;
+        let far = FzSet {
+            variable: 0,
+            set: 1,
+        };
+
+        let dom = FuzzyTerm::from(far).dom(variables);
+        assert_near(dom, 0.5);
+        assert_near(fz_very(far).dom(variables), 0.25);
+        assert_near(fz_fairly(far).dom(variables), 0.5_f32.sqrt());
+    }
+}
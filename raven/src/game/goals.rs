@@ -0,0 +1,943 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use game_ai_core::random::Random;
+use rand::Rng;
+use strum::IntoEnumIterator;
+
+use crate::components::agent::*;
+use crate::components::physics::*;
+use crate::components::planner::*;
+use crate::components::steering::*;
+use crate::components::targeting::Target;
+use crate::game::weapons::*;
+use crate::game::{
+    ATTACK_RANGE, EXPLORE_BIAS, GET_HEALTH_TWEAKER, GET_WEAPON_TWEAKER, GOAL_ARRIVE_DISTANCE,
+    GOAL_MAX_ITEM_DISTANCE, GOAL_MIN_ITEM_DISTANCE, GOAL_TIME_MARGIN_SECONDS,
+};
+use crate::resources::navgraph::*;
+use crate::resources::path::*;
+
+// Programming Game AI by Example, chapter 9
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GoalStatus {
+    #[default]
+    Inactive,
+    Active,
+    Completed,
+    Failed,
+}
+
+// everything a goal needs to drive its bot
+pub struct GoalContext<'a, 'w, 's> {
+    pub commands: &'a mut Commands<'w, 's>,
+    pub manager: &'a mut PathManager,
+    pub navgraph: Option<&'a NavGraph>,
+    pub random: &'a mut Random,
+
+    pub entity: Entity,
+    pub agent: &'a Agent,
+    pub steering: &'a mut Steering,
+    pub physical: &'a mut Physical,
+    pub planner: &'a mut PathPlanner,
+    pub position: Vec2,
+    pub target: Option<Target>,
+    pub name: &'a str,
+
+    pub dt: f32,
+}
+
+// how long it should take to walk the given distance, with some slack
+fn expected_time(distance: f32, max_speed: f32) -> f32 {
+    if max_speed > 0.0 {
+        distance / max_speed * 2.0 + GOAL_TIME_MARGIN_SECONDS
+    } else {
+        GOAL_TIME_MARGIN_SECONDS
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoalType {
+    // atomic
+    SeekToPosition(Vec2),
+
+    // composite
+    FollowPath(Vec<Vec2>),
+    MoveToPosition(Vec2),
+    Explore,
+    GetHealth(Vec2),
+    GetWeapon(Weapon, Vec2),
+    AttackTarget,
+}
+
+impl GoalType {
+    pub fn get_name(&self) -> String {
+        match self {
+            Self::SeekToPosition(position) => format!("Seek To Position {}", position),
+            Self::FollowPath(waypoints) => format!("Follow Path ({} waypoints)", waypoints.len()),
+            Self::MoveToPosition(position) => format!("Move To Position {}", position),
+            Self::Explore => "Explore".to_owned(),
+            Self::GetHealth(_) => "Get Health".to_owned(),
+            Self::GetWeapon(weapon, _) => format!("Get {}", weapon.get_name()),
+            Self::AttackTarget => "Attack Target".to_owned(),
+        }
+    }
+}
+
+// atomic goals never have subgoals,
+// composite goals work through their subgoals from the back of the stack
+#[derive(Debug)]
+pub struct Goal {
+    goal_type: GoalType,
+    status: GoalStatus,
+    subgoals: Vec<Goal>,
+
+    // seconds spent active and how long the goal is expected to take
+    elapsed: f32,
+    time_limit: f32,
+}
+
+impl Goal {
+    pub fn new(goal_type: GoalType) -> Self {
+        Self {
+            goal_type,
+            status: GoalStatus::default(),
+            subgoals: vec![],
+            elapsed: 0.0,
+            time_limit: f32::MAX,
+        }
+    }
+
+    pub fn get_type(&self) -> &GoalType {
+        &self.goal_type
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == GoalStatus::Active
+    }
+
+    pub fn is_inactive(&self) -> bool {
+        self.status == GoalStatus::Inactive
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.status == GoalStatus::Completed
+    }
+
+    pub fn has_failed(&self) -> bool {
+        self.status == GoalStatus::Failed
+    }
+
+    // the goal and its subgoals, front most last, with their depth
+    pub fn describe(&self, depth: usize, out: &mut Vec<(usize, String, GoalStatus)>) {
+        out.push((depth, self.goal_type.get_name(), self.status));
+        for subgoal in self.subgoals.iter().rev() {
+            subgoal.describe(depth + 1, out);
+        }
+    }
+
+    fn add_subgoal(&mut self, goal: Goal) {
+        self.subgoals.push(goal);
+    }
+
+    fn remove_all_subgoals(&mut self, ctx: &mut GoalContext) {
+        while let Some(mut subgoal) = self.subgoals.pop() {
+            subgoal.terminate(ctx);
+        }
+    }
+
+    // drops finished subgoals and processes the front one
+    // a subgoal that failed outside of processing (no path) fails the goal
+    fn process_subgoals(&mut self, ctx: &mut GoalContext) -> GoalStatus {
+        let mut failed = false;
+        while let Some(subgoal) = self.subgoals.last_mut() {
+            if !subgoal.is_complete() && !subgoal.has_failed() {
+                break;
+            }
+
+            failed |= subgoal.has_failed();
+
+            subgoal.terminate(ctx);
+            self.subgoals.pop();
+        }
+
+        if failed {
+            self.remove_all_subgoals(ctx);
+            return GoalStatus::Failed;
+        }
+
+        let subgoals = self.subgoals.len();
+        match self.subgoals.last_mut() {
+            Some(subgoal) => match subgoal.process(ctx) {
+                // keep going if there's more to do
+                GoalStatus::Completed if subgoals > 1 => GoalStatus::Active,
+                status => status,
+            },
+            None => GoalStatus::Completed,
+        }
+    }
+
+    fn activate(&mut self, ctx: &mut GoalContext) {
+        self.status = GoalStatus::Active;
+        self.elapsed = 0.0;
+
+        match self.goal_type.clone() {
+            GoalType::SeekToPosition(position) => {
+                self.time_limit =
+                    expected_time(ctx.position.distance(position), ctx.physical.max_speed);
+
+                ctx.steering.target = position;
+                ctx.agent.seek_on(ctx.commands, ctx.entity);
+            }
+            GoalType::FollowPath(waypoints) => {
+                let distance: f32 = std::iter::once(ctx.position)
+                    .chain(waypoints.iter().copied())
+                    .collect::<Vec<_>>()
+                    .windows(2)
+                    .map(|points| points[0].distance(points[1]))
+                    .sum();
+                self.time_limit = expected_time(distance, ctx.physical.max_speed);
+
+                ctx.agent.follow_path_on(
+                    ctx.commands,
+                    ctx.entity,
+                    FollowPath::new(waypoints, false),
+                );
+            }
+            GoalType::MoveToPosition(destination) => {
+                self.remove_all_subgoals(ctx);
+
+                ctx.planner.request_path_to_position(
+                    ctx.manager,
+                    ctx.entity,
+                    ctx.position,
+                    destination,
+                    ctx.name,
+                );
+
+                // head straight there while the path is planned
+                self.add_subgoal(Goal::new(GoalType::SeekToPosition(destination)));
+            }
+            GoalType::Explore => {
+                self.remove_all_subgoals(ctx);
+
+                let destination = ctx.navgraph.and_then(|navgraph| {
+                    if navgraph.is_empty() {
+                        return None;
+                    }
+
+                    let idx = ctx.random.gen_range(0..navgraph.node_count());
+                    Some(navgraph.node(idx).position)
+                });
+
+                match destination {
+                    Some(destination) => {
+                        info!("[{}]: exploring {} ...", ctx.name, destination);
+                        self.add_subgoal(Goal::new(GoalType::MoveToPosition(destination)));
+                    }
+                    None => self.status = GoalStatus::Failed,
+                }
+            }
+            GoalType::GetHealth(position) | GoalType::GetWeapon(_, position) => {
+                self.remove_all_subgoals(ctx);
+
+                self.add_subgoal(Goal::new(GoalType::MoveToPosition(position)));
+            }
+            GoalType::AttackTarget => {
+                self.remove_all_subgoals(ctx);
+
+                if ctx.target.is_none() {
+                    self.status = GoalStatus::Completed;
+                }
+            }
+        }
+    }
+
+    pub fn process(&mut self, ctx: &mut GoalContext) -> GoalStatus {
+        if self.is_inactive() {
+            self.activate(ctx);
+        }
+
+        if !self.is_active() {
+            return self.status;
+        }
+
+        self.elapsed += ctx.dt;
+
+        self.status = match self.goal_type.clone() {
+            GoalType::SeekToPosition(position) => {
+                if ctx.position.distance_squared(position)
+                    < GOAL_ARRIVE_DISTANCE * GOAL_ARRIVE_DISTANCE
+                {
+                    ctx.physical.stop();
+                    GoalStatus::Completed
+                } else if self.elapsed > self.time_limit {
+                    GoalStatus::Failed
+                } else {
+                    GoalStatus::Active
+                }
+            }
+            GoalType::FollowPath(waypoints) => match waypoints.last() {
+                Some(destination)
+                    if ctx.position.distance_squared(*destination)
+                        >= GOAL_ARRIVE_DISTANCE * GOAL_ARRIVE_DISTANCE =>
+                {
+                    if self.elapsed > self.time_limit {
+                        GoalStatus::Failed
+                    } else {
+                        GoalStatus::Active
+                    }
+                }
+                _ => {
+                    ctx.physical.stop();
+                    GoalStatus::Completed
+                }
+            },
+            GoalType::MoveToPosition(_) => match self.process_subgoals(ctx) {
+                // replan on the next update
+                GoalStatus::Failed => GoalStatus::Inactive,
+                status => status,
+            },
+            GoalType::Explore | GoalType::GetHealth(_) | GoalType::GetWeapon(_, _) => {
+                self.process_subgoals(ctx)
+            }
+            GoalType::AttackTarget => self.process_attack_target(ctx),
+        };
+
+        self.status
+    }
+
+    fn process_attack_target(&mut self, ctx: &mut GoalContext) -> GoalStatus {
+        let target = match ctx.target {
+            Some(target) => target,
+            None => {
+                self.remove_all_subgoals(ctx);
+                return GoalStatus::Completed;
+            }
+        };
+
+        // hunting moves to where the target was last sensed and explores once there
+        let hunting = match self.subgoals.last().map(Goal::get_type) {
+            Some(GoalType::MoveToPosition(destination)) => *destination == target.position,
+            Some(GoalType::Explore) => true,
+            _ => false,
+        };
+
+        if target.visible {
+            if hunting {
+                self.remove_all_subgoals(ctx);
+            }
+
+            if ctx.position.distance(target.position) > ATTACK_RANGE {
+                if self.subgoals.is_empty() {
+                    self.add_subgoal(Goal::new(GoalType::SeekToPosition(target.position)));
+                }
+            } else if !self.subgoals.is_empty() {
+                // close enough, stand and fight
+                self.remove_all_subgoals(ctx);
+                ctx.physical.stop();
+            }
+        } else if !hunting {
+            self.remove_all_subgoals(ctx);
+
+            if ctx.position.distance_squared(target.position)
+                < GOAL_ARRIVE_DISTANCE * GOAL_ARRIVE_DISTANCE
+            {
+                self.add_subgoal(Goal::new(GoalType::Explore));
+            } else {
+                self.add_subgoal(Goal::new(GoalType::MoveToPosition(target.position)));
+            }
+        }
+
+        match self.process_subgoals(ctx) {
+            // keep attacking for as long as there's a target
+            GoalStatus::Completed => GoalStatus::Active,
+            status => status,
+        }
+    }
+
+    pub fn terminate(&mut self, ctx: &mut GoalContext) {
+        self.remove_all_subgoals(ctx);
+
+        match &self.goal_type {
+            GoalType::SeekToPosition(_) => ctx.agent.seek_off(ctx.commands, ctx.entity),
+            GoalType::FollowPath(_) => ctx.agent.follow_path_off(ctx.commands, ctx.entity),
+            GoalType::MoveToPosition(_) if ctx.planner.destination.is_some() => {
+                ctx.planner.cancel(ctx.manager, ctx.entity);
+            }
+            _ => (),
+        }
+    }
+
+    // path planner results go to the front most goal that wants them
+    pub fn path_ready(&mut self, ctx: &mut GoalContext, waypoints: &[Vec2]) -> bool {
+        if let Some(subgoal) = self.subgoals.last_mut() {
+            if subgoal.path_ready(ctx, waypoints) {
+                return true;
+            }
+        }
+
+        match self.goal_type {
+            GoalType::MoveToPosition(destination) if self.is_active() => {
+                // ignore paths from earlier requests
+                if waypoints.last() != Some(&destination) {
+                    return false;
+                }
+
+                self.remove_all_subgoals(ctx);
+                self.add_subgoal(Goal::new(GoalType::FollowPath(waypoints.to_vec())));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn no_path_available(&mut self) -> bool {
+        if let Some(subgoal) = self.subgoals.last_mut() {
+            if subgoal.no_path_available() {
+                return true;
+            }
+        }
+
+        match self.goal_type {
+            GoalType::MoveToPosition(_) if self.is_active() => {
+                self.status = GoalStatus::Failed;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// what a bot knows when it's deciding what to do
+#[derive(Debug, Default)]
+pub struct GoalFeatures {
+    pub position: Vec2,
+
+    // current / max health
+    pub health: f32,
+
+    // available pickups
+    pub health_items: Vec<Vec2>,
+    pub weapon_items: Vec<(Weapon, Vec2)>,
+
+    // ammo / max ammo for each held weapon
+    pub weapon_strengths: HashMap<Weapon, f32>,
+
+    pub target: Option<Target>,
+}
+
+impl GoalFeatures {
+    // the closest of the positions and its distance, scaled from min to 1
+    fn closest_item(&self, positions: impl Iterator<Item = Vec2>) -> Option<(Vec2, f32)> {
+        positions
+            .map(|position| (position, self.position.distance(position)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(position, distance)| {
+                (
+                    position,
+                    (distance / GOAL_MAX_ITEM_DISTANCE).clamp(GOAL_MIN_ITEM_DISTANCE, 1.0),
+                )
+            })
+    }
+
+    fn closest_health(&self) -> Option<(Vec2, f32)> {
+        self.closest_item(self.health_items.iter().copied())
+    }
+
+    fn closest_weapon(&self, weapon: Weapon) -> Option<(Vec2, f32)> {
+        self.closest_item(
+            self.weapon_items
+                .iter()
+                .filter(|(item, _)| *item == weapon)
+                .map(|(_, position)| *position),
+        )
+    }
+
+    fn weapon_strength(&self, weapon: Weapon) -> f32 {
+        self.weapon_strengths.get(&weapon).copied().unwrap_or(0.0)
+    }
+
+    // the blaster always counts for a little
+    // so that bots without any ammo still fight
+    fn total_weapon_strength(&self) -> f32 {
+        let weapons = Weapon::iter()
+            .filter(|weapon| *weapon != Weapon::Blaster)
+            .collect::<Vec<_>>();
+
+        let total = weapons
+            .iter()
+            .map(|weapon| self.weapon_strength(*weapon))
+            .sum::<f32>()
+            / weapons.len() as f32;
+
+        total.max(0.1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalEvaluatorType {
+    Explore,
+    GetHealth,
+    GetWeapon(Weapon),
+    AttackTarget,
+}
+
+// scores a high-level goal, the bias gives each bot its own personality
+#[derive(Debug, Clone, Copy)]
+pub struct GoalEvaluator {
+    pub evaluator_type: GoalEvaluatorType,
+    pub bias: f32,
+}
+
+impl GoalEvaluator {
+    // 0 - 1, scaled by the bias
+    pub fn calculate_desirability(&self, features: &GoalFeatures) -> f32 {
+        let desirability = match self.evaluator_type {
+            GoalEvaluatorType::Explore => EXPLORE_BIAS,
+            GoalEvaluatorType::GetHealth => match features.closest_health() {
+                Some((_, distance)) if features.health < 1.0 => {
+                    GET_HEALTH_TWEAKER * (1.0 - features.health) / distance
+                }
+                _ => 0.0,
+            },
+            GoalEvaluatorType::GetWeapon(weapon) => match features.closest_weapon(weapon) {
+                Some((_, distance)) => {
+                    GET_WEAPON_TWEAKER * features.health * (1.0 - features.weapon_strength(weapon))
+                        / distance
+                }
+                None => 0.0,
+            },
+            GoalEvaluatorType::AttackTarget => match features.target {
+                Some(_) => features.health * features.total_weapon_strength(),
+                None => 0.0,
+            },
+        };
+
+        desirability.clamp(0.0, 1.0) * self.bias
+    }
+
+    pub fn create_goal(&self, features: &GoalFeatures) -> Option<GoalType> {
+        match self.evaluator_type {
+            GoalEvaluatorType::Explore => Some(GoalType::Explore),
+            GoalEvaluatorType::GetHealth => features
+                .closest_health()
+                .map(|(position, _)| GoalType::GetHealth(position)),
+            GoalEvaluatorType::GetWeapon(weapon) => features
+                .closest_weapon(weapon)
+                .map(|(position, _)| GoalType::GetWeapon(weapon, position)),
+            GoalEvaluatorType::AttackTarget => features.target.map(|_| GoalType::AttackTarget),
+        }
+    }
+}
+
+// the top level goal, picks the most desirable goal to work on
+#[derive(Debug)]
+pub struct Think {
+    evaluators: Vec<GoalEvaluator>,
+    subgoals: Vec<Goal>,
+}
+
+impl Think {
+    pub fn new(random: &mut Random) -> Self {
+        let mut evaluator_types = vec![
+            GoalEvaluatorType::Explore,
+            GoalEvaluatorType::GetHealth,
+            GoalEvaluatorType::AttackTarget,
+        ];
+        evaluator_types.extend(
+            Weapon::iter()
+                .filter(|weapon| *weapon != Weapon::Blaster)
+                .map(GoalEvaluatorType::GetWeapon),
+        );
+
+        let evaluators = evaluator_types
+            .into_iter()
+            .map(|evaluator_type| GoalEvaluator {
+                evaluator_type,
+                bias: random.gen_range(0.5..1.5),
+            })
+            .collect();
+
+        Self {
+            evaluators,
+            subgoals: vec![],
+        }
+    }
+
+    pub fn has_goal(&self) -> bool {
+        !self.subgoals.is_empty()
+    }
+
+    // the most desirable goal, if any
+    pub fn best_goal(&self, features: &GoalFeatures) -> Option<GoalType> {
+        self.evaluators
+            .iter()
+            .map(|evaluator| (evaluator, evaluator.calculate_desirability(features)))
+            .filter(|(_, desirability)| *desirability > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .and_then(|(evaluator, _)| evaluator.create_goal(features))
+    }
+
+    pub fn arbitrate(&mut self, ctx: &mut GoalContext, features: &GoalFeatures) {
+        let goal_type = match self.best_goal(features) {
+            Some(goal_type) => goal_type,
+            None => return,
+        };
+
+        if self.subgoals.last().map(Goal::get_type) == Some(&goal_type) {
+            return;
+        }
+
+        info!(
+            "[{}]: thinking about {} ...",
+            ctx.name,
+            goal_type.get_name()
+        );
+
+        self.remove_all_subgoals(ctx);
+        self.subgoals.push(Goal::new(goal_type));
+    }
+
+    pub fn process(&mut self, ctx: &mut GoalContext) {
+        if let Some(goal) = self.subgoals.last_mut() {
+            goal.process(ctx);
+
+            // finished goals get replaced on the next arbitration
+            if goal.is_complete() || goal.has_failed() {
+                goal.terminate(ctx);
+                self.subgoals.pop();
+            }
+        }
+    }
+
+    pub fn remove_all_subgoals(&mut self, ctx: &mut GoalContext) {
+        while let Some(mut goal) = self.subgoals.pop() {
+            goal.terminate(ctx);
+        }
+    }
+
+    pub fn path_ready(&mut self, ctx: &mut GoalContext, waypoints: &[Vec2]) {
+        if let Some(goal) = self.subgoals.last_mut() {
+            goal.path_ready(ctx, waypoints);
+        }
+    }
+
+    pub fn no_path_available(&mut self) {
+        if let Some(goal) = self.subgoals.last_mut() {
+            goal.no_path_available();
+        }
+    }
+
+    // the goal stack for debugging
+    pub fn describe(&self) -> Vec<(usize, String, GoalStatus)> {
+        let mut out = vec![];
+        for goal in self.subgoals.iter().rev() {
+            goal.describe(0, &mut out);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::CommandQueue;
+
+    use super::*;
+
+    // everything a GoalContext borrows
+    struct TestBot {
+        world: World,
+        queue: CommandQueue,
+        manager: PathManager,
+        random: Random,
+
+        entity: Entity,
+        agent: Agent,
+        steering: Steering,
+        physical: Physical,
+        planner: PathPlanner,
+        position: Vec2,
+        target: Option<Target>,
+    }
+
+    impl TestBot {
+        fn new() -> Self {
+            let mut world = World::new();
+            let entity = world.spawn().id();
+
+            Self {
+                world,
+                queue: CommandQueue::default(),
+                manager: PathManager::default(),
+                random: Random::new(0),
+                entity,
+                agent: Agent,
+                steering: Steering::default(),
+                physical: Physical {
+                    max_speed: 10.0,
+                    ..Default::default()
+                },
+                planner: PathPlanner::default(),
+                position: Vec2::ZERO,
+                target: None,
+            }
+        }
+
+        // runs f with a context for this bot and applies any commands
+        fn run<T>(&mut self, dt: f32, f: impl FnOnce(&mut GoalContext) -> T) -> T {
+            let mut commands = Commands::new(&mut self.queue, &self.world);
+            let mut ctx = GoalContext {
+                commands: &mut commands,
+                manager: &mut self.manager,
+                navgraph: None,
+                random: &mut self.random,
+                entity: self.entity,
+                agent: &self.agent,
+                steering: &mut self.steering,
+                physical: &mut self.physical,
+                planner: &mut self.planner,
+                position: self.position,
+                target: self.target,
+                name: "test",
+                dt,
+            };
+
+            let result = f(&mut ctx);
+            self.queue.apply(&mut self.world);
+            result
+        }
+
+        fn has<C: Component>(&self) -> bool {
+            self.world.entity(self.entity).contains::<C>()
+        }
+    }
+
+    #[test]
+    fn seek_activate_process_terminate() {
+        let mut bot = TestBot::new();
+        let destination = Vec2::new(10.0, 0.0);
+        let mut goal = Goal::new(GoalType::SeekToPosition(destination));
+        assert!(goal.is_inactive());
+
+        // the first process activates the goal
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+        assert_eq!(bot.steering.target, destination);
+        assert!(bot.has::<Seek>());
+
+        bot.position = destination;
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Completed);
+
+        bot.run(0.0, |ctx| goal.terminate(ctx));
+        assert!(!bot.has::<Seek>());
+    }
+
+    #[test]
+    fn seek_fails_when_it_takes_too_long() {
+        let mut bot = TestBot::new();
+        let mut goal = Goal::new(GoalType::SeekToPosition(Vec2::new(10.0, 0.0)));
+
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+        assert_eq!(bot.run(100.0, |ctx| goal.process(ctx)), GoalStatus::Failed);
+
+        // failed goals stay failed
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Failed);
+    }
+
+    #[test]
+    fn move_to_position_plans_and_follows_path() {
+        let mut bot = TestBot::new();
+        let destination = Vec2::new(10.0, 0.0);
+        let mut goal = Goal::new(GoalType::MoveToPosition(destination));
+
+        // seeks straight there while the path is planned
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+        assert_eq!(bot.planner.destination, Some(destination));
+        assert_eq!(
+            goal.subgoals.last().map(Goal::get_type),
+            Some(&GoalType::SeekToPosition(destination))
+        );
+
+        // paths to somewhere else are ignored
+        let stale = [Vec2::new(0.0, 10.0)];
+        assert!(!bot.run(0.0, |ctx| goal.path_ready(ctx, &stale)));
+
+        let waypoints = [Vec2::new(5.0, 0.0), destination];
+        assert!(bot.run(0.0, |ctx| goal.path_ready(ctx, &waypoints)));
+        assert!(!bot.has::<Seek>());
+        assert_eq!(
+            goal.subgoals.last().map(Goal::get_type),
+            Some(&GoalType::FollowPath(waypoints.to_vec()))
+        );
+
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+        assert!(bot.has::<FollowPath>());
+
+        bot.position = destination;
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Completed);
+
+        bot.run(0.0, |ctx| goal.terminate(ctx));
+        assert!(!bot.has::<FollowPath>());
+    }
+
+    #[test]
+    fn composite_completes_with_its_subgoals() {
+        let mut bot = TestBot::new();
+        let position = Vec2::new(10.0, 0.0);
+        let mut goal = Goal::new(GoalType::GetHealth(position));
+
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+        assert_eq!(
+            goal.subgoals.last().map(Goal::get_type),
+            Some(&GoalType::MoveToPosition(position))
+        );
+
+        bot.position = position;
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Completed);
+
+        // terminating cleans up the subgoals
+        bot.run(0.0, |ctx| goal.terminate(ctx));
+        assert!(goal.subgoals.is_empty());
+        assert!(!bot.has::<Seek>());
+    }
+
+    #[test]
+    fn subgoal_failure_fails_the_goal() {
+        let mut bot = TestBot::new();
+        let mut goal = Goal::new(GoalType::GetWeapon(Weapon::Shotgun, Vec2::new(10.0, 0.0)));
+
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+
+        // the planner couldn't find a path for the move
+        assert!(goal.no_path_available());
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Failed);
+        assert!(goal.subgoals.is_empty());
+        assert!(!bot.has::<Seek>());
+    }
+
+    #[test]
+    fn explore_fails_without_a_navgraph() {
+        let mut bot = TestBot::new();
+        let mut goal = Goal::new(GoalType::Explore);
+
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Failed);
+    }
+
+    #[test]
+    fn attack_target_completes_without_a_target() {
+        let mut bot = TestBot::new();
+        bot.target = Some(Target {
+            entity: Entity::from_raw(1),
+            position: Vec2::new(100.0, 0.0),
+            visible: true,
+        });
+
+        // out of range, close in on the target
+        let mut goal = Goal::new(GoalType::AttackTarget);
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Active);
+        assert_eq!(
+            goal.subgoals.last().map(Goal::get_type),
+            Some(&GoalType::SeekToPosition(Vec2::new(100.0, 0.0)))
+        );
+
+        bot.target = None;
+        assert_eq!(bot.run(0.1, |ctx| goal.process(ctx)), GoalStatus::Completed);
+        assert!(goal.subgoals.is_empty());
+    }
+
+    fn evaluator(evaluator_type: GoalEvaluatorType) -> GoalEvaluator {
+        GoalEvaluator {
+            evaluator_type,
+            bias: 1.0,
+        }
+    }
+
+    #[test]
+    fn get_health_desirability() {
+        let mut features = GoalFeatures {
+            health: 1.0,
+            health_items: vec![Vec2::new(50.0, 0.0)],
+            ..Default::default()
+        };
+
+        let get_health = evaluator(GoalEvaluatorType::GetHealth);
+        assert_eq!(get_health.calculate_desirability(&features), 0.0);
+
+        features.health = 0.5;
+        let hurt = get_health.calculate_desirability(&features);
+        assert!(hurt > 0.0);
+
+        features.health = 0.1;
+        assert!(get_health.calculate_desirability(&features) > hurt);
+
+        features.health_items.clear();
+        assert_eq!(get_health.calculate_desirability(&features), 0.0);
+    }
+
+    #[test]
+    fn get_weapon_prefers_closer_items() {
+        let near = GoalFeatures {
+            health: 1.0,
+            weapon_items: vec![(Weapon::Shotgun, Vec2::new(20.0, 0.0))],
+            ..Default::default()
+        };
+        let far = GoalFeatures {
+            health: 1.0,
+            weapon_items: vec![(Weapon::Shotgun, Vec2::new(80.0, 0.0))],
+            ..Default::default()
+        };
+
+        let get_shotgun = evaluator(GoalEvaluatorType::GetWeapon(Weapon::Shotgun));
+        assert!(
+            get_shotgun.calculate_desirability(&near) > get_shotgun.calculate_desirability(&far)
+        );
+
+        let get_railgun = evaluator(GoalEvaluatorType::GetWeapon(Weapon::Railgun));
+        assert_eq!(get_railgun.calculate_desirability(&near), 0.0);
+    }
+
+    #[test]
+    fn attack_target_desirability() {
+        let mut features = GoalFeatures {
+            health: 1.0,
+            ..Default::default()
+        };
+
+        let attack = evaluator(GoalEvaluatorType::AttackTarget);
+        assert_eq!(attack.calculate_desirability(&features), 0.0);
+
+        features.target = Some(Target {
+            entity: Entity::from_raw(0),
+            position: Vec2::new(10.0, 0.0),
+            visible: true,
+        });
+        let unarmed = attack.calculate_desirability(&features);
+        assert!(unarmed > evaluator(GoalEvaluatorType::Explore).calculate_desirability(&features));
+
+        features.weapon_strengths.insert(Weapon::Railgun, 1.0);
+        assert!(attack.calculate_desirability(&features) > unarmed);
+    }
+
+    #[test]
+    fn think_best_goal() {
+        let mut random = Random::new(0);
+        let mut think = Think::new(&mut random);
+        for evaluator in think.evaluators.iter_mut() {
+            evaluator.bias = 1.0;
+        }
+
+        let mut features = GoalFeatures {
+            health: 0.2,
+            health_items: vec![Vec2::new(5.0, 0.0)],
+            ..Default::default()
+        };
+        assert_eq!(
+            think.best_goal(&features),
+            Some(GoalType::GetHealth(Vec2::new(5.0, 0.0)))
+        );
+
+        features.health = 1.0;
+        assert_eq!(think.best_goal(&features), Some(GoalType::Explore));
+    }
+}
@@ -1,14 +1,18 @@
-pub mod cooldown;
+pub mod fuzzy;
+pub mod goals;
+pub mod search;
 pub mod weapons;
 
-// 50hz, the same as Unity
-pub const PHYSICS_STEP: f32 = 0.02;
+pub use game_ai_core::physics::PHYSICS_STEP;
 
 // sprite sorting
 pub const BOT_SORT: f32 = 2.0;
 pub const CORPSE_SORT: f32 = 2.0;
 pub const PROJECTILE_SORT: f32 = 2.0;
+pub const EFFECT_SORT: f32 = 2.5;
 pub const WALL_SORT: f32 = 3.0;
+pub const NAVGRAPH_DEBUG_SORT: f32 = 1.0;
+pub const SEARCH_DEBUG_SORT: f32 = 1.5;
 pub const DEBUG_SORT: f32 = 100.0;
 
 pub const BOT_RADIUS: f32 = 1.0;
@@ -18,3 +22,47 @@ pub const PELLET_RADIUS: f32 = 0.1;
 pub const ROCKET_RADIUS: f32 = 0.35;
 pub const SLUG_RADIUS: f32 = 0.2;
 pub const POWERUP_TRIGGER_RADIUS: f32 = 1.0;
+pub const DOOR_SWITCH_TRIGGER_RADIUS: f32 = 1.0;
+
+pub const BOLT_LENGTH: f32 = 1.0;
+pub const ROCKET_LENGTH: f32 = 1.0;
+
+pub const NAVGRAPH_NODE_SPACING: f32 = 5.0;
+
+// total graph search cycles shared by all of the pending path requests each frame
+pub const PATH_SEARCH_CYCLES_PER_UPDATE: usize = 200;
+pub const PATH_WAYPOINT_SEEK_DISTANCE: f32 = 2.0;
+
+// goals
+pub const THINK_ARBITRATE_SECONDS: f32 = 0.5;
+pub const GOAL_ARRIVE_DISTANCE: f32 = 2.0;
+pub const GOAL_TIME_MARGIN_SECONDS: f32 = 1.0;
+pub const ATTACK_RANGE: f32 = 20.0;
+
+// goal desirability
+// item distances are scaled by the max distance and clamped to the min
+pub const GOAL_MAX_ITEM_DISTANCE: f32 = 100.0;
+pub const GOAL_MIN_ITEM_DISTANCE: f32 = 0.1;
+pub const EXPLORE_BIAS: f32 = 0.05;
+pub const GET_HEALTH_TWEAKER: f32 = 0.2;
+pub const GET_WEAPON_TWEAKER: f32 = 0.15;
+
+// respawning
+pub const BOT_RESPAWN_SECONDS: f32 = 3.0;
+pub const BOT_SPAWN_INVULNERABLE_SECONDS: f32 = 2.0;
+
+// sensory memory
+// TODO: pull bot parameters from a config
+pub const MEMORY_SPAN_SECONDS: f32 = 5.0;
+pub const BOT_FOV_DEGREES: f32 = 180.0;
+
+// steering weights
+pub const FOLLOW_PATH_WEIGHT: f32 = 1.0;
+
+// TODO: pull trigger parameters from a config
+pub const WEAPON_TRIGGER_RESPAWN_SECONDS: f32 = 30.0;
+pub const HEALTH_TRIGGER_RESPAWN_SECONDS: f32 = 60.0;
+pub const DOOR_OPEN_SECONDS: f32 = 5.0;
+
+// how often bots re-evaluate their weapon
+pub const WEAPON_SELECTION_SECONDS: f32 = 0.5;
@@ -0,0 +1,467 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+use bevy::prelude::*;
+
+use crate::resources::navgraph::*;
+
+#[derive(Debug, strum_macros::Display, Copy, Clone, Eq, PartialEq)]
+pub enum Heuristic {
+    None,
+    Euclidean,
+    Manhattan,
+}
+
+impl Heuristic {
+    // estimated cost between two positions
+    // manhattan overestimates diagonal moves so it isn't guaranteed to be optimal
+    pub fn estimate(&self, from: Vec2, to: Vec2) -> f32 {
+        match self {
+            Self::None => 0.0,
+            Self::Euclidean => from.distance(to),
+            Self::Manhattan => (from.x - to.x).abs() + (from.y - to.y).abs(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SearchAlgorithm {
+    DepthFirst,
+    BreadthFirst,
+    Dijkstra,
+    AStar(Heuristic),
+}
+
+impl Default for SearchAlgorithm {
+    fn default() -> Self {
+        Self::AStar(Heuristic::Euclidean)
+    }
+}
+
+impl SearchAlgorithm {
+    pub fn get_name(&self) -> String {
+        match self {
+            Self::DepthFirst => "DFS".to_owned(),
+            Self::BreadthFirst => "BFS".to_owned(),
+            Self::Dijkstra => "Dijkstra".to_owned(),
+            Self::AStar(heuristic) => format!("A* ({})", heuristic),
+        }
+    }
+}
+
+pub const SEARCH_ALGORITHMS: [SearchAlgorithm; 6] = [
+    SearchAlgorithm::DepthFirst,
+    SearchAlgorithm::BreadthFirst,
+    SearchAlgorithm::Dijkstra,
+    SearchAlgorithm::AStar(Heuristic::None),
+    SearchAlgorithm::AStar(Heuristic::Euclidean),
+    SearchAlgorithm::AStar(Heuristic::Manhattan),
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SearchStatus {
+    InProgress,
+    Found,
+    NotFound,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub algorithm: SearchAlgorithm,
+    pub status: SearchStatus,
+
+    // empty if no path was found
+    pub path: Vec<NodeIndex>,
+    pub cost: f32,
+
+    // nodes in the order they were expanded
+    pub visited: Vec<NodeIndex>,
+
+    // nodes waiting to be expanded
+    pub frontier: Vec<NodeIndex>,
+
+    // (parent, node) edges of the search tree
+    pub tree: Vec<(NodeIndex, NodeIndex)>,
+}
+
+impl SearchResult {
+    #[allow(dead_code)]
+    pub fn is_found(&self) -> bool {
+        self.status == SearchStatus::Found
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct OpenNode {
+    node: NodeIndex,
+    parent: Option<NodeIndex>,
+    cost: f32,
+    priority: f32,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    // reversed so the heap pops the lowest priority first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority)
+    }
+}
+
+#[derive(Debug)]
+enum OpenList {
+    Stack(Vec<OpenNode>),
+    Queue(VecDeque<OpenNode>),
+    Heap(BinaryHeap<OpenNode>),
+}
+
+impl OpenList {
+    fn push(&mut self, node: OpenNode) {
+        match self {
+            Self::Stack(stack) => stack.push(node),
+            Self::Queue(queue) => queue.push_back(node),
+            Self::Heap(heap) => heap.push(node),
+        }
+    }
+
+    fn pop(&mut self) -> Option<OpenNode> {
+        match self {
+            Self::Stack(stack) => stack.pop(),
+            Self::Queue(queue) => queue.pop_front(),
+            Self::Heap(heap) => heap.pop(),
+        }
+    }
+
+    fn nodes(&self) -> Vec<NodeIndex> {
+        match self {
+            Self::Stack(stack) => stack.iter().map(|open| open.node).collect(),
+            Self::Queue(queue) => queue.iter().map(|open| open.node).collect(),
+            Self::Heap(heap) => heap.iter().map(|open| open.node).collect(),
+        }
+    }
+}
+
+// a search that can be run to completion or a node at a time
+#[derive(Debug)]
+pub struct GraphSearch {
+    algorithm: SearchAlgorithm,
+    target: NodeIndex,
+    status: SearchStatus,
+
+    open: OpenList,
+    parents: Vec<Option<NodeIndex>>,
+    costs: Vec<f32>,
+    expanded: Vec<bool>,
+    visited: Vec<NodeIndex>,
+}
+
+impl GraphSearch {
+    pub fn new(
+        graph: &NavGraph,
+        source: NodeIndex,
+        target: NodeIndex,
+        algorithm: SearchAlgorithm,
+    ) -> Self {
+        let mut open = match algorithm {
+            SearchAlgorithm::DepthFirst => OpenList::Stack(vec![]),
+            SearchAlgorithm::BreadthFirst => OpenList::Queue(VecDeque::new()),
+            SearchAlgorithm::Dijkstra | SearchAlgorithm::AStar(_) => {
+                OpenList::Heap(BinaryHeap::new())
+            }
+        };
+
+        let node_count = graph.node_count();
+        let mut costs = vec![f32::INFINITY; node_count];
+
+        let mut status = SearchStatus::NotFound;
+        if source < node_count && target < node_count {
+            status = SearchStatus::InProgress;
+            costs[source] = 0.0;
+            open.push(OpenNode {
+                node: source,
+                parent: None,
+                cost: 0.0,
+                priority: 0.0,
+            });
+        }
+
+        Self {
+            algorithm,
+            target,
+            status,
+            open,
+            parents: vec![None; node_count],
+            costs,
+            expanded: vec![false; node_count],
+            visited: vec![],
+        }
+    }
+
+    fn is_cost_based(&self) -> bool {
+        matches!(
+            self.algorithm,
+            SearchAlgorithm::Dijkstra | SearchAlgorithm::AStar(_)
+        )
+    }
+
+    pub fn status(&self) -> SearchStatus {
+        self.status
+    }
+
+    // expands a single node
+    pub fn step(&mut self, graph: &NavGraph) -> SearchStatus {
+        if self.status != SearchStatus::InProgress {
+            return self.status;
+        }
+
+        let current = loop {
+            match self.open.pop() {
+                Some(open) if self.expanded[open.node] => continue,
+                Some(open) => break open,
+                None => {
+                    self.status = SearchStatus::NotFound;
+                    return self.status;
+                }
+            }
+        };
+
+        self.expanded[current.node] = true;
+        self.parents[current.node] = current.parent;
+        self.costs[current.node] = current.cost;
+        self.visited.push(current.node);
+
+        if current.node == self.target {
+            self.status = SearchStatus::Found;
+            return self.status;
+        }
+
+        let target_position = graph.node(self.target).position;
+        for edge in graph.edges(current.node) {
+            if self.expanded[edge.to] {
+                continue;
+            }
+
+            let cost = current.cost + edge.cost;
+            let priority = match self.algorithm {
+                SearchAlgorithm::DepthFirst | SearchAlgorithm::BreadthFirst => 0.0,
+                SearchAlgorithm::Dijkstra => cost,
+                SearchAlgorithm::AStar(heuristic) => {
+                    cost + heuristic.estimate(graph.node(edge.to).position, target_position)
+                }
+            };
+
+            // only keep improvements for the cost based searches
+            if self.is_cost_based() {
+                if cost >= self.costs[edge.to] {
+                    continue;
+                }
+                self.costs[edge.to] = cost;
+            }
+
+            self.open.push(OpenNode {
+                node: edge.to,
+                parent: Some(current.node),
+                cost,
+                priority,
+            });
+        }
+
+        self.status
+    }
+
+    // runs the search until it completes
+    pub fn run(&mut self, graph: &NavGraph) -> SearchStatus {
+        while self.step(graph) == SearchStatus::InProgress {}
+
+        self.status
+    }
+
+    // the nodes from the source to the target, empty if no path was found
+    pub fn path(&self) -> Vec<NodeIndex> {
+        let mut path = vec![];
+        if self.status == SearchStatus::Found {
+            let mut node = Some(self.target);
+            while let Some(current) = node {
+                path.push(current);
+                node = self.parents[current];
+            }
+            path.reverse();
+        }
+
+        path
+    }
+
+    pub fn result(&self) -> SearchResult {
+        let mut cost = 0.0;
+        if self.status == SearchStatus::Found {
+            cost = self.costs[self.target];
+        }
+
+        SearchResult {
+            algorithm: self.algorithm,
+            status: self.status,
+            path: self.path(),
+            cost,
+            visited: self.visited.clone(),
+            frontier: self
+                .open
+                .nodes()
+                .into_iter()
+                .filter(|node| !self.expanded[*node])
+                .collect(),
+            tree: self
+                .visited
+                .iter()
+                .filter_map(|node| self.parents[*node].map(|parent| (parent, *node)))
+                .collect(),
+        }
+    }
+}
+
+// searches the graph for a path from the source node to the target node
+pub fn search(
+    graph: &NavGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    algorithm: SearchAlgorithm,
+) -> SearchResult {
+    let mut search = GraphSearch::new(graph, source, target, algorithm);
+    search.run(graph);
+    search.result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> NavGraph {
+        // 0 - 1 - 2
+        // |       |
+        // |   4 - 5 - 6
+        // | /
+        // 3
+        //
+        // 7
+        //
+        // the top (0 - 1 - 2 - 5 - 6) is cheaper than the bottom (0 - 3 - 4 - 5 - 6),
+        // 7 is unreachable and the detour (8) is the fewest edges but the most cost
+        let mut graph = NavGraph::from_nodes(
+            &[
+                Vec2::new(0.0, 10.0),
+                Vec2::new(10.0, 10.0),
+                Vec2::new(20.0, 10.0),
+                Vec2::new(0.0, -5.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(20.0, 0.0),
+                Vec2::new(30.0, 0.0),
+                Vec2::new(0.0, -10.0),
+            ],
+            &[(0, 1), (1, 2), (0, 3), (3, 4), (4, 5), (2, 5), (5, 6)],
+        );
+        let detour = graph.add_node(Vec2::new(15.0, 50.0));
+        graph.add_edge(0, detour, EdgeFlags::WALKABLE);
+        graph.add_edge(detour, 6, EdgeFlags::WALKABLE);
+
+        graph
+    }
+
+    fn path_cost(graph: &NavGraph, path: &[NodeIndex]) -> f32 {
+        path.windows(2)
+            .map(|nodes| graph.edge(nodes[0], nodes[1]).unwrap().cost)
+            .sum()
+    }
+
+    #[test]
+    fn every_algorithm_finds_a_path() {
+        let graph = graph();
+
+        for algorithm in SEARCH_ALGORITHMS {
+            let result = search(&graph, 0, 6, algorithm);
+            assert!(result.is_found(), "{:?}", algorithm);
+            assert_eq!(result.path.first(), Some(&0));
+            assert_eq!(result.path.last(), Some(&6));
+            assert!((result.cost - path_cost(&graph, &result.path)).abs() < 0.001);
+            assert_eq!(result.visited.first(), Some(&0));
+            assert_eq!(result.visited.last(), Some(&6));
+            assert_eq!(result.tree.len(), result.visited.len() - 1);
+        }
+    }
+
+    #[test]
+    fn breadth_first_is_fewest_edges() {
+        let graph = graph();
+
+        let result = search(&graph, 0, 6, SearchAlgorithm::BreadthFirst);
+        assert_eq!(result.path, vec![0, 8, 6]);
+    }
+
+    #[test]
+    fn cost_based_searches_are_cheapest() {
+        let graph = graph();
+
+        for algorithm in [
+            SearchAlgorithm::Dijkstra,
+            SearchAlgorithm::AStar(Heuristic::None),
+            SearchAlgorithm::AStar(Heuristic::Euclidean),
+        ] {
+            let result = search(&graph, 0, 6, algorithm);
+            assert_eq!(result.path, vec![0, 1, 2, 5, 6], "{:?}", algorithm);
+            assert_eq!(result.cost, 40.0);
+        }
+    }
+
+    #[test]
+    fn astar_visits_fewer_nodes() {
+        let graph = graph();
+
+        let dijkstra = search(&graph, 0, 6, SearchAlgorithm::Dijkstra);
+        let astar = search(&graph, 0, 6, SearchAlgorithm::AStar(Heuristic::Euclidean));
+        assert!(astar.visited.len() < dijkstra.visited.len());
+    }
+
+    #[test]
+    fn unreachable_target() {
+        let graph = graph();
+
+        for algorithm in SEARCH_ALGORITHMS {
+            let result = search(&graph, 0, 7, algorithm);
+            assert_eq!(result.status, SearchStatus::NotFound);
+            assert!(result.path.is_empty());
+            assert!(result.frontier.is_empty());
+
+            // everything reachable was visited
+            assert_eq!(result.visited.len(), graph.node_count() - 1);
+        }
+
+        let result = search(&graph, 0, 100, SearchAlgorithm::Dijkstra);
+        assert_eq!(result.status, SearchStatus::NotFound);
+    }
+
+    #[test]
+    fn step_exposes_frontier() {
+        let graph = graph();
+
+        let mut search = GraphSearch::new(&graph, 0, 6, SearchAlgorithm::BreadthFirst);
+        assert_eq!(search.step(&graph), SearchStatus::InProgress);
+
+        let result = search.result();
+        assert_eq!(result.visited, vec![0]);
+        assert_eq!(result.frontier, vec![1, 3, 8]);
+        assert!(result.path.is_empty());
+
+        assert_eq!(search.run(&graph), SearchStatus::Found);
+        assert_eq!(search.status(), SearchStatus::Found);
+    }
+}
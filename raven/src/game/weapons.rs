@@ -1,6 +1,14 @@
 use bevy_inspector_egui::prelude::*;
+use serde::Deserialize;
 use strum_macros::EnumIter;
 
+use crate::game::fuzzy::*;
+
+// fuzzy variable names
+const DISTANCE_TO_TARGET: &str = "DistanceToTarget";
+const AMMO_STATUS: &str = "AmmoStatus";
+const DESIRABILITY: &str = "Desirability";
+
 // TODO: pull ammo parameters from a config
 
 // TODO: pull weapon parameters from a config
@@ -30,7 +38,18 @@ impl Ammo {
     }
 }
 
-#[derive(Debug, strum_macros::Display, Copy, Clone, Eq, PartialEq, Hash, Inspectable, EnumIter)]
+#[derive(
+    Debug,
+    strum_macros::Display,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Inspectable,
+    EnumIter,
+    Deserialize,
+)]
 pub enum Weapon {
     Blaster,
     Shotgun,
@@ -74,4 +93,145 @@ impl Weapon {
             Self::Railgun => 1.0,
         }
     }
+
+    // fuzzy rules mapping distance to target and ammo status to desirability
+    // (the blaster never runs out of ammo so it only cares about distance)
+    pub fn create_fuzzy_module(&self) -> FuzzyModule {
+        let mut module = FuzzyModule::default();
+
+        let distance = module.create_variable(DISTANCE_TO_TARGET);
+        let close = distance.add_left_shoulder_set(0.0, 2.5, 15.0);
+        let medium = distance.add_triangular_set(2.5, 15.0, 30.0);
+        let far = distance.add_right_shoulder_set(15.0, 30.0, 100.0);
+
+        let desirability = module.create_variable(DESIRABILITY);
+        let undesirable = desirability.add_left_shoulder_set(0.0, 25.0, 50.0);
+        let desirable = desirability.add_triangular_set(25.0, 50.0, 75.0);
+        let very_desirable = desirability.add_right_shoulder_set(50.0, 75.0, 100.0);
+
+        match self {
+            Self::Blaster => {
+                module.add_rule(close, desirable);
+                module.add_rule(fz_or(medium, far), fz_very(undesirable));
+            }
+            Self::Shotgun => {
+                let (low, okay, loads) = self.create_ammo_sets(&mut module, 3.0, 6.0);
+                add_ammo_rules(
+                    &mut module,
+                    [
+                        (close, loads, very_desirable.into()),
+                        (close, okay, very_desirable.into()),
+                        (close, low, very_desirable.into()),
+                        (medium, loads, very_desirable.into()),
+                        (medium, okay, desirable.into()),
+                        (medium, low, undesirable.into()),
+                        (far, loads, desirable.into()),
+                        (far, okay, undesirable.into()),
+                        (far, low, undesirable.into()),
+                    ],
+                );
+            }
+            Self::RocketLauncher => {
+                let (low, okay, loads) = self.create_ammo_sets(&mut module, 1.0, 3.0);
+                add_ammo_rules(
+                    &mut module,
+                    [
+                        (close, loads, undesirable.into()),
+                        (close, okay, undesirable.into()),
+                        (close, low, undesirable.into()),
+                        (medium, loads, very_desirable.into()),
+                        (medium, okay, very_desirable.into()),
+                        (medium, low, desirable.into()),
+                        (far, loads, desirable.into()),
+                        (far, okay, undesirable.into()),
+                        (far, low, undesirable.into()),
+                    ],
+                );
+            }
+            Self::Railgun => {
+                let (low, okay, loads) = self.create_ammo_sets(&mut module, 1.0, 3.0);
+                add_ammo_rules(
+                    &mut module,
+                    [
+                        (close, loads, fz_fairly(desirable)),
+                        (close, okay, fz_fairly(desirable)),
+                        (close, low, undesirable.into()),
+                        (medium, loads, very_desirable.into()),
+                        (medium, okay, desirable.into()),
+                        (medium, low, desirable.into()),
+                        (far, loads, fz_very(very_desirable)),
+                        (far, okay, fz_very(very_desirable)),
+                        (far, low, fz_very(very_desirable)),
+                    ],
+                );
+            }
+        }
+
+        module
+    }
+
+    // low, okay and loads of ammo
+    fn create_ammo_sets(
+        &self,
+        module: &mut FuzzyModule,
+        low_max: f32,
+        okay_max: f32,
+    ) -> (FzSet, FzSet, FzSet) {
+        let ammo = module.create_variable(AMMO_STATUS);
+        let low = ammo.add_triangular_set(0.0, 0.0, low_max);
+        let okay = ammo.add_triangular_set(0.0, low_max, okay_max);
+        let loads =
+            ammo.add_right_shoulder_set(low_max, okay_max, self.get_ammo().get_max_amount() as f32);
+
+        (low, okay, loads)
+    }
+
+    // 0 - 100, module should be one created for this weapon
+    pub fn get_desirability(&self, module: &mut FuzzyModule, distance: f32, ammo: usize) -> f32 {
+        module.fuzzify(DISTANCE_TO_TARGET, distance);
+        if *self != Self::Blaster {
+            module.fuzzify(AMMO_STATUS, ammo as f32);
+        }
+
+        module.defuzzify(DESIRABILITY)
+    }
+}
+
+// (distance, ammo, desirability) for every combination of distance and ammo
+fn add_ammo_rules(module: &mut FuzzyModule, rules: [(FzSet, FzSet, FuzzyTerm); 9]) {
+    for (distance, ammo, consequence) in rules {
+        module.add_rule(fz_and(distance, ammo), consequence);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desirability(weapon: Weapon, distance: f32, ammo: usize) -> f32 {
+        weapon.get_desirability(&mut weapon.create_fuzzy_module(), distance, ammo)
+    }
+
+    #[test]
+    fn shotgun_prefers_close_targets() {
+        assert!(desirability(Weapon::Shotgun, 2.0, 10) > desirability(Weapon::Shotgun, 50.0, 10));
+        assert!(
+            desirability(Weapon::Shotgun, 2.0, 10) > desirability(Weapon::RocketLauncher, 2.0, 5)
+        );
+    }
+
+    #[test]
+    fn railgun_prefers_far_targets() {
+        assert!(desirability(Weapon::Railgun, 50.0, 5) > desirability(Weapon::Railgun, 2.0, 5));
+        assert!(desirability(Weapon::Railgun, 50.0, 5) > desirability(Weapon::Shotgun, 50.0, 10));
+        assert!(desirability(Weapon::Railgun, 50.0, 5) > desirability(Weapon::Blaster, 50.0, 0));
+    }
+
+    #[test]
+    fn rocket_launcher_ammo() {
+        assert!(
+            desirability(Weapon::RocketLauncher, 50.0, 5)
+                > desirability(Weapon::RocketLauncher, 50.0, 1)
+        );
+    }
 }
@@ -14,13 +14,17 @@ mod util;
 use bevy::diagnostic::*;
 use bevy::prelude::*;
 use bevy::window::PresentMode;
+use bevy_common_assets::ron::RonAssetPlugin;
 use bevy_egui::{EguiPlugin, EguiSettings};
 use bevy_inspector_egui::prelude::*;
 use bevy_inspector_egui::WorldInspectorParams;
 use bevy_prototype_lyon::prelude::*;
+use game_ai_core::args::{parse_arg, parse_seed};
+use game_ai_core::random::Random;
 
 use plugins::debug::DebugPlugin;
 use plugins::states::StatesPlugins;
+use resources::game::*;
 use resources::ui::*;
 use states::GameState;
 
@@ -30,13 +34,22 @@ const WINDOW_HEIGHT: f32 = 768.0;
 // half-size in units
 pub const ORTHO_SIZE: f32 = 50.0;
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, random: Res<Random>) {
+    info!("random seed: {}", random.seed());
+
     #[cfg(debug_assertions)]
     asset_server.watch_for_changes().unwrap();
 
     commands.insert_resource(Fonts {
         normal: asset_server.load("fonts/FiraSans-Bold.ttf"),
     });
+
+    commands.insert_resource(MapAsset {
+        handle: asset_server.load(&format!(
+            "maps/{}.map",
+            parse_arg::<String>("--map").unwrap_or_else(|| DEFAULT_MAP.to_owned())
+        )),
+    });
 }
 
 #[bevy_main]
@@ -63,6 +76,9 @@ fn main() {
     // prototype lyon
     app.add_plugin(ShapePlugin);
 
+    // assets
+    app.add_plugin(RonAssetPlugin::<Map>::new(&["map"]));
+
     // egui
     app.insert_resource(EguiSettings {
         scale_factor: 0.75,
@@ -84,13 +100,18 @@ fn main() {
     .register_inspectable::<components::collision::Bounds>()
     .register_inspectable::<components::corpse::Corpse>()
     .register_inspectable::<components::inventory::Inventory>()
+    .register_inspectable::<components::memory::SensoryMemory>()
     .register_inspectable::<components::physics::Physical>()
     .register_inspectable::<components::physics::PhysicalCache>()
+    .register_inspectable::<components::planner::PathPlanner>()
     .register_inspectable::<components::weapon::EquippedWeapon>()
+    .register_inspectable::<components::weapon::WeaponSelector>()
     .register_inspectable::<components::spawnpoint::SpawnPoint>()
     .register_inspectable::<components::steering::Steering>()
     .register_inspectable::<components::steering::Arrive>()
     .register_inspectable::<components::steering::Seek>()
+    .register_inspectable::<components::steering::FollowPath>()
+    .register_inspectable::<components::steering::PathSmoothing>()
     .register_inspectable::<components::wall::Wall>()
     .register_inspectable::<game::weapons::Ammo>()
     .register_inspectable::<game::weapons::Weapon>();
@@ -98,6 +119,9 @@ fn main() {
     // plugins
     app.add_plugin(DebugPlugin).add_plugins(StatesPlugins);
 
+    // seedable rng
+    app.insert_resource(parse_seed().map(Random::new).unwrap_or_default());
+
     // initial game state
     app.add_state(GameState::Intro);
 
@@ -14,6 +14,10 @@ impl Plugin for DebugPlugin {
         app.add_startup_system(setup_debug);
 
         // systems
-        app.add_system(debug_system).add_system(debug_ui);
+        app.add_system(debug_system)
+            .add_system(debug_ui)
+            .add_system(navgraph_debug)
+            .add_system(search_debug)
+            .add_system(search_debug_visual.after(search_debug));
     }
 }
@@ -3,6 +3,7 @@ use bevy::ecs::schedule::ShouldRun;
 use bevy::prelude::*;
 use bevy::time::FixedTimestep;
 
+use crate::events::path::*;
 use crate::game::PHYSICS_STEP;
 use crate::states;
 use crate::states::*;
@@ -36,6 +37,10 @@ struct MainStatePlugin;
 
 impl Plugin for MainStatePlugin {
     fn build(&self, app: &mut App) {
+        // events
+        app.add_event::<PathReadyEvent>()
+            .add_event::<NoPathAvailableEvent>();
+
         // systems
         app.add_system_set(SystemSet::on_enter(GameState::Main).with_system(states::main::setup))
             // physics (fixed timestep)
@@ -56,6 +61,7 @@ impl Plugin for MainStatePlugin {
                     // steering
                     .with_system(systems::steering::update_seek.label(Systems::Steering))
                     .with_system(systems::steering::update_arrive.label(Systems::Steering))
+                    .with_system(systems::steering::update_follow_path.label(Systems::Steering))
                     .with_system(
                         systems::steering::update
                             .label(Systems::SteeringUpdatePhysics)
@@ -82,6 +88,12 @@ impl Plugin for MainStatePlugin {
                             .after(Systems::PhysicsUpdate)
                             .before(Systems::PhysicsSync),
                     )
+                    .with_system(
+                        systems::projectile::check_range
+                            .label(Systems::BoundsCheck)
+                            .after(Systems::PhysicsUpdate)
+                            .before(Systems::PhysicsSync),
+                    )
                     // collisions
                     .with_system(
                         systems::bot::check_wall_collision
@@ -101,6 +113,12 @@ impl Plugin for MainStatePlugin {
                             .after(Systems::PhysicsUpdate)
                             .before(Systems::PhysicsSync),
                     )
+                    .with_system(
+                        systems::projectile::check_hit_scan
+                            .label(Systems::CollisionCheck)
+                            .after(Systems::PhysicsUpdate)
+                            .before(Systems::PhysicsSync),
+                    )
                     .with_system(
                         systems::trigger::check_bot_collision
                             .label(Systems::CollisionCheck)
@@ -116,15 +134,50 @@ impl Plugin for MainStatePlugin {
                     // input
                     .with_system(systems::input::handle_select.label(Systems::Input))
                     .with_system(systems::input::deselect_bot.label(Systems::Input))
+                    .with_system(systems::input::toggle_path_loop.label(Systems::Input))
                     .with_system(systems::input::select_weapon.label(Systems::Input))
+                    .with_system(systems::input::toggle_weapon_auto_select.label(Systems::Input))
                     .with_system(systems::input::fire_weapon.label(Systems::Input))
                     .with_system(systems::input::fill_inventory.label(Systems::Input))
                     .with_system(systems::input::damage_bot.label(Systems::Input))
                     .with_system(systems::input::kill_bot.label(Systems::Input))
+                    // senses
+                    .with_system(systems::memory::update_vision.label(Systems::Senses))
+                    .with_system(systems::memory::hear_sounds.label(Systems::Senses))
+                    .with_system(
+                        systems::memory::update_targeting
+                            .label(Systems::Targeting)
+                            .after(Systems::Senses),
+                    )
+                    // goals
+                    .with_system(
+                        systems::brain::update
+                            .label(Systems::Brain)
+                            .after(Systems::Input)
+                            .after(Systems::Targeting),
+                    )
+                    .with_system(systems::brain::fire_weapon.after(Systems::Brain))
+                    // path planning
+                    .with_system(
+                        systems::planner::update
+                            .label(Systems::PathPlanning)
+                            .after(Systems::Input)
+                            .after(Systems::Brain),
+                    )
+                    .with_system(systems::planner::path_ready.after(Systems::PathPlanning))
+                    .with_system(systems::planner::no_path_available.after(Systems::PathPlanning))
                     // everything else
+                    .with_system(systems::bot::update)
                     .with_system(systems::trigger::update)
+                    .with_system(systems::door::update)
                     .with_system(systems::corpse::update)
-                    .with_system(systems::weapons::update.label(Systems::Weapons)),
+                    .with_system(systems::effect::update)
+                    .with_system(systems::weapons::update.label(Systems::Weapons))
+                    .with_system(
+                        systems::weapons::auto_select
+                            .label(Systems::Weapons)
+                            .after(Systems::Input),
+                    ),
             )
             .add_system_set(
                 SystemSet::on_exit(GameState::Main).with_system(states::main::teardown),
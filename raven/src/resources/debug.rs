@@ -1,4 +1,21 @@
+use bevy::prelude::*;
+
+use crate::game::search::*;
+
 #[derive(Debug, Default)]
 pub struct DebugState {
     pub enabled: bool,
+
+    pub show_navgraph: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SearchDebugState {
+    pub algorithm: SearchAlgorithm,
+
+    // (source, target) positions
+    pub endpoints: Option<(Vec2, Vec2)>,
+
+    // cleared whenever the search needs to be re-run
+    pub result: Option<SearchResult>,
 }
@@ -1,12 +1,229 @@
-#[derive(Debug, Default)]
-pub struct Map;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::game::weapons::Weapon;
+use crate::game::{BOT_RADIUS, NAVGRAPH_NODE_SPACING};
+use crate::resources::navgraph::*;
+
+pub const DEFAULT_MAP: &str = "default";
+
+// a wall segment in world space
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct MapWall {
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+impl MapWall {
+    // walls are spawned at their center
+    // with the end points relative to that
+    pub fn center(&self) -> Vec2 {
+        (self.from + self.to) * 0.5
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct MapDoor {
+    pub wall: MapWall,
+
+    // switch trigger positions
+    pub switches: Vec<Vec2>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct MapSpawnPoint {
+    pub position: Vec2,
+
+    #[serde(default)]
+    pub offset: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum MapTrigger {
+    Weapon { weapon: Weapon, position: Vec2 },
+    Health { position: Vec2 },
+}
+
+#[derive(Debug, Default, Clone, Deserialize, TypeUuid)]
+#[uuid = "3c7f1a52-8d0e-4b6a-a2f4-95e1c0d7b836"]
+pub struct Map {
+    pub name: String,
+
+    pub walls: Vec<MapWall>,
+
+    #[serde(default)]
+    pub doors: Vec<MapDoor>,
+
+    pub spawnpoints: Vec<MapSpawnPoint>,
+
+    #[serde(default)]
+    pub triggers: Vec<MapTrigger>,
+
+    // navgraph node positions and the edges between them (by node index)
+    #[serde(default)]
+    pub nav_nodes: Vec<Vec2>,
+    #[serde(default)]
+    pub nav_edges: Vec<(usize, usize)>,
+}
 
 impl Map {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.spawnpoints.is_empty() {
+            return Err("no spawnpoints".to_owned());
+        }
+
+        for (idx, wall) in self
+            .walls
+            .iter()
+            .chain(self.doors.iter().map(|door| &door.wall))
+            .enumerate()
+        {
+            if wall.from.distance_squared(wall.to) < f32::EPSILON {
+                return Err(format!("wall {} has no length", idx));
+            }
+        }
+
+        for (idx, door) in self.doors.iter().enumerate() {
+            if door.switches.is_empty() {
+                return Err(format!("door {} has no switches", idx));
+            }
+        }
+
+        let node_count = self.nav_nodes.len();
+        for (from, to) in self.nav_edges.iter() {
+            if *from >= node_count || *to >= node_count {
+                return Err(format!(
+                    "nav edge {} -> {} is outside of the {} nav nodes",
+                    from, to, node_count
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn walls(&self) -> Vec<Segment> {
+        self.walls.iter().map(|wall| (wall.from, wall.to)).collect()
+    }
+
+    fn doors(&self) -> Vec<Segment> {
+        self.doors
+            .iter()
+            .map(|door| (door.wall.from, door.wall.to))
+            .collect()
+    }
+
+    // the area covered by the walls and spawnpoints
+    fn extents(&self) -> (Vec2, Vec2) {
+        self.walls
+            .iter()
+            .chain(self.doors.iter().map(|door| &door.wall))
+            .flat_map(|wall| [wall.from, wall.to])
+            .chain(
+                self.spawnpoints
+                    .iter()
+                    .map(|spawnpoint| spawnpoint.position),
+            )
+            .fold(
+                (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+                |(min, max), position| (min.min(position), max.max(position)),
+            )
+    }
+
+    // uses the map navgraph if there is one,
+    // otherwise the navgraph is flood filled out from the spawnpoints
     pub fn calculate_navgraph(&self) -> NavGraph {
-        // TODO:
-        NavGraph::default()
+        let walls = self.walls();
+        let doors = self.doors();
+
+        let navgraph = if self.nav_nodes.is_empty() {
+            let (min, max) = self.extents();
+
+            NavGraph::flood_fill(
+                self.spawnpoints
+                    .iter()
+                    .map(|spawnpoint| spawnpoint.position + spawnpoint.offset),
+                NAVGRAPH_NODE_SPACING,
+                min,
+                max,
+                BOT_RADIUS,
+                &walls,
+                &doors,
+            )
+        } else {
+            let mut navgraph = NavGraph::from_nodes(&self.nav_nodes, &self.nav_edges);
+            navgraph.prune(&walls, &doors);
+            navgraph
+        };
+
+        if navgraph.is_empty() {
+            warn!("map '{}' has an empty navgraph", self.name);
+        } else {
+            info!(
+                "map '{}' navgraph has {} nodes and {} edges",
+                self.name,
+                navgraph.node_count(),
+                navgraph.edge_count()
+            );
+        }
+
+        navgraph
     }
 }
 
-#[derive(Debug, Default)]
-pub struct NavGraph;
+pub struct MapAsset {
+    pub handle: Handle<Map>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> Map {
+        Map {
+            name: "test".to_owned(),
+            walls: vec![MapWall {
+                from: Vec2::new(0.0, -10.0),
+                to: Vec2::new(0.0, 10.0),
+            }],
+            spawnpoints: vec![MapSpawnPoint::default()],
+            nav_nodes: vec![Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0)],
+            nav_edges: vec![(0, 1)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validate_map() {
+        assert!(map().validate().is_ok());
+
+        let mut no_spawnpoints = map();
+        no_spawnpoints.spawnpoints.clear();
+        assert!(no_spawnpoints.validate().is_err());
+
+        let mut bad_wall = map();
+        bad_wall.walls[0].to = bad_wall.walls[0].from;
+        assert!(bad_wall.validate().is_err());
+
+        let mut bad_door = map();
+        bad_door.doors.push(MapDoor {
+            wall: bad_door.walls[0],
+            switches: vec![],
+        });
+        assert!(bad_door.validate().is_err());
+
+        let mut bad_edge = map();
+        bad_edge.nav_edges.push((1, 2));
+        assert!(bad_edge.validate().is_err());
+    }
+
+    #[test]
+    fn map_wall_center() {
+        let wall = MapWall {
+            from: Vec2::new(0.0, -10.0),
+            to: Vec2::new(10.0, 10.0),
+        };
+        assert_eq!(wall.center(), Vec2::new(5.0, 0.0));
+    }
+}
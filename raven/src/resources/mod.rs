@@ -1,3 +1,5 @@
 pub mod debug;
 pub mod game;
+pub mod navgraph;
+pub mod path;
 pub mod ui;
@@ -0,0 +1,480 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::BitOr;
+
+use bevy::prelude::*;
+use game_ai_core::util::*;
+
+pub type NodeIndex = usize;
+
+// a line segment in world space
+pub type Segment = (Vec2, Vec2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeFlags(u8);
+
+impl EdgeFlags {
+    pub const WALKABLE: Self = Self(1);
+    pub const DOOR: Self = Self(1 << 1);
+
+    pub fn contains(&self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+}
+
+impl Default for EdgeFlags {
+    fn default() -> Self {
+        Self::WALKABLE
+    }
+}
+
+impl BitOr for EdgeFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NavGraphNode {
+    pub index: NodeIndex,
+    pub position: Vec2,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NavGraphEdge {
+    pub from: NodeIndex,
+    pub to: NodeIndex,
+    pub cost: f32,
+    pub flags: EdgeFlags,
+}
+
+// sparse, undirected navigation graph
+// edges are stored in both directions in per-node adjacency lists
+#[derive(Debug, Default, Clone)]
+pub struct NavGraph {
+    nodes: Vec<NavGraphNode>,
+    edges: Vec<Vec<NavGraphEdge>>,
+}
+
+impl NavGraph {
+    // builds a graph from explicit nodes and edges (by node index)
+    pub fn from_nodes(nodes: &[Vec2], edges: &[(NodeIndex, NodeIndex)]) -> Self {
+        let mut graph = Self::default();
+
+        for position in nodes {
+            graph.add_node(*position);
+        }
+
+        for (from, to) in edges {
+            graph.add_edge(*from, *to, EdgeFlags::WALKABLE);
+        }
+
+        graph
+    }
+
+    // flood fills a grid of nodes out from each of the seeds
+    // nodes and edges must be at least clearance away from any wall,
+    // edges may cross (and are flagged for) doors
+    pub fn flood_fill(
+        seeds: impl IntoIterator<Item = Vec2>,
+        spacing: f32,
+        min: Vec2,
+        max: Vec2,
+        clearance: f32,
+        walls: &[Segment],
+        doors: &[Segment],
+    ) -> Self {
+        let mut graph = Self::default();
+
+        let is_walkable = |position: Vec2| {
+            is_open(position, min, max, clearance, walls)
+                && is_open(position, min, max, clearance, doors)
+        };
+
+        let mut grid = HashMap::new();
+        let mut open = VecDeque::new();
+
+        for seed in seeds {
+            let cell = (
+                (seed.x / spacing).round() as i32,
+                (seed.y / spacing).round() as i32,
+            );
+            if grid.contains_key(&cell) {
+                continue;
+            }
+
+            let position = Vec2::new(cell.0 as f32, cell.1 as f32) * spacing;
+            if !is_walkable(position) {
+                warn!("unable to flood fill navgraph from {}", seed);
+                continue;
+            }
+
+            grid.insert(cell, graph.add_node(position));
+            open.push_back(cell);
+        }
+
+        while let Some(cell) = open.pop_front() {
+            let index = grid[&cell];
+            let position = graph.node(index).position;
+
+            for (x, y) in [
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ] {
+                let neighbor = (cell.0 + x, cell.1 + y);
+                let neighbor_position = Vec2::new(neighbor.0 as f32, neighbor.1 as f32) * spacing;
+
+                if is_obstructed(walls, position, neighbor_position, clearance) {
+                    continue;
+                }
+
+                let neighbor_index = match grid.get(&neighbor) {
+                    Some(neighbor_index) => *neighbor_index,
+                    None => {
+                        if !is_walkable(neighbor_position) {
+                            continue;
+                        }
+
+                        let neighbor_index = graph.add_node(neighbor_position);
+                        grid.insert(neighbor, neighbor_index);
+                        open.push_back(neighbor);
+
+                        neighbor_index
+                    }
+                };
+
+                let mut flags = EdgeFlags::WALKABLE;
+                if is_blocked(doors, position, neighbor_position) {
+                    flags = flags | EdgeFlags::DOOR;
+                }
+
+                graph.add_edge(index, neighbor_index, flags);
+            }
+        }
+
+        graph
+    }
+
+    // a grid of nodes covering min to max,
+    // skipping any node or edge that isn't at least clearance away from a wall
+    pub fn grid(spacing: f32, min: Vec2, max: Vec2, clearance: f32, walls: &[Segment]) -> Self {
+        let mut graph = Self::default();
+
+        let min_cell = (min / spacing).ceil();
+        let max_cell = (max / spacing).floor();
+
+        let mut grid = HashMap::new();
+        for y in min_cell.y as i32..=max_cell.y as i32 {
+            for x in min_cell.x as i32..=max_cell.x as i32 {
+                let position = Vec2::new(x as f32, y as f32) * spacing;
+                if !is_open(position, min, max, clearance, walls) {
+                    continue;
+                }
+
+                let index = graph.add_node(position);
+                grid.insert((x, y), index);
+
+                // connect to the neighbors that have already been added
+                for neighbor in [(x - 1, y), (x - 1, y - 1), (x, y - 1), (x + 1, y - 1)] {
+                    if let Some(&neighbor_index) = grid.get(&neighbor) {
+                        let neighbor_position = graph.node(neighbor_index).position;
+                        if !is_obstructed(walls, neighbor_position, position, clearance) {
+                            graph.add_edge(neighbor_index, index, EdgeFlags::WALKABLE);
+                        }
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // undirected edge count
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(|edges| edges.len()).sum::<usize>() / 2
+    }
+
+    pub fn node(&self, index: NodeIndex) -> &NavGraphNode {
+        &self.nodes[index]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &NavGraphNode> {
+        self.nodes.iter()
+    }
+
+    // edges leaving the given node
+    pub fn edges(&self, from: NodeIndex) -> &[NavGraphEdge] {
+        &self.edges[from]
+    }
+
+    // each undirected edge once
+    pub fn unique_edges(&self) -> impl Iterator<Item = &NavGraphEdge> {
+        self.edges
+            .iter()
+            .flatten()
+            .filter(|edge| edge.from < edge.to)
+    }
+
+    pub fn edge(&self, from: NodeIndex, to: NodeIndex) -> Option<&NavGraphEdge> {
+        self.edges
+            .get(from)
+            .and_then(|edges| edges.iter().find(|edge| edge.to == to))
+    }
+
+    pub fn closest_node(&self, position: Vec2) -> Option<NodeIndex> {
+        self.nodes
+            .iter()
+            .min_by(|a, b| {
+                a.position
+                    .distance_squared(position)
+                    .total_cmp(&b.position.distance_squared(position))
+            })
+            .map(|node| node.index)
+    }
+
+    pub fn add_node(&mut self, position: Vec2) -> NodeIndex {
+        let index = self.nodes.len();
+
+        self.nodes.push(NavGraphNode { index, position });
+        self.edges.push(vec![]);
+
+        index
+    }
+
+    // returns false if the edge already exists
+    pub fn add_edge(&mut self, from: NodeIndex, to: NodeIndex, flags: EdgeFlags) -> bool {
+        if from == to || self.edge(from, to).is_some() {
+            return false;
+        }
+
+        let cost = self.nodes[from].position.distance(self.nodes[to].position);
+
+        self.edges[from].push(NavGraphEdge {
+            from,
+            to,
+            cost,
+            flags,
+        });
+        self.edges[to].push(NavGraphEdge {
+            from: to,
+            to: from,
+            cost,
+            flags,
+        });
+
+        true
+    }
+
+    // removes any edges that cross a wall
+    // and flags any edges that cross a door
+    pub fn prune(&mut self, walls: &[Segment], doors: &[Segment]) {
+        let nodes = &self.nodes;
+        for edges in self.edges.iter_mut() {
+            edges.retain(|edge| {
+                !is_blocked(walls, nodes[edge.from].position, nodes[edge.to].position)
+            });
+
+            for edge in edges.iter_mut() {
+                if is_blocked(doors, nodes[edge.from].position, nodes[edge.to].position) {
+                    edge.flags = edge.flags | EdgeFlags::DOOR;
+                }
+            }
+        }
+    }
+}
+
+// true if something with the given radius can move from one point to the other
+// without touching any of the walls
+pub fn can_walk_between(from: Vec2, to: Vec2, radius: f32, walls: &[Segment]) -> bool {
+    walls
+        .iter()
+        .all(|(a, b)| distance_between_line_segments(from, to, *a, *b) >= radius)
+}
+
+// inside the bounds and at least clearance away from all of the segments
+fn is_open(position: Vec2, min: Vec2, max: Vec2, clearance: f32, segments: &[Segment]) -> bool {
+    position.cmpge(min).all()
+        && position.cmple(max).all()
+        && segments
+            .iter()
+            .all(|(from, to)| distance_to_line_segment(*from, *to, position) >= clearance)
+}
+
+fn is_blocked(segments: &[Segment], from: Vec2, to: Vec2) -> bool {
+    segments
+        .iter()
+        .any(|(a, b)| line_intersection(from, to, *a, *b).is_some())
+}
+
+// blocked, or passing too close to the end of a segment
+fn is_obstructed(segments: &[Segment], from: Vec2, to: Vec2, clearance: f32) -> bool {
+    is_blocked(segments, from, to)
+        || segments.iter().any(|(a, b)| {
+            distance_to_line_segment(from, to, *a) < clearance
+                || distance_to_line_segment(from, to, *b) < clearance
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> NavGraph {
+        // 0 - 1
+        // |   |
+        // 3 - 2
+        NavGraph::from_nodes(
+            &[
+                Vec2::new(0.0, 10.0),
+                Vec2::new(10.0, 10.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(0.0, 0.0),
+            ],
+            &[(0, 1), (1, 2), (2, 3), (3, 0)],
+        )
+    }
+
+    #[test]
+    fn edges_are_undirected() {
+        let mut graph = square();
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.unique_edges().count(), 4);
+
+        assert_eq!(graph.edge(1, 0).unwrap().cost, 10.0);
+
+        // no duplicates
+        assert!(!graph.add_edge(1, 0, EdgeFlags::WALKABLE));
+        assert!(!graph.add_edge(2, 2, EdgeFlags::WALKABLE));
+    }
+
+    #[test]
+    fn prune_walls_and_doors() {
+        let mut graph = square();
+
+        let walls = [(Vec2::new(5.0, 5.0), Vec2::new(5.0, 15.0))];
+        let doors = [(Vec2::new(5.0, -5.0), Vec2::new(5.0, 5.0))];
+        graph.prune(&walls, &doors);
+
+        // the top edge crosses the wall
+        assert!(graph.edge(0, 1).is_none());
+        assert!(graph.edge(1, 0).is_none());
+
+        // the bottom edge crosses the door
+        assert!(graph.edge(2, 3).unwrap().flags.contains(EdgeFlags::DOOR));
+        assert!(graph.edge(3, 2).unwrap().flags.contains(EdgeFlags::DOOR));
+
+        // the sides are untouched
+        assert_eq!(graph.edge(1, 2).unwrap().flags, EdgeFlags::WALKABLE);
+        assert_eq!(graph.edge(3, 0).unwrap().flags, EdgeFlags::WALKABLE);
+    }
+
+    #[test]
+    fn flood_fill_stays_behind_walls() {
+        // a wall splitting the area in two
+        let walls = [(Vec2::new(12.5, -100.0), Vec2::new(12.5, 100.0))];
+
+        let graph = NavGraph::flood_fill(
+            [Vec2::ZERO],
+            5.0,
+            Vec2::new(-20.0, -20.0),
+            Vec2::new(40.0, 20.0),
+            1.0,
+            &walls,
+            &[],
+        );
+
+        // x = -20 to 10, y = -20 to 20
+        assert_eq!(graph.node_count(), 7 * 9);
+        assert!(graph.nodes().all(|node| node.position.x < 12.5));
+
+        // every node is connected to its neighbors
+        let corner = graph.closest_node(Vec2::new(-20.0, -20.0)).unwrap();
+        assert_eq!(graph.edges(corner).len(), 3);
+
+        let center = graph.closest_node(Vec2::ZERO).unwrap();
+        assert_eq!(graph.edges(center).len(), 8);
+    }
+
+    #[test]
+    fn flood_fill_through_doors() {
+        let walls = [
+            (Vec2::new(12.5, -100.0), Vec2::new(12.5, -5.0)),
+            (Vec2::new(12.5, 5.0), Vec2::new(12.5, 100.0)),
+        ];
+        let doors = [(Vec2::new(12.5, -5.0), Vec2::new(12.5, 5.0))];
+
+        let graph = NavGraph::flood_fill(
+            [Vec2::ZERO],
+            5.0,
+            Vec2::new(-20.0, -20.0),
+            Vec2::new(40.0, 20.0),
+            1.0,
+            &walls,
+            &doors,
+        );
+
+        // the other side of the door is reachable
+        assert!(graph.nodes().any(|node| node.position.x > 12.5));
+
+        // but only through door edges
+        for edge in graph.unique_edges() {
+            let from = graph.node(edge.from).position;
+            let to = graph.node(edge.to).position;
+            if (from.x < 12.5) != (to.x < 12.5) {
+                assert!(edge.flags.contains(EdgeFlags::DOOR));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_avoids_walls() {
+        let walls = [(Vec2::new(12.5, -100.0), Vec2::new(12.5, 100.0))];
+
+        let graph = NavGraph::grid(
+            5.0,
+            Vec2::new(-20.0, -20.0),
+            Vec2::new(40.0, 20.0),
+            1.0,
+            &walls,
+        );
+
+        // x = -20 to 40, y = -20 to 20
+        assert_eq!(graph.node_count(), 13 * 9);
+
+        // nothing crosses the wall
+        for edge in graph.unique_edges() {
+            let from = graph.node(edge.from).position;
+            let to = graph.node(edge.to).position;
+            assert_eq!(from.x < 12.5, to.x < 12.5);
+        }
+
+        let corner = graph.closest_node(Vec2::new(-20.0, -20.0)).unwrap();
+        assert_eq!(graph.edges(corner).len(), 3);
+
+        let center = graph.closest_node(Vec2::ZERO).unwrap();
+        assert_eq!(graph.edges(center).len(), 8);
+    }
+
+    #[test]
+    fn closest_node() {
+        let graph = square();
+        assert_eq!(graph.closest_node(Vec2::new(9.0, 1.0)), Some(2));
+        assert_eq!(NavGraph::default().closest_node(Vec2::ZERO), None);
+    }
+}
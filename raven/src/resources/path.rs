@@ -0,0 +1,251 @@
+use bevy::prelude::*;
+
+use crate::game::search::*;
+use crate::game::{BOT_RADIUS, NAVGRAPH_NODE_SPACING};
+use crate::resources::navgraph::*;
+
+// a pending path request for a single bot
+#[derive(Debug)]
+struct PathRequest {
+    entity: Entity,
+    source: Vec2,
+    destination: Vec2,
+
+    // started on the first update that has a graph to search
+    search: Option<GraphSearch>,
+}
+
+impl PathRequest {
+    // graph must not be empty
+    fn step(&mut self, graph: &NavGraph) -> SearchStatus {
+        let (source, destination) = (self.source, self.destination);
+        self.search
+            .get_or_insert_with(|| {
+                GraphSearch::new(
+                    graph,
+                    graph.closest_node(source).unwrap(),
+                    graph.closest_node(destination).unwrap(),
+                    SearchAlgorithm::AStar(Heuristic::Euclidean),
+                )
+            })
+            .step(graph)
+    }
+
+    // the node positions followed by the destination
+    fn path(&self, graph: &NavGraph) -> Option<Vec<Vec2>> {
+        let search = self.search.as_ref()?;
+        if search.status() != SearchStatus::Found {
+            return None;
+        }
+
+        Some(
+            search
+                .path()
+                .into_iter()
+                .map(|node| graph.node(node).position)
+                .chain(std::iter::once(self.destination))
+                .collect(),
+        )
+    }
+}
+
+// time-sliced path planning for every bot
+#[derive(Debug, Default)]
+pub struct PathManager {
+    // grid built from the walls when the navgraph is empty
+    grid: Option<NavGraph>,
+
+    requests: Vec<PathRequest>,
+}
+
+impl PathManager {
+    // replaces any pending request for the entity
+    pub fn request(&mut self, entity: Entity, source: Vec2, destination: Vec2) {
+        self.cancel(entity);
+
+        self.requests.push(PathRequest {
+            entity,
+            source,
+            destination,
+            search: None,
+        });
+    }
+
+    pub fn cancel(&mut self, entity: Entity) {
+        self.requests.retain(|request| request.entity != entity);
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    pub fn has_grid(&self) -> bool {
+        self.grid.is_some()
+    }
+
+    pub fn build_grid(&mut self, walls: &[Segment]) {
+        let (min, max) = walls.iter().flat_map(|(from, to)| [*from, *to]).fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), position| (min.min(position), max.max(position)),
+        );
+
+        let grid = NavGraph::grid(NAVGRAPH_NODE_SPACING, min, max, BOT_RADIUS, walls);
+        info!(
+            "path manager grid has {} nodes and {} edges",
+            grid.node_count(),
+            grid.edge_count()
+        );
+
+        self.grid = Some(grid);
+    }
+
+    // drops the grid and restarts any pending searches
+    pub fn invalidate(&mut self) {
+        self.grid = None;
+
+        for request in self.requests.iter_mut() {
+            request.search = None;
+        }
+    }
+
+    // advances the pending searches one cycle at a time, round robin,
+    // until they complete or the cycles run out
+    // returns the path (or None if there is no path) for each completed request
+    pub fn update(
+        &mut self,
+        navgraph: Option<&NavGraph>,
+        mut cycles: usize,
+    ) -> Vec<(Entity, Option<Vec<Vec2>>)> {
+        let graph = match navgraph
+            .filter(|navgraph| !navgraph.is_empty())
+            .or_else(|| self.grid.as_ref().filter(|grid| !grid.is_empty()))
+        {
+            Some(graph) => graph,
+            None => return vec![],
+        };
+
+        let mut completed = vec![];
+        while cycles > 0 && !self.requests.is_empty() {
+            let mut idx = 0;
+            while idx < self.requests.len() && cycles > 0 {
+                cycles -= 1;
+
+                if self.requests[idx].step(graph) == SearchStatus::InProgress {
+                    idx += 1;
+                    continue;
+                }
+
+                let request = self.requests.remove(idx);
+                completed.push((request.entity, request.path(graph)));
+            }
+        }
+
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn navgraph() -> NavGraph {
+        // 0 - 1 - 2 - 3    4
+        NavGraph::from_nodes(
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(20.0, 0.0),
+                Vec2::new(30.0, 0.0),
+                Vec2::new(40.0, 0.0),
+            ],
+            &[(0, 1), (1, 2), (2, 3)],
+        )
+    }
+
+    #[test]
+    fn searches_are_time_sliced() {
+        let navgraph = navgraph();
+
+        let a = Entity::from_raw(0);
+        let b = Entity::from_raw(1);
+
+        let mut manager = PathManager::default();
+        manager.request(a, Vec2::new(1.0, 1.0), Vec2::new(31.0, 1.0));
+        manager.request(b, Vec2::new(1.0, 1.0), Vec2::new(41.0, 1.0));
+
+        // each search needs 4 cycles to reach node 3
+        assert!(manager.update(Some(&navgraph), 6).is_empty());
+        assert!(!manager.is_idle());
+
+        let completed = manager.update(Some(&navgraph), 2);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].0, a);
+        assert_eq!(
+            completed[0].1,
+            Some(vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(20.0, 0.0),
+                Vec2::new(30.0, 0.0),
+                Vec2::new(31.0, 1.0),
+            ])
+        );
+
+        // node 4 is unreachable
+        let completed = manager.update(Some(&navgraph), 100);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0], (b, None));
+        assert!(manager.is_idle());
+    }
+
+    #[test]
+    fn requests_replace_pending_requests() {
+        let navgraph = navgraph();
+
+        let a = Entity::from_raw(0);
+
+        let mut manager = PathManager::default();
+        manager.request(a, Vec2::ZERO, Vec2::new(40.0, 0.0));
+        manager.request(a, Vec2::ZERO, Vec2::new(10.0, 0.0));
+
+        let completed = manager.update(Some(&navgraph), 100);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(
+            completed[0].1.as_ref().unwrap().last(),
+            Some(&Vec2::new(10.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn grid_is_used_without_a_navgraph() {
+        let a = Entity::from_raw(0);
+
+        let mut manager = PathManager::default();
+        manager.request(a, Vec2::new(-10.0, 0.0), Vec2::new(10.0, 0.0));
+
+        // nothing to search yet
+        assert!(manager.update(None, 100).is_empty());
+        assert!(manager.update(Some(&NavGraph::default()), 100).is_empty());
+
+        // a box with a wall down the middle that stops short of the top
+        manager.build_grid(&[
+            (Vec2::new(-20.0, -20.0), Vec2::new(20.0, -20.0)),
+            (Vec2::new(20.0, -20.0), Vec2::new(20.0, 20.0)),
+            (Vec2::new(20.0, 20.0), Vec2::new(-20.0, 20.0)),
+            (Vec2::new(-20.0, 20.0), Vec2::new(-20.0, -20.0)),
+            (Vec2::new(2.5, -20.0), Vec2::new(2.5, 12.5)),
+        ]);
+        assert!(manager.has_grid());
+
+        let completed = manager.update(None, 1000);
+        assert_eq!(completed.len(), 1);
+
+        // the path goes around the top of the wall
+        let path = completed[0].1.as_ref().unwrap();
+        assert!(path.iter().any(|position| position.y > 12.5));
+        assert_eq!(path.last(), Some(&Vec2::new(10.0, 0.0)));
+
+        manager.invalidate();
+        assert!(!manager.has_grid());
+    }
+}
@@ -1,6 +1,8 @@
+use bevy::asset::LoadState;
 use bevy::prelude::*;
 
 use crate::components::camera::*;
+use crate::resources::game::MapAsset;
 use crate::resources::ui::*;
 
 use super::*;
@@ -22,10 +24,17 @@ pub fn setup(mut commands: Commands, fonts: Res<Fonts>) {
 pub fn button_handler(
     mut action_query: Query<(&Interaction, With<Button>), (Changed<Interaction>, With<Button>)>,
     mut state: ResMut<State<GameState>>,
+    asset_server: Res<AssetServer>,
+    map_asset: Res<MapAsset>,
 ) {
     if let Ok((interaction, _)) = action_query.get_single_mut() {
         if *interaction == Interaction::Clicked {
-            state.set(GameState::Main).unwrap();
+            // the main state needs the map
+            match asset_server.get_load_state(&map_asset.handle) {
+                LoadState::Loaded => state.set(GameState::Main).unwrap(),
+                LoadState::Failed => error!("failed to load map"),
+                _ => info!("map is still loading ..."),
+            }
         }
     }
 }
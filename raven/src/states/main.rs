@@ -1,18 +1,29 @@
 use bevy::prelude::*;
 use bevy::render::camera::ScalingMode;
+use game_ai_core::cooldown::*;
+use game_ai_core::random::Random;
 
 use crate::bundles::bot::BotBundle;
+use crate::bundles::door::DoorBundle;
+use crate::bundles::spawnpoint::SpawnPointBundle;
 use crate::bundles::trigger::*;
 use crate::bundles::wall::WallBundle;
 use crate::components::camera::*;
 use crate::components::trigger::*;
-use crate::game::cooldown::*;
-use crate::game::weapons::*;
-use crate::game::POWERUP_TRIGGER_RADIUS;
+use crate::game::{
+    HEALTH_TRIGGER_RESPAWN_SECONDS, POWERUP_TRIGGER_RADIUS, WEAPON_TRIGGER_RESPAWN_SECONDS,
+};
 use crate::resources::game::*;
+use crate::resources::navgraph::*;
+use crate::resources::path::*;
 use crate::ORTHO_SIZE;
 
-pub fn setup(mut commands: Commands) {
+pub fn setup(
+    mut commands: Commands,
+    mut random: ResMut<Random>,
+    map_asset: Res<MapAsset>,
+    maps: Res<Assets<Map>>,
+) {
     debug!("entering main state");
 
     // cameras
@@ -25,57 +36,88 @@ pub fn setup(mut commands: Commands) {
         .insert(Name::new("Main Camera"));
 
     // load map
-    // TODO:
-    let map = Map::default();
+    let map = match maps.get(&map_asset.handle) {
+        Some(map) => map,
+        None => {
+            error!("map not loaded");
+            return;
+        }
+    };
+    map.validate()
+        .unwrap_or_else(|err| panic!("invalid map '{}': {}", map.name, err));
+
+    info!("loading map '{}'", map.name);
 
     commands.insert_resource(map.calculate_navgraph());
+    commands.insert_resource(PathManager::default());
 
-    // spawn the world
-    // TODO: this should come from the map
-
-    WallBundle::spawn(
-        &mut commands,
-        Vec2::new(20.0, 0.0),
-        Vec2::new(0.0, -15.0),
-        Vec2::new(0.0, 15.0),
-    );
-
-    // TODO: spawn spawnpoints
-
-    // spawn triggers
-    TriggerBundle::spawn(
-        &mut commands,
-        Trigger::Weapon(Weapon::RocketLauncher, Cooldown::from_seconds(30.0)),
-        Vec2::new(-20.0, 0.0),
-        POWERUP_TRIGGER_RADIUS,
-    );
-
-    TriggerBundle::spawn(
-        &mut commands,
-        Trigger::Health(Cooldown::from_seconds(60.0)),
-        Vec2::new(30.0, 0.0),
-        POWERUP_TRIGGER_RADIUS,
-    );
+    spawn_map(&mut commands, map);
 
     // spawn bots
-    // TODO: this should be done using spawnpoints
-    // and should probably happen after setup
-
-    BotBundle::spawn_at_position(
-        &mut commands,
-        "Bot A",
-        Color::TURQUOISE,
-        10,
-        Vec2::new(0.0, 10.0),
-    );
-
-    BotBundle::spawn_at_position(
-        &mut commands,
-        "Bot B",
-        Color::ORANGE,
-        10,
-        Vec2::new(-10.0, -10.0),
-    );
+    // TODO: this should probably happen after setup
+    // so that it can use the spawnpoint entities
+
+    let bots = [("Bot A", Color::TURQUOISE), ("Bot B", Color::ORANGE)];
+    for ((name, color), spawnpoint) in bots.into_iter().zip(map.spawnpoints.iter().cycle()) {
+        BotBundle::spawn_at_position(
+            &mut commands,
+            random.stream("setup"),
+            name,
+            color,
+            10,
+            spawnpoint.position + spawnpoint.offset,
+        );
+    }
+}
+
+fn spawn_map(commands: &mut Commands, map: &Map) {
+    // walls
+    for wall in map.walls.iter() {
+        let center = wall.center();
+        WallBundle::spawn(commands, center, wall.from - center, wall.to - center);
+    }
+
+    // doors
+    for door in map.doors.iter() {
+        let center = door.wall.center();
+        DoorBundle::spawn(
+            commands,
+            center,
+            door.wall.from - center,
+            door.wall.to - center,
+            door.switches.iter().copied(),
+        );
+    }
+
+    // spawnpoints
+    for spawnpoint in map.spawnpoints.iter() {
+        SpawnPointBundle::spawn(commands, spawnpoint.position, spawnpoint.offset);
+    }
+
+    // triggers
+    for trigger in map.triggers.iter() {
+        match *trigger {
+            MapTrigger::Weapon { weapon, position } => {
+                TriggerBundle::spawn(
+                    commands,
+                    Trigger::Weapon(
+                        weapon,
+                        Cooldown::from_seconds(WEAPON_TRIGGER_RESPAWN_SECONDS),
+                    ),
+                    position,
+                    POWERUP_TRIGGER_RADIUS,
+                );
+            }
+            MapTrigger::Health { position } => {
+                TriggerBundle::spawn(
+                    commands,
+                    Trigger::Health(Cooldown::from_seconds(HEALTH_TRIGGER_RESPAWN_SECONDS)),
+                    position,
+                    POWERUP_TRIGGER_RADIUS,
+                );
+            }
+        }
+    }
 }
 
 pub fn teardown(mut commands: Commands, entities: Query<Entity>) {
@@ -86,5 +128,6 @@ pub fn teardown(mut commands: Commands, entities: Query<Entity>) {
     }
 
     commands.remove_resource::<NavGraph>();
+    commands.remove_resource::<PathManager>();
     commands.remove_resource::<ClearColor>();
 }
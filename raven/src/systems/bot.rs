@@ -1,11 +1,100 @@
 use bevy::prelude::*;
+use game_ai_core::random::Random;
+use rand::seq::SliceRandom;
 
 use crate::components::bot::*;
 use crate::components::collision::*;
 use crate::components::physics::*;
+use crate::components::spawnpoint::*;
 use crate::components::wall::*;
+use crate::util::*;
 use crate::ORTHO_SIZE;
 
+pub fn update(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut random: ResMut<Random>,
+    mut bots: Query<(
+        Entity,
+        &mut Bot,
+        &mut Physical,
+        &mut Transform,
+        &mut Visibility,
+        &Bounds,
+        &Children,
+        &Name,
+    )>,
+    spawnpoints: Query<SpawnPointQuery, Without<Bot>>,
+    mut invulnerable_visibility: Query<
+        &mut Visibility,
+        (With<InvulnerableBotVisual>, Without<Bot>),
+    >,
+) {
+    // bots in play block the spawnpoints they're standing on
+    let mut occupied = bots
+        .iter()
+        .filter(|(_, bot, _, _, _, _, _, _)| bot.is_alive())
+        .map(|(_, _, _, transform, _, bounds, _, _)| {
+            (transform.translation.truncate(), bounds.clone())
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, mut bot, mut physical, mut transform, mut visibility, bounds, children, name) in
+        bots.iter_mut()
+    {
+        let respawn = bot.update(time.delta_seconds(), name.as_str());
+
+        for &child in children.iter() {
+            if let Ok(mut invulnerable_visibility) = invulnerable_visibility.get_mut(child) {
+                invulnerable_visibility.is_visible = bot.invulnerable;
+            }
+        }
+
+        if bot.is_alive() {
+            continue;
+        }
+
+        // dead bots don't move
+        physical.stop();
+
+        if !respawn {
+            continue;
+        }
+
+        let available = spawnpoints
+            .iter()
+            .map(|spawnpoint| {
+                spawnpoint
+                    .spawnpoint
+                    .get_spawn_position(spawnpoint.transform)
+            })
+            .filter(|position| {
+                !occupied.iter().any(|(other_position, other_bounds)| {
+                    bounds.bounds_intersects(*position, other_bounds, *other_position)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // try again next frame if every spawnpoint is occupied
+        let position = match available.choose(random.stream("respawn")) {
+            Some(position) => *position,
+            None => continue,
+        };
+
+        bot.respawn(
+            &mut commands,
+            entity,
+            &mut physical,
+            &mut transform,
+            &mut visibility,
+            position,
+            name.as_str(),
+        );
+
+        occupied.push((position, bounds.clone()));
+    }
+}
+
 pub fn check_bounds(
     windows: Res<Windows>,
     mut bots: Query<(PhysicalQueryMut, &Bounds), With<Bot>>,
@@ -17,14 +106,10 @@ pub fn check_bounds(
     let max_y = ORTHO_SIZE;
 
     for (mut physical, bounds) in bots.iter_mut() {
-        let bot_min_x =
-            physical.physical.cache.future_position.x + bounds.center().x - bounds.width();
-        let bot_min_y =
-            physical.physical.cache.future_position.y + bounds.center().y - bounds.height();
-        let bot_max_x =
-            physical.physical.cache.future_position.x + bounds.center().x + bounds.width();
-        let bot_max_y =
-            physical.physical.cache.future_position.y + bounds.center().y + bounds.height();
+        let bot_min_x = physical.cache.future_position.x + bounds.center().x - bounds.width();
+        let bot_min_y = physical.cache.future_position.y + bounds.center().y - bounds.height();
+        let bot_max_x = physical.cache.future_position.x + bounds.center().x + bounds.width();
+        let bot_max_y = physical.cache.future_position.y + bounds.center().y + bounds.height();
 
         if bot_min_x < -max_x || bot_max_x > max_x || bot_min_y < -max_y || bot_max_y > max_y {
             // TODO: we should stop at the intersection
@@ -40,8 +125,8 @@ pub fn check_wall_collision(
     for (mut physical, bounds) in bots.iter_mut() {
         // TODO: need to account for bounds width / height
 
-        let bot_position = physical.physical.cache.position + bounds.center();
-        let heading = physical.physical.cache.heading * physical.physical.cache.max_distance;
+        let bot_position = physical.cache.position + bounds.center();
+        let heading = physical.cache.heading * physical.cache.max_distance;
 
         for wall in walls.iter() {
             let wall_position = wall.transform.translation.truncate();
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use game_ai_core::random::Random;
+use strum::IntoEnumIterator;
+
+use crate::components::agent::*;
+use crate::components::bot::*;
+use crate::components::brain::*;
+use crate::components::inventory::*;
+use crate::components::memory::*;
+use crate::components::physics::*;
+use crate::components::planner::*;
+use crate::components::steering::*;
+use crate::components::targeting::*;
+use crate::components::trigger::*;
+use crate::components::weapon::*;
+use crate::game::goals::*;
+use crate::game::weapons::*;
+use crate::resources::navgraph::*;
+use crate::resources::path::*;
+
+pub fn update(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut random: ResMut<Random>,
+    mut manager: ResMut<PathManager>,
+    navgraph: Option<Res<NavGraph>>,
+    mut bots: Query<(
+        Entity,
+        &mut Brain,
+        &Agent,
+        &mut Steering,
+        &mut Physical,
+        &mut PathPlanner,
+        &Bot,
+        &Inventory,
+        &Targeting,
+        &SensoryMemory,
+        &Transform,
+        &Name,
+        Option<&PossessedBot>,
+    )>,
+    triggers: Query<(&Trigger, &Transform)>,
+) {
+    let dt = time.delta_seconds();
+
+    let mut health_items = vec![];
+    let mut weapon_items = vec![];
+    for (trigger, transform) in triggers.iter() {
+        let position = transform.translation.truncate();
+        match trigger {
+            Trigger::Health(cooldown) if cooldown.is_available() => health_items.push(position),
+            Trigger::Weapon(weapon, cooldown) if cooldown.is_available() => {
+                weapon_items.push((*weapon, position))
+            }
+            _ => (),
+        }
+    }
+
+    for (
+        entity,
+        mut brain,
+        agent,
+        mut steering,
+        mut physical,
+        mut planner,
+        bot,
+        inventory,
+        targeting,
+        memory,
+        transform,
+        name,
+        possessed,
+    ) in bots.iter_mut()
+    {
+        let position = transform.translation.truncate();
+        let target = targeting.get_target(memory);
+
+        let mut ctx = GoalContext {
+            commands: &mut commands,
+            manager: &mut manager,
+            navgraph: navgraph.as_deref(),
+            random: random.stream("brain"),
+            entity,
+            agent,
+            steering: &mut steering,
+            physical: &mut physical,
+            planner: &mut planner,
+            position,
+            target,
+            name: name.as_str(),
+            dt,
+        };
+
+        // possessed bots are driven by the player
+        if possessed.is_some() || !bot.is_alive() {
+            brain.think.remove_all_subgoals(&mut ctx);
+            brain.take_path_result();
+            continue;
+        }
+
+        match brain.take_path_result() {
+            Some(PathResult::Ready(waypoints)) => brain.think.path_ready(&mut ctx, &waypoints),
+            Some(PathResult::NoPath) => brain.think.no_path_available(),
+            None => (),
+        }
+
+        if brain.should_arbitrate(dt) {
+            let features = GoalFeatures {
+                position,
+                health: bot.get_health_percent(),
+                health_items: health_items.clone(),
+                weapon_items: weapon_items.clone(),
+                weapon_strengths: Weapon::iter()
+                    .map(|weapon| (weapon, inventory.get_weapon_strength(weapon)))
+                    .collect(),
+                target,
+            };
+
+            brain.think.arbitrate(&mut ctx, &features);
+        }
+
+        brain.think.process(&mut ctx);
+    }
+}
+
+// bots that aren't possessed shoot at their target when it can be shot
+pub fn fire_weapon(
+    mut commands: Commands,
+    mut bots: Query<
+        (
+            Entity,
+            &Targeting,
+            &SensoryMemory,
+            &Bot,
+            &mut EquippedWeapon,
+            &mut Inventory,
+            &Transform,
+            &Name,
+        ),
+        Without<PossessedBot>,
+    >,
+) {
+    for (entity, targeting, memory, bot, mut weapon, mut inventory, transform, name) in
+        bots.iter_mut()
+    {
+        let target = match targeting.get_target(memory) {
+            Some(target) if target.visible => target,
+            _ => continue,
+        };
+
+        if !weapon.is_ready() || weapon.is_empty(&inventory) {
+            continue;
+        }
+
+        bot.fire_weapon(
+            &mut commands,
+            entity,
+            &mut weapon,
+            &mut inventory,
+            target.position,
+            transform,
+            name.as_str(),
+        );
+    }
+}
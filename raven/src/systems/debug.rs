@@ -2,12 +2,22 @@ use bevy::diagnostic::*;
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContext};
 use bevy_inspector_egui::WorldInspectorParams;
+use bevy_prototype_lyon::prelude::*;
 
+use crate::components::bot::*;
+use crate::components::brain::*;
+use crate::components::camera::*;
+use crate::components::*;
 use crate::events::debug::*;
+use crate::game::search::*;
+use crate::game::{NAVGRAPH_DEBUG_SORT, SEARCH_DEBUG_SORT};
 use crate::resources::debug::*;
+use crate::resources::navgraph::*;
+use crate::util::*;
 
 pub(crate) fn setup_debug(mut commands: Commands) {
     commands.insert_resource(DebugState::default());
+    commands.insert_resource(SearchDebugState::default());
 }
 
 pub fn debug_system(
@@ -48,11 +58,13 @@ fn fps(diagnostics: &Diagnostics, dt: f64) -> (f64, f64) {
 }
 
 pub fn debug_ui(
-    debug_state: Res<DebugState>,
+    mut debug_state: ResMut<DebugState>,
+    mut search: ResMut<SearchDebugState>,
     mut context: ResMut<EguiContext>,
     mut inspector: ResMut<WorldInspectorParams>,
     time: Res<Time>,
     diagnostics: Res<Diagnostics>,
+    selected: Query<(&Brain, &Name), With<SelectedBot>>,
 ) {
     if !debug_state.enabled {
         return;
@@ -71,6 +83,256 @@ pub fn debug_ui(
             if ui.button("Inspector").clicked() {
                 inspector.enabled = !inspector.enabled;
             }
+
+            if ui.button("NavGraph").clicked() {
+                debug_state.show_navgraph = !debug_state.show_navgraph;
+            }
+
+            ui.separator();
+
+            let mut algorithm = search.algorithm;
+            egui::ComboBox::from_label("Search")
+                .selected_text(algorithm.get_name())
+                .show_ui(ui, |ui| {
+                    for option in SEARCH_ALGORITHMS {
+                        ui.selectable_value(&mut algorithm, option, option.get_name());
+                    }
+                });
+
+            if algorithm != search.algorithm {
+                search.algorithm = algorithm;
+                search.result = None;
+            }
+
+            if let Some(result) = &search.result {
+                ui.label(format!(
+                    "{:?}: {} nodes, cost {:.1}, {} visited",
+                    result.status,
+                    result.path.len(),
+                    result.cost,
+                    result.visited.len()
+                ));
+            }
+
+            if ui.button("Clear Search").clicked() {
+                search.endpoints = None;
+                search.result = None;
+            }
+
+            // goal stack of the selected bot
+            if let Some((brain, name)) = selected.optional_single() {
+                ui.separator();
+
+                ui.label(format!("{} goals", name.as_str()));
+                for (depth, goal, status) in brain.think.describe() {
+                    ui.label(format!("{}{} ({:?})", "  ".repeat(depth), goal, status));
+                }
+            }
         });
     });
 }
+
+// rebuilds the navgraph debug shapes whenever the navgraph or debug state changes
+pub fn navgraph_debug(
+    mut commands: Commands,
+    debug_state: Res<DebugState>,
+    navgraph: Option<Res<NavGraph>>,
+    debug: Query<Entity, With<NavGraphDebug>>,
+) {
+    let navgraph_changed = navgraph
+        .as_ref()
+        .map(|navgraph| navgraph.is_changed())
+        .unwrap_or_default();
+    if !debug_state.is_changed() && !navgraph_changed {
+        return;
+    }
+
+    for entity in debug.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let navgraph = match navgraph {
+        Some(navgraph) if debug_state.enabled && debug_state.show_navgraph => navgraph,
+        _ => return,
+    };
+
+    let mut edges = GeometryBuilder::new();
+    let mut door_edges = GeometryBuilder::new();
+    for edge in navgraph.unique_edges() {
+        let line = shapes::Line(
+            navgraph.node(edge.from).position,
+            navgraph.node(edge.to).position,
+        );
+
+        if edge.flags.contains(EdgeFlags::DOOR) {
+            door_edges = door_edges.add(&line);
+        } else {
+            edges = edges.add(&line);
+        }
+    }
+
+    let mut nodes = GeometryBuilder::new();
+    for node in navgraph.nodes() {
+        nodes = nodes.add(&shapes::Circle {
+            radius: 0.25,
+            center: node.position,
+        });
+    }
+
+    let transform = Transform::from_translation(Vec3::new(0.0, 0.0, NAVGRAPH_DEBUG_SORT));
+
+    commands
+        .spawn_bundle(edges.build(
+            DrawMode::Stroke(StrokeMode::new(Color::DARK_GRAY, 0.1)),
+            transform,
+        ))
+        .insert(NavGraphDebug)
+        .insert(Name::new("NavGraph Edges"));
+
+    commands
+        .spawn_bundle(door_edges.build(
+            DrawMode::Stroke(StrokeMode::new(Color::SILVER, 0.1)),
+            transform,
+        ))
+        .insert(NavGraphDebug)
+        .insert(Name::new("NavGraph Door Edges"));
+
+    commands
+        .spawn_bundle(nodes.build(DrawMode::Fill(FillMode::color(Color::DARK_GRAY)), transform))
+        .insert(NavGraphDebug)
+        .insert(Name::new("NavGraph Nodes"));
+}
+
+// middle click searches from the selected bot (or the previous target) to the cursor
+pub fn search_debug(
+    windows: Res<Windows>,
+    buttons: Res<Input<MouseButton>>,
+    debug_state: Res<DebugState>,
+    mut search: ResMut<SearchDebugState>,
+    navgraph: Option<Res<NavGraph>>,
+    camera: Query<CameraQuery, With<MainCamera>>,
+    bots: Query<&Transform, Or<(With<SelectedBot>, With<PossessedBot>)>>,
+) {
+    let navgraph = match navgraph {
+        Some(navgraph) => navgraph,
+        None => return,
+    };
+
+    // node indices are only valid for the graph they were searched on
+    if navgraph.is_changed() {
+        search.result = None;
+    }
+
+    if !debug_state.enabled {
+        return;
+    }
+
+    if buttons.just_released(MouseButton::Middle) {
+        if let Ok(camera) = camera.get_single() {
+            let window = windows.get_primary().unwrap();
+            if let Some(mouse_position) =
+                get_mouse_position((camera.camera, camera.transform), window)
+            {
+                let source = bots
+                    .iter()
+                    .next()
+                    .map(|transform| transform.translation.truncate())
+                    .or_else(|| search.endpoints.map(|(_, target)| target));
+
+                search.endpoints = source.map(|source| (source, mouse_position));
+                search.result = None;
+            }
+        }
+    }
+
+    if search.result.is_some() {
+        return;
+    }
+
+    if let Some((source, target)) = search.endpoints {
+        if let (Some(source), Some(target)) =
+            (navgraph.closest_node(source), navgraph.closest_node(target))
+        {
+            let result = crate::game::search::search(&navgraph, source, target, search.algorithm);
+            info!(
+                "{} search {:?}: {} nodes, cost {:.1}, {} visited",
+                result.algorithm.get_name(),
+                result.status,
+                result.path.len(),
+                result.cost,
+                result.visited.len()
+            );
+
+            search.result = Some(result);
+        }
+    }
+}
+
+// rebuilds the search debug shapes whenever the search changes
+pub fn search_debug_visual(
+    mut commands: Commands,
+    debug_state: Res<DebugState>,
+    search: Res<SearchDebugState>,
+    navgraph: Option<Res<NavGraph>>,
+    debug: Query<Entity, With<SearchDebug>>,
+) {
+    if !debug_state.is_changed() && !search.is_changed() {
+        return;
+    }
+
+    for entity in debug.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let (navgraph, result) = match (navgraph, &search.result) {
+        (Some(navgraph), Some(result)) if debug_state.enabled => (navgraph, result),
+        _ => return,
+    };
+
+    let mut tree = GeometryBuilder::new();
+    for (from, to) in result.tree.iter() {
+        tree = tree.add(&shapes::Line(
+            navgraph.node(*from).position,
+            navgraph.node(*to).position,
+        ));
+    }
+
+    let mut frontier = GeometryBuilder::new();
+    for node in result.frontier.iter() {
+        frontier = frontier.add(&shapes::Circle {
+            radius: 0.4,
+            center: navgraph.node(*node).position,
+        });
+    }
+
+    let mut path = GeometryBuilder::new();
+    for nodes in result.path.windows(2) {
+        path = path.add(&shapes::Line(
+            navgraph.node(nodes[0]).position,
+            navgraph.node(nodes[1]).position,
+        ));
+    }
+
+    let transform = Transform::from_translation(Vec3::new(0.0, 0.0, SEARCH_DEBUG_SORT));
+
+    commands
+        .spawn_bundle(tree.build(
+            DrawMode::Stroke(StrokeMode::new(Color::CYAN, 0.1)),
+            transform,
+        ))
+        .insert(SearchDebug)
+        .insert(Name::new("Search Tree"));
+
+    commands
+        .spawn_bundle(frontier.build(DrawMode::Fill(FillMode::color(Color::ORANGE)), transform))
+        .insert(SearchDebug)
+        .insert(Name::new("Search Frontier"));
+
+    commands
+        .spawn_bundle(path.build(
+            DrawMode::Stroke(StrokeMode::new(Color::LIME_GREEN, 0.3)),
+            transform,
+        ))
+        .insert(SearchDebug)
+        .insert(Name::new("Search Path"));
+}
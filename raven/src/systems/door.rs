@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::components::door::*;
+
+pub fn update(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut doors: Query<(Entity, &mut Door, &mut Visibility)>,
+) {
+    for (entity, mut door, mut visibility) in doors.iter_mut() {
+        door.update(&mut commands, entity, &mut visibility, time.delta_seconds());
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use crate::components::effect::*;
+
+pub fn update(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut effects: Query<(Entity, &mut Effect, &mut Transform, &Children, &Name)>,
+    mut models: Query<&mut DrawMode>,
+) {
+    for (entity, mut effect, mut transform, children, name) in effects.iter_mut() {
+        effect.timer.tick(time.delta());
+
+        if effect.timer.finished() {
+            debug!("despawning effect '{}'", name);
+
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.scale = Vec2::splat(effect.get_scale()).extend(1.0);
+
+        let alpha = effect.get_alpha();
+        for &child in children.iter() {
+            if let Ok(mut draw_mode) = models.get_mut(child) {
+                match &mut *draw_mode {
+                    DrawMode::Fill(fill_mode) => {
+                        fill_mode.color.set_a(alpha);
+                    }
+                    DrawMode::Stroke(stroke_mode) => {
+                        stroke_mode.color.set_a(alpha);
+                    }
+                    DrawMode::Outlined {
+                        fill_mode,
+                        outline_mode,
+                    } => {
+                        fill_mode.color.set_a(alpha);
+                        outline_mode.color.set_a(alpha);
+                    }
+                }
+            }
+        }
+    }
+}
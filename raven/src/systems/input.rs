@@ -5,18 +5,33 @@ use crate::components::bot::*;
 use crate::components::camera::*;
 use crate::components::collision::*;
 use crate::components::inventory::*;
+use crate::components::planner::*;
+use crate::components::steering::*;
 use crate::components::weapon::*;
 use crate::game::weapons::*;
+use crate::resources::path::*;
 use crate::util::*;
 
 pub fn handle_select(
     mut commands: Commands,
     windows: Res<Windows>,
     buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
     camera: Query<CameraQuery, With<MainCamera>>,
     bots: Query<(Entity, BotQuery, BoundsQuery, &Children)>,
     selected: Query<(Entity, BotQuery, &Children), With<SelectedBot>>,
-    mut possessed: Query<(Entity, AgentQueryMut, &Name), With<PossessedBot>>,
+    mut manager: ResMut<PathManager>,
+    mut possessed: Query<
+        (
+            Entity,
+            &Agent,
+            &mut PathPlanner,
+            Option<&mut FollowPath>,
+            &Transform,
+            &Name,
+        ),
+        With<PossessedBot>,
+    >,
     mut selected_visibility: Query<
         &mut Visibility,
         (With<SelectedBotVisual>, Without<PossessedBotVisual>),
@@ -43,7 +58,9 @@ pub fn handle_select(
                         bot.name.as_str(),
                         children,
                         selected.optional_single(),
-                        possessed.optional_single().map(|(entity, _, _)| entity),
+                        possessed
+                            .optional_single()
+                            .map(|(entity, _, _, _, _, _)| entity),
                         &mut selected_visibility,
                         &mut possessed_visibility,
                     );
@@ -52,16 +69,58 @@ pub fn handle_select(
             }
 
             // ground clicked
-            if let Some((entity, mut agent, name)) = possessed.optional_single_mut() {
-                info!("[{}]: moving to {} ...", name.as_ref(), mouse_position);
+            if let Some((entity, agent, mut planner, follow_path, transform, name)) =
+                possessed.optional_single_mut()
+            {
+                if keys.pressed(KeyCode::LShift) {
+                    // shift click adds a waypoint to the current path
+                    info!(
+                        "[{}]: adding waypoint {} ...",
+                        name.as_str(),
+                        mouse_position
+                    );
 
-                agent.agent.arrive_on(&mut commands, entity);
-                agent.steering.target = mouse_position;
+                    planner.cancel(&mut manager, entity);
+                    match follow_path {
+                        Some(mut follow_path) => follow_path.path.push(mouse_position),
+                        None => agent.follow_path_on(
+                            &mut commands,
+                            entity,
+                            FollowPath::new(vec![mouse_position], false),
+                        ),
+                    }
+                } else {
+                    planner.request_path_to_position(
+                        &mut manager,
+                        entity,
+                        transform.translation.truncate(),
+                        mouse_position,
+                        name.as_str(),
+                    );
+                }
             }
         }
     }
 }
 
+pub fn toggle_path_loop(
+    keys: Res<Input<KeyCode>>,
+    mut possessed: Query<(&mut FollowPath, &Name), With<PossessedBot>>,
+) {
+    if keys.just_pressed(KeyCode::L) {
+        if let Some((mut follow_path, name)) = possessed.optional_single_mut() {
+            follow_path.path.looped = !follow_path.path.looped;
+            info!(
+                "[{}]: path looped: {}",
+                name.as_str(),
+                follow_path.path.looped
+            );
+        } else {
+            info!("no possessed bot path to loop");
+        }
+    }
+}
+
 pub fn deselect_bot(
     mut commands: Commands,
     keys: Res<Input<KeyCode>>,
@@ -101,7 +160,10 @@ pub fn deselect_bot(
 
 pub fn select_weapon(
     keys: Res<Input<KeyCode>>,
-    mut possessed: Query<(&mut EquippedWeapon, &Inventory, &Name), With<PossessedBot>>,
+    mut possessed: Query<
+        (&mut EquippedWeapon, &mut WeaponSelector, &Inventory, &Name),
+        With<PossessedBot>,
+    >,
 ) {
     let mut weapon = None;
     if keys.just_pressed(KeyCode::Key1) {
@@ -115,7 +177,15 @@ pub fn select_weapon(
     }
 
     if let Some(weapon) = weapon {
-        if let Some((mut equipped_weapon, inventory, name)) = possessed.optional_single_mut() {
+        if let Some((mut equipped_weapon, mut selector, inventory, name)) =
+            possessed.optional_single_mut()
+        {
+            // picking by hand turns off auto select
+            if selector.auto_select {
+                info!("[{}]: weapon auto select off", name.as_str());
+                selector.auto_select = false;
+            }
+
             equipped_weapon.select(inventory, weapon, name.as_str());
         } else {
             info!("no bot possessed for select weapon '{}'", weapon.get_name());
@@ -123,6 +193,24 @@ pub fn select_weapon(
     }
 }
 
+pub fn toggle_weapon_auto_select(
+    keys: Res<Input<KeyCode>>,
+    mut possessed: Query<(&mut WeaponSelector, &Name), With<PossessedBot>>,
+) {
+    if keys.just_pressed(KeyCode::A) {
+        if let Some((mut selector, name)) = possessed.optional_single_mut() {
+            selector.auto_select = !selector.auto_select;
+            info!(
+                "[{}]: weapon auto select: {}",
+                name.as_str(),
+                selector.auto_select
+            );
+        } else {
+            info!("no bot possessed for weapon auto select");
+        }
+    }
+}
+
 pub fn fire_weapon(
     mut commands: Commands,
     windows: Res<Windows>,
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::components::bot::*;
+use crate::components::collision::*;
+use crate::components::memory::*;
+use crate::components::physics::*;
+use crate::components::targeting::*;
+use crate::components::trigger::*;
+use crate::components::wall::*;
+use crate::util::*;
+
+pub fn update_vision(
+    time: Res<Time>,
+    mut bots: Query<(Entity, &mut SensoryMemory, &Bot, &Physical, &Transform)>,
+    others: Query<(Entity, &Bot, &Transform)>,
+    walls: Query<WallQuery>,
+) {
+    let now = time.seconds_since_startup() as f32;
+
+    let walls = walls
+        .iter()
+        .map(|wall| {
+            let position = wall.transform.translation.truncate();
+            (wall.wall.from(position), wall.wall.to(position))
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, mut memory, bot, physical, transform) in bots.iter_mut() {
+        if !bot.is_alive() {
+            continue;
+        }
+
+        memory.update_facing(physical.velocity);
+
+        let position = transform.translation.truncate();
+
+        // dead bots are forgotten rather than left looking visible
+        for (other, _, _) in others
+            .iter()
+            .filter(|(_, other_bot, _)| !other_bot.is_alive())
+        {
+            memory.remove_bot(other);
+        }
+
+        let opponents = others
+            .iter()
+            .filter(|(other, other_bot, _)| *other != entity && other_bot.is_alive())
+            .map(|(other, _, other_transform)| {
+                let other_position = other_transform.translation.truncate();
+                (
+                    other,
+                    other_position,
+                    has_line_of_sight(position, other_position, &walls),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        memory.update_vision(now, position, &opponents);
+    }
+}
+
+// bots within range of a sound remember where the bot that made it is
+pub fn hear_sounds(
+    time: Res<Time>,
+    triggers: Query<(&Trigger, &Transform, &Bounds)>,
+    mut bots: Query<(Entity, &mut SensoryMemory, &Bot, &Transform, &Bounds, &Name)>,
+    sources: Query<(&Bot, &Transform)>,
+) {
+    let now = time.seconds_since_startup() as f32;
+
+    for (trigger, transform, bounds) in triggers.iter() {
+        let owner = match trigger {
+            Trigger::Sound(owner, _) => *owner,
+            _ => continue,
+        };
+
+        // sounds from bots that have since died are ignored
+        let owner_position = match sources.get(owner) {
+            Ok((owner_bot, owner_transform)) if owner_bot.is_alive() => {
+                owner_transform.translation.truncate()
+            }
+            _ => continue,
+        };
+
+        let position = transform.translation.truncate();
+
+        for (entity, mut memory, bot, bot_transform, bot_bounds, name) in bots.iter_mut() {
+            if entity == owner || !bot.is_alive() {
+                continue;
+            }
+
+            let bot_position = bot_transform.translation.truncate();
+            if !bounds.bounds_intersects(position, bot_bounds, bot_position) {
+                continue;
+            }
+
+            debug!("[{}]: heard {:?}", name.as_str(), owner);
+
+            memory.update_with_sound(now, owner, owner_position);
+        }
+    }
+}
+
+pub fn update_targeting(
+    time: Res<Time>,
+    mut bots: Query<(&mut Targeting, &SensoryMemory, &Transform, &Name)>,
+) {
+    let now = time.seconds_since_startup() as f32;
+
+    for (mut targeting, memory, transform, name) in bots.iter_mut() {
+        targeting.update(now, memory, transform.translation.truncate(), name.as_str());
+    }
+}
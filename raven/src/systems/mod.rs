@@ -1,8 +1,13 @@
 pub mod bot;
+pub mod brain;
 pub mod corpse;
 pub mod debug;
+pub mod door;
+pub mod effect;
 pub mod input;
+pub mod memory;
 pub mod physics;
+pub mod planner;
 pub mod projectile;
 pub mod steering;
 pub mod trigger;
@@ -20,6 +25,12 @@ pub enum Systems {
 
     Input,
 
+    Senses,
+    Targeting,
+    Brain,
+
+    PathPlanning,
+
     // steering
     Steering,
     SteeringUpdatePhysics,
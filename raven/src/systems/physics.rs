@@ -4,10 +4,11 @@ use crate::components::physics::*;
 
 pub fn update(_time: Res<Time>, mut physicals: Query<PhysicalQueryUpdateMut>) {
     for mut physical in physicals.iter_mut() {
-        physical.physical.update(
-            &physical.transform,
-            /*time.delta_seconds()*/
-        );
+        physical.physical.integrate(/*time.delta_seconds()*/);
+
+        physical
+            .cache
+            .update(&physical.physical, &physical.transform);
     }
 }
 
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::components::agent::*;
+use crate::components::bot::*;
+use crate::components::brain::*;
+use crate::components::collision::*;
+use crate::components::planner::*;
+use crate::components::wall::*;
+use crate::events::path::*;
+use crate::game::PATH_SEARCH_CYCLES_PER_UPDATE;
+use crate::resources::navgraph::*;
+use crate::resources::path::*;
+
+pub fn update(
+    mut manager: ResMut<PathManager>,
+    navgraph: Option<Res<NavGraph>>,
+    walls: Query<WallQuery>,
+    mut ready_events: EventWriter<PathReadyEvent>,
+    mut no_path_events: EventWriter<NoPathAvailableEvent>,
+) {
+    if let Some(navgraph) = &navgraph {
+        if navgraph.is_changed() {
+            manager.invalidate();
+        }
+    }
+
+    if manager.is_idle() {
+        return;
+    }
+
+    // fall back to searching a grid around the walls
+    let navgraph_empty = navgraph
+        .as_ref()
+        .map(|navgraph| navgraph.is_empty())
+        .unwrap_or(true);
+    if navgraph_empty && !manager.has_grid() {
+        let walls = walls
+            .iter()
+            .map(|wall| {
+                let position = wall.transform.translation.truncate();
+                (wall.wall.from(position), wall.wall.to(position))
+            })
+            .collect::<Vec<_>>();
+
+        manager.build_grid(&walls);
+    }
+
+    for (entity, path) in manager.update(navgraph.as_deref(), PATH_SEARCH_CYCLES_PER_UPDATE) {
+        match path {
+            Some(path) => ready_events.send(PathReadyEvent { entity, path }),
+            None => no_path_events.send(NoPathAvailableEvent { entity }),
+        }
+    }
+}
+
+pub fn path_ready(
+    mut commands: Commands,
+    mut events: EventReader<PathReadyEvent>,
+    mut planners: Query<(
+        &Agent,
+        &mut PathPlanner,
+        Option<&mut Brain>,
+        Option<&PossessedBot>,
+        BoundsQuery,
+        &Name,
+    )>,
+    walls: Query<WallQuery>,
+) {
+    for event in events.iter() {
+        if let Ok((agent, mut planner, brain, possessed, bounds, name)) =
+            planners.get_mut(event.entity)
+        {
+            let walls = walls
+                .iter()
+                .map(|wall| {
+                    let position = wall.transform.translation.truncate();
+                    (wall.wall.from(position), wall.wall.to(position))
+                })
+                .collect::<Vec<_>>();
+            let radius = bounds.bounds.radius();
+
+            let follow_path = planner.path_ready(
+                bounds.transform.translation.truncate(),
+                event.path.clone(),
+                |from, to| can_walk_between(from, to, radius, &walls),
+                name.as_str(),
+            );
+
+            // the brain drives bots that aren't possessed
+            match brain {
+                Some(mut brain) if possessed.is_none() => {
+                    brain.path_ready(follow_path.path.waypoints)
+                }
+                _ => agent.follow_path_on(&mut commands, event.entity, follow_path),
+            }
+        }
+    }
+}
+
+pub fn no_path_available(
+    mut events: EventReader<NoPathAvailableEvent>,
+    mut planners: Query<(
+        &mut PathPlanner,
+        Option<&mut Brain>,
+        Option<&PossessedBot>,
+        &Name,
+    )>,
+) {
+    for event in events.iter() {
+        if let Ok((mut planner, brain, possessed, name)) = planners.get_mut(event.entity) {
+            planner.no_path_available(name.as_str());
+
+            if let Some(mut brain) = brain {
+                if possessed.is_none() {
+                    brain.no_path_available();
+                }
+            }
+        }
+    }
+}
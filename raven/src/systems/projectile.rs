@@ -1,17 +1,19 @@
 use bevy::prelude::*;
 
+use crate::bundles::effect::*;
 use crate::components::bot::*;
 use crate::components::collision::*;
 use crate::components::inventory::*;
 use crate::components::physics::*;
 use crate::components::projectile::*;
 use crate::components::wall::*;
+use crate::util::*;
 use crate::ORTHO_SIZE;
 
 pub fn check_bounds(
     mut commands: Commands,
     windows: Res<Windows>,
-    projectiles: Query<(Entity, PhysicalQuery, &Bounds, &Name), With<Projectile>>,
+    projectiles: Query<(Entity, &Projectile, PhysicalQuery, &Bounds, &Name)>,
 ) {
     let window = windows.get_primary().unwrap();
     let aspect_ratio = window.width() / window.height();
@@ -19,15 +21,19 @@ pub fn check_bounds(
     let max_x = ORTHO_SIZE * aspect_ratio;
     let max_y = ORTHO_SIZE;
 
-    for (entity, physical, bounds, name) in projectiles.iter() {
+    for (entity, projectile, physical, bounds, name) in projectiles.iter() {
+        if projectile.is_hit_scan() {
+            continue;
+        }
+
         let projectile_min_x =
-            physical.physical.cache.future_position.x + bounds.center().x - bounds.width();
+            physical.cache.future_position.x + bounds.center().x - bounds.width();
         let projectile_min_y =
-            physical.physical.cache.future_position.y + bounds.center().y - bounds.height();
+            physical.cache.future_position.y + bounds.center().y - bounds.height();
         let projectile_max_x =
-            physical.physical.cache.future_position.x + bounds.center().x + bounds.width();
+            physical.cache.future_position.x + bounds.center().x + bounds.width();
         let projectile_max_y =
-            physical.physical.cache.future_position.y + bounds.center().y + bounds.height();
+            physical.cache.future_position.y + bounds.center().y + bounds.height();
 
         if projectile_min_x < -max_x
             || projectile_max_x > max_x
@@ -49,6 +55,24 @@ pub fn check_bounds(
     }
 }
 
+pub fn check_range(
+    mut commands: Commands,
+    projectiles: Query<(Entity, &Projectile, &ProjectileOrigin, PhysicalQuery, &Name)>,
+) {
+    for (entity, projectile, origin, physical, name) in projectiles.iter() {
+        if projectile.is_hit_scan() {
+            continue;
+        }
+
+        if let Some(range) = projectile.get_range() {
+            if physical.cache.future_position.distance(origin.0) > range {
+                info!("projectile '{}' is out of range", name);
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
 pub fn check_wall_collision(
     mut commands: Commands,
     projectiles: Query<(Entity, &Projectile, PhysicalQuery, &Bounds, &Name)>,
@@ -56,10 +80,14 @@ pub fn check_wall_collision(
     mut bots: Query<(Entity, BotQueryMut, &mut Inventory, PhysicalQuery, &Bounds)>,
 ) {
     for (entity, projectile, physical, bounds, name) in projectiles.iter() {
+        if projectile.is_hit_scan() {
+            continue;
+        }
+
         // TODO: need to account for bounds width / height
 
-        let projectile_position = physical.physical.cache.position + bounds.center();
-        let heading = physical.physical.cache.heading * physical.physical.cache.max_distance;
+        let projectile_position = physical.cache.position + bounds.center();
+        let heading = physical.cache.heading * physical.cache.max_distance;
 
         for wall in walls.iter() {
             let wall_position = wall.transform.translation.truncate();
@@ -76,7 +104,7 @@ pub fn check_wall_collision(
                 line_intersection(wall_from, wall_to, projectile_position, heading)
             {
                 info!("projectile '{}' hit a wall at {}", name, hit);
-                projectile.on_impact(&mut commands, entity, hit, bots.iter_mut());
+                projectile.on_impact(&mut commands, hit, None, bots.iter_mut());
 
                 commands.entity(entity).despawn_recursive();
                 break;
@@ -92,16 +120,21 @@ pub fn check_bot_collision(
     mut bots: Query<(Entity, BotQueryMut, &mut Inventory, PhysicalQuery, &Bounds)>,
 ) {
     for (entity, projectile, physical, bounds, name) in projectiles.iter() {
+        if projectile.is_hit_scan() {
+            continue;
+        }
+
         // TODO: need to account for bounds width / height
 
-        let projectile_position = physical.physical.cache.position + bounds.center();
+        let projectile_position = physical.cache.position + bounds.center();
 
         for (bot_entity, mut bot, mut inventory, bot_physical, bot_bounds) in bots.iter_mut() {
-            if bot_entity == projectile.get_owner() {
+            // dead bots are out of play
+            if bot_entity == projectile.get_owner() || !bot.bot.is_alive() {
                 continue;
             }
 
-            let bot_position = bot_physical.physical.cache.position + bot_bounds.center();
+            let bot_position = bot_physical.cache.position + bot_bounds.center();
 
             let contains = bot_bounds.contains(bot_position, projectile_position);
             if contains {
@@ -112,8 +145,8 @@ pub fn check_bot_collision(
             if let Some(hit) = bot_bounds.ray_intersects(
                 bot_position,
                 projectile_position,
-                physical.physical.cache.heading,
-                physical.physical.cache.max_distance,
+                physical.cache.heading,
+                physical.cache.max_distance,
             ) {
                 info!("projectile '{}' hit bot '{}' at {}!", name, bot.name, hit);
                 bot.bot.damage(
@@ -125,13 +158,67 @@ pub fn check_bot_collision(
                     projectile.get_damage(),
                 );
 
-                projectile.on_impact(&mut commands, entity, hit, bots.iter_mut());
+                projectile.on_impact(&mut commands, hit, Some(bot_entity), bots.iter_mut());
 
-                if !matches!(projectile, Projectile::Slug(_)) {
-                    commands.entity(entity).despawn_recursive();
-                }
+                commands.entity(entity).despawn_recursive();
                 break;
             }
         }
     }
 }
+
+// hit-scan projectiles are traced along their ray on their first update
+// they pass through bots and stop at the first wall
+pub fn check_hit_scan(
+    mut commands: Commands,
+    projectiles: Query<(Entity, &Projectile, PhysicalQuery, &Name)>,
+    walls: Query<WallQuery>,
+    mut bots: Query<(Entity, BotQueryMut, &mut Inventory, PhysicalQuery, &Bounds)>,
+) {
+    for (entity, projectile, physical, name) in projectiles.iter() {
+        if !projectile.is_hit_scan() {
+            continue;
+        }
+
+        let origin = physical.cache.position;
+        let direction = physical.cache.heading;
+
+        let mut distance = projectile.get_range().unwrap_or_default();
+        for wall in walls.iter() {
+            let wall_position = wall.transform.translation.truncate();
+            let wall_from = wall.wall.from(wall_position);
+            let wall_to = wall.wall.to(wall_position);
+
+            if let Some((_, hit)) =
+                line_intersection(wall_from, wall_to, origin, origin + direction * distance)
+            {
+                distance = origin.distance(hit);
+            }
+        }
+
+        for (bot_entity, mut bot, mut inventory, bot_physical, bot_bounds) in bots.iter_mut() {
+            if bot_entity == projectile.get_owner() || !bot.bot.is_alive() {
+                continue;
+            }
+
+            let bot_position = bot_physical.cache.position + bot_bounds.center();
+
+            if let Some(hit) = bot_bounds.ray_intersects(bot_position, origin, direction, distance)
+            {
+                info!("projectile '{}' hit bot '{}' at {}!", name, bot.name, hit);
+                bot.bot.damage(
+                    &mut commands,
+                    bot_entity,
+                    bot_physical.transform,
+                    &mut inventory,
+                    bot.name,
+                    projectile.get_damage(),
+                );
+            }
+        }
+
+        EffectBundle::spawn_trace(&mut commands, origin, origin + direction * distance);
+
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -2,12 +2,14 @@ use bevy::prelude::*;
 
 use crate::components::physics::*;
 use crate::components::steering::*;
-use crate::game::DEBUG_SORT;
+use crate::game::{DEBUG_SORT, FOLLOW_PATH_WEIGHT, PATH_WAYPOINT_SEEK_DISTANCE};
 use crate::util::*;
 
 pub fn update(mut steering_behaviors: Query<SteeringQueryMut>) {
     for mut steering in steering_behaviors.iter_mut() {
-        steering.steering.update_physical(&mut steering.physical);
+        // steering forces are treated as accelerations
+        let force = steering.steering.take_force() * steering.physical.mass;
+        steering.physical.apply_force(force);
     }
 }
 
@@ -29,7 +31,9 @@ pub fn update_debug(
 
 pub fn update_seek(mut seeking: Query<(SeekQueryMut, PhysicalQuery)>) {
     for (mut steering, physical) in seeking.iter_mut() {
-        let force = steering.seek.force(&steering.steering, &physical);
+        let force = steering
+            .seek
+            .force(&steering.steering, physical.transform, physical.physical);
         steering
             .steering
             .accumulate_force(physical.physical, force, 1.0);
@@ -38,9 +42,32 @@ pub fn update_seek(mut seeking: Query<(SeekQueryMut, PhysicalQuery)>) {
 
 pub fn update_arrive(mut arriving: Query<(ArriveQueryMut, PhysicalQuery)>) {
     for (mut steering, physical) in arriving.iter_mut() {
-        let force = steering.arrive.force(&steering.steering, &physical);
+        let force =
+            steering
+                .arrive
+                .force(&steering.steering, physical.transform, physical.physical);
         steering
             .steering
             .accumulate_force(physical.physical, force, 1.0);
     }
 }
+
+pub fn update_follow_path(mut following: Query<(FollowPathQueryMut, PhysicalQuery)>) {
+    for (mut steering, physical) in following.iter_mut() {
+        steering.follow_path.path.update(
+            physical.transform.translation.truncate(),
+            PATH_WAYPOINT_SEEK_DISTANCE,
+        );
+
+        if let Some(waypoint) = steering.follow_path.path.current_waypoint() {
+            steering.steering.target = waypoint;
+        }
+
+        let force = steering
+            .follow_path
+            .force(physical.transform, physical.physical);
+        steering
+            .steering
+            .accumulate_force(physical.physical, force, FOLLOW_PATH_WEIGHT);
+    }
+}
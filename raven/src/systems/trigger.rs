@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use crate::components::bot::*;
 use crate::components::collision::*;
+use crate::components::door::*;
 use crate::components::inventory::*;
 use crate::components::physics::*;
 use crate::components::trigger::*;
@@ -17,23 +18,23 @@ pub fn update(
 }
 
 pub fn check_bot_collision(
+    mut commands: Commands,
     mut triggers: Query<(&mut Trigger, &Transform, &Bounds)>,
-    mut bots: Query<(
-        Entity,
-        BotQueryMut,
-        PhysicalQuery,
-        &mut Inventory,
-        &Bounds,
-        &Name,
-    )>,
+    mut bots: Query<(BotQueryMut, PhysicalQuery, &mut Inventory, &Bounds, &Name)>,
+    mut doors: Query<(&mut Door, &mut Visibility)>,
 ) {
     for (mut trigger, transform, bounds) in triggers.iter_mut() {
+        // sounds are heard by the sensory memory system
+        if let Trigger::Sound(_, _) = *trigger {
+            continue;
+        }
+
         // TODO: need to account for bounds width / height
 
         let position = transform.translation.truncate() + bounds.center();
 
-        for (entity, mut bot, bot_physical, mut inventory, bot_bounds, name) in bots.iter_mut() {
-            let bot_position = bot_physical.physical.cache.position + bot_bounds.center();
+        for (mut bot, bot_physical, mut inventory, bot_bounds, name) in bots.iter_mut() {
+            let bot_position = bot_physical.cache.position + bot_bounds.center();
 
             let contains = bot_bounds.contains(bot_position, position);
             if contains {
@@ -45,12 +46,21 @@ pub fn check_bot_collision(
                 .ray_intersects(
                     position,
                     bot_position,
-                    bot_physical.physical.cache.heading,
-                    bot_physical.physical.cache.max_distance,
+                    bot_physical.cache.heading,
+                    bot_physical.cache.max_distance,
                 )
                 .is_some()
             {
-                trigger.trigger(entity, &mut bot.bot, &mut inventory, name);
+                trigger.trigger(&mut bot.bot, &mut inventory, name);
+
+                if let Trigger::DoorSwitch(door_entity) = *trigger {
+                    if bot.bot.is_alive() {
+                        if let Ok((mut door, mut visibility)) = doors.get_mut(door_entity) {
+                            door.open(&mut commands, door_entity, &mut visibility);
+                        }
+                    }
+                }
+
                 break;
             }
         }
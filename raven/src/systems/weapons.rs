@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::components::bot::*;
+use crate::components::inventory::*;
 use crate::components::weapon::*;
 
 pub fn update(time: Res<Time>, mut weapons: Query<&mut EquippedWeapon>) {
@@ -7,3 +9,36 @@ pub fn update(time: Res<Time>, mut weapons: Query<&mut EquippedWeapon>) {
         weapon.cooldown.tick(time.delta_seconds());
     }
 }
+
+// picks the best weapon for the nearest enemy
+pub fn auto_select(
+    time: Res<Time>,
+    mut bots: Query<(
+        Entity,
+        &mut WeaponSelector,
+        &mut EquippedWeapon,
+        &Inventory,
+        &Transform,
+        &Name,
+    )>,
+    others: Query<(Entity, &Bot, &Transform)>,
+) {
+    for (entity, mut selector, mut weapon, inventory, transform, name) in bots.iter_mut() {
+        if !selector.tick(time.delta_seconds()) || !selector.auto_select {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let nearest = others
+            .iter()
+            .filter(|(other, other_bot, _)| *other != entity && other_bot.is_alive())
+            .map(|(_, _, other_transform)| {
+                position.distance(other_transform.translation.truncate())
+            })
+            .min_by(|a, b| a.total_cmp(b));
+
+        if let Some(distance) = nearest {
+            selector.select_best(&mut weapon, inventory, distance, name.as_str());
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use bevy::ecs::query::{QueryItem, QuerySingleError, ROQueryItem, WorldQuery};
 use bevy::prelude::*;
 
+pub use game_ai_core::util::*;
+
 // https://bevy-cheatbook.github.io/cookbook/cursor2world.html#2d-games
 pub fn get_mouse_position(camera: (&Camera, &Transform), window: &Window) -> Option<Vec2> {
     if let Some(screen_position) = window.cursor_position() {
@@ -14,86 +15,9 @@ pub fn get_mouse_position(camera: (&Camera, &Transform), window: &Window) -> Opt
     }
 }
 
-pub trait OptionalSingle<'s, Q>
-where
-    Q: WorldQuery,
-{
-    fn optional_single(&self) -> Option<ROQueryItem<'_, Q>>;
-    fn optional_single_mut(&mut self) -> Option<QueryItem<'_, Q>>;
-}
-
-impl<'w, 's, Q, F> OptionalSingle<'s, Q> for Query<'w, 's, Q, F>
-where
-    Q: WorldQuery,
-    F: WorldQuery,
-{
-    fn optional_single(&self) -> Option<ROQueryItem<'_, Q>> {
-        match self.get_single() {
-            Ok(item) => Some(item),
-            Err(QuerySingleError::NoEntities(_)) => None,
-            Err(QuerySingleError::MultipleEntities(_)) => {
-                panic!("multiple items from optional single query")
-            }
-        }
-    }
-
-    fn optional_single_mut(&mut self) -> Option<QueryItem<'_, Q>> {
-        match self.get_single_mut() {
-            Ok(item) => Some(item),
-            Err(QuerySingleError::NoEntities(_)) => None,
-            Err(QuerySingleError::MultipleEntities(_)) => {
-                panic!("multiple items from optional single query")
-            }
-        }
-    }
-}
-
-pub trait Vec2Utils {
-    fn sign(&self, v2: Vec2) -> f32;
-
-    fn rotate_origin(&self, angle: f32) -> Vec2;
-}
-
-impl Vec2Utils for Vec2 {
-    fn sign(&self, v2: Vec2) -> f32 {
-        if self.y * v2.x > self.x * v2.y {
-            -1.0
-        } else {
-            1.0
-        }
-    }
-
-    fn rotate_origin(&self, angle: f32) -> Vec2 {
-        let v = self.extend(0.0);
-        (Quat::from_rotation_z(angle) * v).truncate()
-    }
-}
-
-pub trait TransformUtils {
-    fn set_world_translation(&mut self, global_transform: &GlobalTransform, world_position: Vec3);
-}
-
-impl TransformUtils for Transform {
-    fn set_world_translation(&mut self, global_transform: &GlobalTransform, world_position: Vec3) {
-        /*println!(
-            "before global: {}, local: {} to world_position: {}",
-            global_transform.translation, self.translation, world_position
-        );*/
-
-        let parent_position = global_transform.translation() - self.translation;
-        //println!("parent: {}", parent_position);
-
-        let local_position = world_position - parent_position;
-        if self.translation.distance_squared(local_position) > f32::EPSILON * f32::EPSILON {
-            self.translation = local_position;
-        }
-        //println!("after local: {}", self.translation);
-    }
-}
-
-#[derive(WorldQuery)]
-#[world_query(mutable, derive(Debug))]
-pub struct TransformQueryMut {
-    pub global_transform: &'static GlobalTransform,
-    pub transform: &'static mut Transform,
+// true if the line between the points doesn't cross any of the walls
+pub fn has_line_of_sight(from: Vec2, to: Vec2, walls: &[(Vec2, Vec2)]) -> bool {
+    walls
+        .iter()
+        .all(|(a, b)| line_intersection(from, to, *a, *b).is_none())
 }
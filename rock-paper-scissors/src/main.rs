@@ -1,15 +1,25 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use game_ai_core::args::parse_seed;
 use mlua::prelude::*;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 const POSSIBLE_PLAY_STRINGS: [&str; 3] = ["scissors", "rock", "paper"];
 const SCORE_TABLE: [[isize; 3]; 3] = [[0, -1, 1], [1, 0, -1], [-1, 1, 0]];
 
 fn main() -> anyhow::Result<()> {
+    let seed = parse_seed().unwrap_or_else(|| rand::thread_rng().gen());
+    println!("random seed: {}", seed);
+
+    let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(seed)));
+
     let lua = Lua::new();
 
     let globals = lua.globals();
 
-    let get_ai_move = lua.create_function(|_, ()| Ok(get_ai_move()))?;
+    let get_ai_move = lua.create_function(move |_, ()| Ok(get_ai_move(&mut *rng.borrow_mut())))?;
     globals.set("cpp_GetAIMove", get_ai_move)?;
 
     let evaluate_the_guesses = lua.create_function(
@@ -30,9 +40,7 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn get_ai_move() -> &'static str {
-    let mut rng = rand::thread_rng();
-
+fn get_ai_move(rng: &mut impl Rng) -> &'static str {
     POSSIBLE_PLAY_STRINGS[rng.gen_range(0..=2)]
 }
 
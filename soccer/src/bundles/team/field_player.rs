@@ -5,7 +5,7 @@ use crate::components::obstacle::*;
 use crate::components::physics::*;
 use crate::components::steering::*;
 use crate::components::team::*;
-use crate::game::PLAYER_RADIUS;
+use crate::game::{PLAYER_HEADING_SMOOTHING_SAMPLES, PLAYER_RADIUS};
 use crate::resources::pitch::*;
 use crate::resources::ui::*;
 use crate::resources::*;
@@ -44,6 +44,7 @@ where
         pitch: &Pitch,
         role: FieldPlayerRole,
         number: usize,
+        formation_slot: usize,
         home_region: usize,
     ) -> Entity
     where
@@ -62,6 +63,7 @@ where
         let mut bundle = commands.spawn_bundle(FieldPlayerBundle {
             player: SoccerPlayer {
                 number,
+                formation_slot,
                 home_region,
                 default_region: home_region,
             },
@@ -72,6 +74,8 @@ where
                 max_speed: params.player_max_speed_without_ball,
                 max_force: params.player_max_force,
                 max_turn_rate: params.player_max_turn_rate,
+                limit_turn_rate: true,
+                heading_smoothing_samples: PLAYER_HEADING_SMOOTHING_SAMPLES,
                 ..Default::default()
             },
             bounds: BoundingCircle::from_radius(PLAYER_RADIUS),
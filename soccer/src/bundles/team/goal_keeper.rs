@@ -5,7 +5,7 @@ use crate::components::obstacle::*;
 use crate::components::physics::*;
 use crate::components::steering::*;
 use crate::components::team::*;
-use crate::game::PLAYER_RADIUS;
+use crate::game::{PLAYER_HEADING_SMOOTHING_SAMPLES, PLAYER_RADIUS};
 use crate::resources::pitch::*;
 use crate::resources::ui::*;
 use crate::resources::*;
@@ -58,6 +58,8 @@ where
         let mut bundle = commands.spawn_bundle(GoalKeeperBundle {
             player: SoccerPlayer {
                 number,
+                // goal keepers aren't field players
+                formation_slot: 0,
                 home_region,
                 default_region: home_region,
             },
@@ -67,6 +69,8 @@ where
                 max_speed: params.player_max_speed_without_ball,
                 max_force: params.player_max_force,
                 max_turn_rate: params.player_max_turn_rate,
+                limit_turn_rate: true,
+                heading_smoothing_samples: PLAYER_HEADING_SMOOTHING_SAMPLES,
                 ..Default::default()
             },
             bounds: BoundingCircle::from_radius(PLAYER_RADIUS),
@@ -5,7 +5,7 @@ use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
 
 use crate::components::team::*;
-use crate::game::team::*;
+use crate::resources::formation::*;
 use crate::resources::pitch::*;
 use crate::resources::ui::*;
 use crate::resources::*;
@@ -32,11 +32,24 @@ impl<T> SoccerTeamBundle<T>
 where
     T: TeamColorMarker,
 {
-    pub fn spawn(commands: &mut Commands, params: &SimulationParams, fonts: &Fonts, pitch: &Pitch) {
+    pub fn spawn(
+        commands: &mut Commands,
+        params: &SimulationParams,
+        fonts: &Fonts,
+        pitch: &Pitch,
+        formation: &Formation,
+    ) {
         let color = T::default();
         let team_color = color.team_color();
 
-        info!("spawning team {:?}", team_color);
+        info!(
+            "spawning team {:?} of {} in formation '{}'",
+            team_color,
+            formation.team_size(),
+            formation.name
+        );
+
+        let formation = formation.for_team(team_color, pitch);
 
         let support_spots = SupportSpotCalculator::new(team_color, params);
         let debug_support_spots = if params.debug_vis {
@@ -45,8 +58,37 @@ where
             None
         };
 
+        let first_number = team_color.first_number();
+
+        // goal keeper
+        GoalKeeperBundle::<T>::spawn(
+            commands,
+            params,
+            fonts,
+            pitch,
+            first_number,
+            formation.goal_keeper.region(FormationState::KickOff),
+        );
+
+        // players
+        for (slot, player) in formation.field_players.iter().enumerate() {
+            FieldPlayerBundle::<T>::spawn(
+                commands,
+                params,
+                fonts,
+                pitch,
+                player.role,
+                first_number + slot + 1,
+                slot,
+                player.regions.region(FormationState::KickOff),
+            );
+        }
+
         let mut bundle = commands.spawn_bundle(SoccerTeamBundle {
-            team: SoccerTeam::default(),
+            team: SoccerTeam {
+                formation,
+                ..Default::default()
+            },
             color,
             support_spots,
             spatial: SpatialBundle::default(),
@@ -75,30 +117,5 @@ where
                 }
             });
         }
-
-        let (numbers, home_regions) = match team_color {
-            TeamColor::Red => (RED_TEAM_NUMBERS, RED_TEAM_DEFENDING_HOME_REGIONS),
-            TeamColor::Blue => (BLUE_TEAM_NUMBERS, BLUE_TEAM_DEFENDING_HOME_REGIONS),
-        };
-
-        // goal keeper
-        GoalKeeperBundle::<T>::spawn(commands, params, fonts, pitch, numbers[0], home_regions[0]);
-
-        // players
-        for i in 1..TEAM_SIZE {
-            FieldPlayerBundle::<T>::spawn(
-                commands,
-                params,
-                fonts,
-                pitch,
-                if i % 2 == 0 {
-                    FieldPlayerRole::Attacker
-                } else {
-                    FieldPlayerRole::Defender
-                },
-                numbers[i],
-                home_regions[i],
-            );
-        }
     }
 }
@@ -1,31 +1,149 @@
 use bevy::prelude::*;
 use bevy_inspector_egui::*;
+use game_ai_core::random::Random;
 use rand::Rng;
 
 use crate::components::obstacle::*;
 use crate::resources::*;
 
-use super::physics::Physical;
+use super::physics::{Physical, PHYSICS_STEP};
+
+// the most wall bounces resolved in a single physics step
+// (more than one can happen when the ball is kicked into a corner)
+const MAX_WALL_BOUNCES: usize = 4;
 
 #[derive(Debug, Default, Component, Inspectable)]
 pub struct Ball;
 
+#[derive(Debug)]
+struct WallContact {
+    // fraction of the step's displacement traveled before the hit
+    time: f32,
+    normal: Vec2,
+    // how far the ball already overlaps the wall
+    penetration: f32,
+}
+
 impl Ball {
-    pub fn test_collision_with_walls<'a, W>(&self, walls: W)
+    // sweeps the ball along its last physics step rather than testing its end position
+    // so that hard kicks can't tunnel through the walls
+    //
+    // returns true if the ball bounced off of a wall
+    pub fn test_collision_with_walls<'a, W>(
+        &self,
+        params: &SimulationParams,
+        physical: &mut Physical,
+        transform: &mut Transform,
+        radius: f32,
+        walls: W,
+    ) -> bool
     where
         W: Iterator<Item = WallQueryItem<'a>>,
     {
-        // TODO:
+        let walls = walls.collect::<Vec<_>>();
+
+        let mut velocity = physical.velocity;
+        let mut displacement = velocity * PHYSICS_STEP;
+        let mut position = transform.translation.truncate() - displacement;
+
+        let mut bounces = 0;
+        loop {
+            let contact = walls
+                .iter()
+                .filter_map(|wall| {
+                    Self::sweep_wall(wall.wall, wall.transform, position, displacement, radius)
+                })
+                .min_by(|a, b| a.time.total_cmp(&b.time));
+
+            let contact = match contact {
+                Some(contact) => contact,
+                None => break,
+            };
+
+            position += displacement * contact.time + contact.normal * contact.penetration;
+            if bounces == MAX_WALL_BOUNCES {
+                // give up and leave the ball at the wall
+                displacement = Vec2::ZERO;
+                break;
+            }
+
+            displacement = Self::reflect(
+                displacement * (1.0 - contact.time),
+                contact.normal,
+                params.ball_restitution,
+            );
+            velocity = Self::reflect(velocity, contact.normal, params.ball_restitution);
+
+            bounces += 1;
+        }
+
+        if bounces == 0 {
+            return false;
+        }
+
+        physical.velocity = velocity;
+        transform.translation = (position + displacement).extend(transform.translation.z);
+
+        true
+    }
+
+    fn sweep_wall(
+        wall: &Wall,
+        wall_transform: &Transform,
+        position: Vec2,
+        displacement: Vec2,
+        radius: f32,
+    ) -> Option<WallContact> {
+        let normal = wall.normal();
+
+        // moving parallel to or away from the wall
+        let approach = displacement.dot(normal);
+        if approach >= 0.0 {
+            return None;
+        }
+
+        let distance = (position - wall.surface(wall_transform)).dot(normal) - radius;
+
+        // already all the way through the wall
+        if distance < -(wall.thickness() + radius * 2.0) {
+            return None;
+        }
+
+        let time = if distance > 0.0 {
+            distance / -approach
+        } else {
+            0.0
+        };
+        if time > 1.0 {
+            return None;
+        }
+
+        // make sure the ball reaches the wall and not just the line it sits on
+        let contact = position + displacement * time;
+        let along = (contact - wall_transform.translation.truncate()).dot(normal.perp());
+        if along.abs() > wall.half_length() + radius {
+            return None;
+        }
+
+        Some(WallContact {
+            time,
+            normal,
+            penetration: (-distance).max(0.0),
+        })
+    }
+
+    // reflect off of a surface, scaling the normal component by the restitution
+    fn reflect(v: Vec2, normal: Vec2, restitution: f32) -> Vec2 {
+        v - normal * v.dot(normal) * (1.0 + restitution)
     }
 
     pub fn add_noise_to_kick(
         &self,
         params: &SimulationParams,
+        rng: &mut Random,
         transform: &Transform,
         target: Vec2,
     ) -> Vec2 {
-        let mut rng = rand::thread_rng();
-
         let displacement = (std::f32::consts::PI
             - std::f32::consts::PI * params.player_kick_accuracy)
             * rng.gen_range(-1.0..=1.0);
@@ -42,3 +160,164 @@ impl Ball {
         physical.apply_force(direction * force);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RADIUS: f32 = 5.0;
+
+    fn params(restitution: f32) -> SimulationParams {
+        SimulationParams {
+            ball_restitution: restitution,
+            ..Default::default()
+        }
+    }
+
+    // a 100x100 pitch with 5 wide borders, the same layout as PitchBundle
+    fn walls() -> Vec<(Wall, Transform)> {
+        let wall = |position: Vec2, extents: Vec2, facing: Vec2| {
+            (
+                Wall { extents, facing },
+                Transform::from_translation(position.extend(0.0)),
+            )
+        };
+
+        vec![
+            wall(Vec2::new(-50.0, 0.0), Vec2::new(5.0, 100.0), Vec2::X),
+            wall(Vec2::new(0.0, 50.0), Vec2::new(100.0, 5.0), -Vec2::Y),
+            wall(Vec2::new(50.0, 0.0), Vec2::new(5.0, 100.0), -Vec2::X),
+            wall(Vec2::new(0.0, -50.0), Vec2::new(100.0, 5.0), Vec2::Y),
+        ]
+    }
+
+    // integrate a single physics step from start and then test for collisions
+    fn step(
+        restitution: f32,
+        walls: &[(Wall, Transform)],
+        start: Vec2,
+        velocity: Vec2,
+    ) -> (bool, Physical, Transform) {
+        let mut physical = Physical {
+            velocity,
+            ..Default::default()
+        };
+        let mut transform =
+            Transform::from_translation((start + velocity * PHYSICS_STEP).extend(0.0));
+
+        let collided = Ball.test_collision_with_walls(
+            &params(restitution),
+            &mut physical,
+            &mut transform,
+            RADIUS,
+            walls
+                .iter()
+                .map(|(wall, transform)| WallQueryItem { wall, transform }),
+        );
+
+        (collided, physical, transform)
+    }
+
+    #[test]
+    fn no_collision_in_open_play() {
+        let walls = walls();
+        let (collided, physical, _) = step(1.0, &walls, Vec2::ZERO, Vec2::new(100.0, 50.0));
+
+        assert!(!collided);
+        assert_eq!(physical.velocity, Vec2::new(100.0, 50.0));
+    }
+
+    #[test]
+    fn bounce_straight_back() {
+        let walls = walls();
+        // surface at x=47.5, contact at x=42.5, 2.5 of travel left over
+        let (collided, physical, transform) =
+            step(1.0, &walls, Vec2::new(40.0, 0.0), Vec2::new(250.0, 0.0));
+
+        assert!(collided);
+        assert_eq!(physical.velocity, Vec2::new(-250.0, 0.0));
+        assert!((transform.translation.x - 40.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn restitution_only_damps_normal_velocity() {
+        let walls = walls();
+        let (collided, physical, _) =
+            step(0.5, &walls, Vec2::new(40.0, 0.0), Vec2::new(250.0, 100.0));
+
+        assert!(collided);
+        assert_eq!(physical.velocity, Vec2::new(-125.0, 100.0));
+    }
+
+    #[test]
+    fn zero_restitution_stops_at_wall() {
+        let walls = walls();
+        let (collided, physical, transform) =
+            step(0.0, &walls, Vec2::new(40.0, 0.0), Vec2::new(250.0, 0.0));
+
+        assert!(collided);
+        assert_eq!(physical.velocity, Vec2::ZERO);
+        assert!((transform.translation.x - 42.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn no_tunnelling_at_high_speed() {
+        let walls = walls();
+        // covers 100 units in a single step, well past the far side of the wall
+        let (collided, physical, transform) = step(1.0, &walls, Vec2::ZERO, Vec2::new(5000.0, 0.0));
+
+        assert!(collided);
+        assert_eq!(physical.velocity, Vec2::new(-5000.0, 0.0));
+        assert!((transform.translation.x - -15.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn moving_away_from_touching_wall() {
+        let walls = walls();
+        let (collided, physical, _) =
+            step(1.0, &walls, Vec2::new(42.0, 0.0), Vec2::new(-100.0, 0.0));
+
+        assert!(!collided);
+        assert_eq!(physical.velocity, Vec2::new(-100.0, 0.0));
+    }
+
+    #[test]
+    fn push_out_of_overlapping_wall() {
+        let walls = walls();
+        // starts overlapping the east wall by 2
+        let (collided, physical, transform) =
+            step(1.0, &walls, Vec2::new(44.5, 0.0), Vec2::new(50.0, 0.0));
+
+        assert!(collided);
+        assert_eq!(physical.velocity, Vec2::new(-50.0, 0.0));
+        assert!(transform.translation.x <= 42.5 + 0.001);
+    }
+
+    #[test]
+    fn corner_reflects_both_axes() {
+        let walls = walls();
+        let (collided, physical, transform) =
+            step(1.0, &walls, Vec2::new(40.0, 40.0), Vec2::new(500.0, 500.0));
+
+        assert!(collided);
+        assert_eq!(physical.velocity, Vec2::new(-500.0, -500.0));
+        assert!(transform.translation.x <= 42.5 + 0.001);
+        assert!(transform.translation.y <= 42.5 + 0.001);
+    }
+
+    #[test]
+    fn miss_past_end_of_wall() {
+        // a single short wall that the ball passes beside
+        let walls = vec![(
+            Wall {
+                extents: Vec2::new(5.0, 10.0),
+                facing: -Vec2::X,
+            },
+            Transform::from_translation(Vec2::new(50.0, 0.0).extend(0.0)),
+        )];
+
+        let (collided, _, _) = step(1.0, &walls, Vec2::new(40.0, 30.0), Vec2::new(500.0, 0.0));
+
+        assert!(!collided);
+    }
+}
@@ -15,6 +15,25 @@ pub struct Wall {
     pub facing: Vec2,
 }
 
+impl Wall {
+    pub fn normal(&self) -> Vec2 {
+        self.facing.normalize_or_zero()
+    }
+
+    pub fn thickness(&self) -> f32 {
+        self.extents.dot(self.normal().abs())
+    }
+
+    pub fn half_length(&self) -> f32 {
+        self.extents.dot(self.normal().perp().abs()) * 0.5
+    }
+
+    // center of the side of the wall that faces into the pitch
+    pub fn surface(&self, transform: &Transform) -> Vec2 {
+        transform.translation.truncate() + self.normal() * self.thickness() * 0.5
+    }
+}
+
 #[derive(WorldQuery)]
 #[world_query(derive(Debug))]
 pub struct WallQuery {
@@ -4,22 +4,53 @@ use bevy::prelude::*;
 // sparse storage because these get added and removed frequently
 pub trait StateComponent: Component<Storage = bevy::ecs::component::SparseStorage> {}
 
-// TODO: we can't use states across stages as noted here: https://bevy-cheatbook.github.io/programming/states.html
-// so for now we're stuck with a frame between every state transition step
-
 // state transitions need to happen in separate stages
 // so that component changes can be committed
-// and everything can happen in a single frame
-/*#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+// and everything can happen in a single frame:
+//
+// Exit -> Enter -> Update (execute)
+//
+// the advance systems are exclusive and run at the end of each stage,
+// after any state changes made by that stage's systems have been applied
+//
+// bevy states can't be used across stages (https://bevy-cheatbook.github.io/programming/states.html)
+// so systems added to these stages need to check the game state themselves
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
 pub enum StateMachineStage {
     Exit,
     Enter,
-}*/
+}
+
+// adds the state machine stages, shared by all of the state machine plugins
+pub struct StateMachinePlugin;
+
+impl Plugin for StateMachinePlugin {
+    fn build(&self, app: &mut App) {
+        if app
+            .schedule
+            .get_stage::<SystemStage>(&StateMachineStage::Exit)
+            .is_some()
+        {
+            return;
+        }
+
+        app.add_stage_before(
+            CoreStage::Update,
+            StateMachineStage::Exit,
+            SystemStage::parallel(),
+        )
+        .add_stage_after(
+            StateMachineStage::Exit,
+            StateMachineStage::Enter,
+            SystemStage::parallel(),
+        );
+    }
+}
 
 macro_rules! impl_state_machine {
     ($name:ident, $($states:ident),+) => {
         paste::paste! {
-            use bevy::prelude::ParallelSystemDescriptorCoercion;
+            use bevy::prelude::{ExclusiveSystemDescriptorCoercion, IntoExclusiveSystem};
 
             // base state type component trait
             pub trait [<$name StateComponent>]: $crate::components::state::StateComponent {}
@@ -89,6 +120,16 @@ macro_rules! impl_state_machine {
                         )*
                     };
                 }
+
+                // used by the exclusive advance systems,
+                // which work on the world directly rather than through commands
+                fn advance_enter(&self, entity: &mut bevy::ecs::world::EntityMut) {
+                    match self {
+                        $(
+                            Self::$states => entity.insert([<$name State $states Enter>]),
+                        )*
+                    };
+                }
             }
 
             // the state machine
@@ -117,7 +158,8 @@ macro_rules! impl_state_machine {
                         reenter_state
                     });
 
-                    // insert the starting state component
+                    // the starting state is entered like any other state,
+                    // so its enter system runs before its first execute
                     starting_state.insert_enter(commands);
                 }
 
@@ -168,30 +210,33 @@ macro_rules! impl_state_machine {
             // needed to move from enter -> execute and to remove the exit state marker
             $(
                 #[allow(non_snake_case)]
-                fn [<$name _state_ $states _enter_advance>](
-                    mut commands: bevy::prelude::Commands,
-                    query: bevy::prelude::Query<bevy::prelude::Entity, bevy::prelude::With<[<$name State $states Enter>]>>
-                ) {
+                fn [<$name _state_ $states _enter_advance>](world: &mut bevy::prelude::World) {
                     bevy::prelude::debug!("advancing enter states to execute ...");
 
-                    for entity in query.iter() {
-                        let mut entity = commands.entity(entity);
+                    let mut query = world.query_filtered::<bevy::prelude::Entity, bevy::prelude::With<[<$name State $states Enter>]>>();
+                    let entities = query.iter(world).collect::<Vec<_>>();
+
+                    for entity in entities {
+                        let mut entity = world.entity_mut(entity);
                         entity.remove::<[<$name State $states Enter>]>();
                         entity.insert([<$name State $states Execute>]);
                     }
                 }
 
                 #[allow(non_snake_case)]
-                fn [<$name _state_ $states _exit_advance>](
-                    mut commands: bevy::prelude::Commands,
-                    query: bevy::prelude::Query<(bevy::prelude::Entity, &[<$name StateMachine>]), bevy::prelude::With<[<$name State $states Exit>]>>
-                ) {
+                fn [<$name _state_ $states _exit_advance>](world: &mut bevy::prelude::World) {
                     bevy::prelude::debug!("advancing exit states to enter ...");
 
-                    for (entity, state_machine) in query.iter() {
-                        let mut entity = commands.entity(entity);
+                    let mut query = world.query_filtered::<(bevy::prelude::Entity, &[<$name StateMachine>]), bevy::prelude::With<[<$name State $states Exit>]>>();
+                    let entities = query
+                        .iter(world)
+                        .map(|(entity, state_machine)| (entity, state_machine.current_state))
+                        .collect::<Vec<_>>();
+
+                    for (entity, current_state) in entities {
+                        let mut entity = world.entity_mut(entity);
                         entity.remove::<[<$name State $states Exit>]>();
-                        state_machine.current_state.insert_enter(&mut entity);
+                        current_state.advance_enter(&mut entity);
                     }
                 }
             )*
@@ -212,24 +257,19 @@ macro_rules! impl_state_machine {
                             .register_inspectable::<[<$name StateMachine>]>();
                     )*
 
-                    // exit stage
-                    /*app.add_stage_before(bevy::prelude::CoreStage::Update, crate::components::state::StateMachineStage::Exit, bevy::prelude::SystemStage::parallel())
-                    $(
-                        .add_system([<$name _state_ $states _exit_advance>])
-                    )*;*/
-
-                    // enter stage
-                    /*app.add_stage_after(crate::components::state::StateMachineStage::Exit, crate::components::state::StateMachineStage::Enter, bevy::prelude::SystemStage::parallel())
-                    $(
-                        .add_system([<$name _state_ $states _enter_advance>])
-                    )*;*/
+                    app.add_plugin($crate::components::state::StateMachinePlugin);
 
                     // state advancement
+                    // these run after the stage systems have been flushed
                     $(
-                        app.add_system([<$name _state_ $states _enter_advance>])
-                            .add_system([<$name _state_ $states _exit_advance>]
-                                .after([<$name _state_ $states _enter_advance>])
-                            );
+                        app.add_system_to_stage(
+                            $crate::components::state::StateMachineStage::Exit,
+                            [<$name _state_ $states _exit_advance>].exclusive_system().at_end(),
+                        )
+                        .add_system_to_stage(
+                            $crate::components::state::StateMachineStage::Enter,
+                            [<$name _state_ $states _enter_advance>].exclusive_system().at_end(),
+                        );
                     )*
                 }
             }
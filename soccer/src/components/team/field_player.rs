@@ -1,17 +1,18 @@
 use bevy::ecs::query::WorldQuery;
 use bevy::prelude::*;
 use bevy_inspector_egui::*;
+use serde::Deserialize;
 
 use crate::components::actor::*;
 use crate::components::agent::*;
 use crate::components::state::impl_state_machine;
 use crate::components::steering::*;
-use crate::game::Cooldown;
 use crate::resources::*;
+use game_ai_core::cooldown::Cooldown;
 
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Inspectable)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Inspectable, Deserialize)]
 pub enum FieldPlayerRole {
     Attacker,
     Defender,
@@ -45,7 +46,7 @@ impl FieldPlayer {
     }
 
     pub fn is_ready_for_next_kick(&self) -> bool {
-        self.kick_cooldown.available()
+        self.kick_cooldown.is_available()
     }
 
     pub fn is_ball_within_receiving_range(
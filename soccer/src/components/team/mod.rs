@@ -9,14 +9,16 @@ pub use player::*;
 use bevy::ecs::query::WorldQuery;
 use bevy::prelude::*;
 use bevy_inspector_egui::*;
+use game_ai_core::random::Random;
 use rand::Rng;
 
 use crate::components::goal::*;
 use crate::components::physics::*;
 use crate::game::team::*;
+use crate::resources::formation::*;
 use crate::resources::pitch::*;
 use crate::resources::SimulationParams;
-use crate::util::{get_tangent_points, point_to_world_space};
+use crate::util::{get_tangent_points, point_to_local_space};
 
 use super::state::impl_state_machine;
 
@@ -25,6 +27,10 @@ impl_state_machine!(SoccerTeam, PrepareForKickOff, Defending, Attacking);
 #[derive(Debug, Default, Component, Inspectable)]
 pub struct SoccerTeam {
     pub best_support_spot: Option<Vec2>,
+
+    // already mirrored for the team's side of the pitch
+    #[inspectable(ignore)]
+    pub formation: Formation,
 }
 
 impl SoccerTeam {
@@ -70,21 +76,20 @@ impl SoccerTeam {
         }
     }
 
-    pub fn reset_player_home_regions<T>(
+    pub fn update_player_home_regions<T>(
         &self,
         field_players: &mut Query<FieldPlayerQueryMut<T>, Without<GoalKeeper>>,
         goal_keeper: &mut GoalKeeperQueryMutItem<T>,
-        home_regions: [usize; TEAM_SIZE],
+        state: FormationState,
     ) where
         T: TeamColorMarker,
     {
-        goal_keeper.player.home_region = home_regions[0];
+        goal_keeper.player.home_region = self.formation.goal_keeper.region(state);
 
-        let mut idx = 1;
         for mut field_player in field_players.iter_mut() {
-            field_player.player.home_region = home_regions[idx];
-
-            idx += 1;
+            field_player.player.home_region = self
+                .formation
+                .field_player_home_region(field_player.player.formation_slot, state);
         }
     }
 
@@ -116,6 +121,7 @@ impl SoccerTeam {
     pub fn determine_best_supporting_position<'a, T, O, F>(
         &mut self,
         params: &SimulationParams,
+        rng: &mut Random,
         team: &T,
         support_calculator: &mut SupportSpotCalculator,
         opponents: F,
@@ -158,6 +164,7 @@ impl SoccerTeam {
             if self
                 .can_shoot::<T, O, F>(
                     params,
+                    rng,
                     spot.position,
                     opponent_goal,
                     ball,
@@ -232,15 +239,7 @@ impl SoccerTeam {
     ) -> bool {
         let opponent_position = opponent.0.transform.translation.truncate();
 
-        let to_target = target - from;
-        let to_target_norm = to_target.normalize_or_zero();
-
-        let local_pos_opp = point_to_world_space(
-            opponent_position,
-            to_target_norm,
-            to_target_norm.perp(),
-            from,
-        );
+        let local_pos_opp = to_pass_space(from, target, opponent_position);
 
         // ignore opponents behind us
         // TODO: this should be less than our negative bounding radius
@@ -254,15 +253,15 @@ impl SoccerTeam {
             // can the receiver get there first?
             if let Some(receiver) = receiver {
                 let receiver_position = receiver.translation.truncate();
-                return target.distance_squared(opponent_position)
-                    < target.distance_squared(receiver_position);
+                return target.distance_squared(receiver_position)
+                    < target.distance_squared(opponent_position);
             } else {
                 return true;
             }
         }
 
         let time_for_ball = ball.0.time_to_cover_distance(
-            params,
+            params.friction,
             Vec2::ZERO,
             Vec2::new(local_pos_opp.x, 0.0),
             passing_force,
@@ -277,6 +276,7 @@ impl SoccerTeam {
     pub fn determine_best_supporting_attacker<'a, T, M, O, F>(
         &mut self,
         params: &SimulationParams,
+        rng: &mut Random,
         team: &T,
         support_calculator: &mut SupportSpotCalculator,
         teammates: M,
@@ -299,6 +299,7 @@ impl SoccerTeam {
         } else {
             self.determine_best_supporting_position(
                 params,
+                rng,
                 team,
                 support_calculator,
                 opponents,
@@ -334,6 +335,7 @@ impl SoccerTeam {
     pub fn can_shoot<'a, T, O, F>(
         &self,
         params: &SimulationParams,
+        rng: &mut Random,
         from: Vec2,
         opponent_goal: &GoalQueryItem,
         ball: (&Physical, &BoundingCircle),
@@ -345,8 +347,6 @@ impl SoccerTeam {
         F: Fn() -> O,
         O: Iterator<Item = (PhysicalQueryItem<'a>, &'a BoundingCircle)>,
     {
-        let mut rng = rand::thread_rng();
-
         let top = opponent_goal.goal.get_top(opponent_goal.transform);
         let bottom = opponent_goal.goal.get_bottom(opponent_goal.transform);
         let center = opponent_goal.goal.get_score_center(opponent_goal.transform);
@@ -362,7 +362,9 @@ impl SoccerTeam {
 
             target.y = rng.gen_range(min_y..=max_y);
 
-            let time = ball.0.time_to_cover_distance(params, from, target, power);
+            let time = ball
+                .0
+                .time_to_cover_distance(params.friction, from, target, power);
             if time >= 0.0
                 && self.is_pass_safe_from_all_opponents::<T, O>(
                     params,
@@ -383,6 +385,7 @@ impl SoccerTeam {
         (target, false)
     }
 
+    // opponents returns the opponents within a radius of a position
     pub fn find_pass<'a, T, M, O, F>(
         &self,
         params: &SimulationParams,
@@ -397,7 +400,7 @@ impl SoccerTeam {
     where
         T: TeamColorMarker,
         M: Iterator<Item = (Entity, FieldPlayerQueryItem<'a, T>, PhysicalQueryItem<'a>)>,
-        F: Fn() -> O + Copy,
+        F: Fn(Vec2, f32) -> O + Copy,
         O: Iterator<Item = (PhysicalQueryItem<'a>, &'a BoundingCircle)>,
     {
         let passer_position = passer.1.translation.truncate();
@@ -449,16 +452,16 @@ impl SoccerTeam {
     ) -> Option<Vec2>
     where
         T: TeamColorMarker,
-        F: Fn() -> O + Copy,
+        F: Fn(Vec2, f32) -> O + Copy,
         O: Iterator<Item = (PhysicalQueryItem<'a>, &'a BoundingCircle)>,
     {
         let receiver_position = receiver.transform.translation.truncate();
         let opponent_goal_center = opponent_goal.goal.get_score_center(opponent_goal.transform);
         let ball_position = ball.1.translation.truncate();
 
-        let time = ball
-            .0
-            .time_to_cover_distance(params, ball_position, receiver_position, power);
+        let time =
+            ball.0
+                .time_to_cover_distance(params.friction, ball_position, receiver_position, power);
         if time < 0.0 {
             return None;
         }
@@ -478,14 +481,19 @@ impl SoccerTeam {
         let mut target = None;
 
         for pass in passes {
+            // only opponents that are closer to the ball than the pass target,
+            // or closer to the pass target than the receiver, can make the pass unsafe
+            let opponents = opponents(ball_position, ball_position.distance(pass))
+                .chain(opponents(pass, pass.distance(receiver_position)));
+
             let dist = pass.x - opponent_goal_center.x;
             if dist < closest_so_far
-                && self.is_pass_safe_from_all_opponents::<T, O>(
+                && self.is_pass_safe_from_all_opponents::<T, _>(
                     params,
                     ball_position,
                     pass,
                     Some(receiver.transform),
-                    opponents(),
+                    opponents,
                     (ball.0, ball.2),
                     power,
                 )
@@ -621,3 +629,38 @@ impl SupportSpotCalculator {
         Self { spots }
     }
 }
+
+// x is the distance along the pass, y is the distance off to the side of it
+fn to_pass_space(from: Vec2, target: Vec2, position: Vec2) -> Vec2 {
+    let heading = (target - from).normalize_or_zero();
+    point_to_local_space(position, heading, heading.perp(), from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 0.0001, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_to_pass_space() {
+        let from = Vec2::new(10.0, 10.0);
+        let target = Vec2::new(10.0, 30.0);
+
+        // on the pass line, halfway to the target
+        assert_near(
+            to_pass_space(from, target, Vec2::new(10.0, 20.0)),
+            Vec2::new(10.0, 0.0),
+        );
+
+        // behind the passer
+        assert!(to_pass_space(from, target, Vec2::new(10.0, 5.0)).x < 0.0);
+
+        // off to the side of the pass line
+        let opponent = to_pass_space(from, target, Vec2::new(13.0, 20.0));
+        assert!((opponent.x - 10.0).abs() < 0.0001);
+        assert!((opponent.y.abs() - 3.0).abs() < 0.0001);
+    }
+}
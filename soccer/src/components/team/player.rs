@@ -11,6 +11,9 @@ use super::*;
 pub struct SoccerPlayer {
     pub number: usize,
 
+    // index into the team formation's field players
+    pub formation_slot: usize,
+
     pub home_region: usize,
     pub default_region: usize,
 }
@@ -101,6 +104,7 @@ impl SoccerPlayer {
         &self,
         commands: &mut Commands,
         params: &SimulationParams,
+        rng: &mut Random,
         message_dispatcher: &mut FieldPlayerMessageDispatcher,
         team: &mut SoccerTeamQueryMutItem<T>,
         support_calculator: &mut SupportSpotCalculator,
@@ -122,6 +126,7 @@ impl SoccerPlayer {
             .team
             .determine_best_supporting_attacker(
                 params,
+                rng,
                 team.color,
                 support_calculator,
                 teammates,
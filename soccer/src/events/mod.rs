@@ -1,5 +1,4 @@
 pub mod debug;
-pub mod messaging;
 
 use bevy::prelude::*;
 
@@ -12,3 +11,11 @@ use crate::game::team::*;
 pub struct FindSupportEvent(pub Entity);
 
 pub struct GoalScoredEvent(pub TeamColor);
+
+// match statistics that can't be picked up from other events
+#[derive(Debug)]
+pub enum MatchEvent {
+    Shot { team: TeamColor, on_target: bool },
+    PassCompleted(TeamColor),
+    GoalKeeperTrap { team: TeamColor, intercepting: bool },
+}
@@ -1,9 +1,7 @@
-pub mod cooldown;
 pub mod team;
 
-pub use cooldown::*;
-
 pub const BALL_RADIUS: f32 = 7.0;
 pub const GOAL_BAR_WIDTH: f32 = 5.0;
 pub const BORDER_WIDTH: f32 = 5.0;
 pub const PLAYER_RADIUS: f32 = 15.0;
+pub const PLAYER_HEADING_SMOOTHING_SAMPLES: usize = 5;
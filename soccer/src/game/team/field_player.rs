@@ -1,7 +1,6 @@
 use bevy::prelude::*;
 
-use crate::events::messaging::*;
-use crate::resources::messaging::*;
+use game_ai_core::messaging::*;
 
 #[derive(Debug, PartialEq)]
 pub enum FieldPlayerMessage {
@@ -1,10 +1,9 @@
-use crate::events::messaging::*;
-use crate::resources::messaging::*;
+use game_ai_core::messaging::*;
 
 #[derive(Debug, PartialEq)]
 pub enum GoalKeeperMessage {
     //GoHome,
-//ReceiveBall,
+    //ReceiveBall,
 }
 
 impl Eq for GoalKeeperMessage {}
@@ -8,20 +8,13 @@ pub use goal_keeper::*;
 
 use bevy::prelude::*;
 use bevy_inspector_egui::prelude::*;
+use serde::Serialize;
 
-pub const TEAM_SIZE: usize = 5;
+// goal keeper included
+pub const MIN_TEAM_SIZE: usize = 3;
+pub const MAX_TEAM_SIZE: usize = 11;
 
-// first number is the goal keeper number
-pub const BLUE_TEAM_NUMBERS: [usize; TEAM_SIZE] = [1, 2, 3, 4, 5];
-pub const RED_TEAM_NUMBERS: [usize; TEAM_SIZE] = [11, 12, 13, 14, 15];
-
-// first region is the goal keeper home
-pub const BLUE_TEAM_DEFENDING_HOME_REGIONS: [usize; TEAM_SIZE] = [1, 6, 8, 3, 5];
-pub const RED_TEAM_DEFENDING_HOME_REGIONS: [usize; TEAM_SIZE] = [16, 9, 11, 12, 14];
-pub const BLUE_TEAM_ATTACKING_HOME_REGIONS: [usize; TEAM_SIZE] = [1, 4, 6, 12, 14];
-pub const RED_TEAM_ATTACKING_HOME_REGIONS: [usize; TEAM_SIZE] = [16, 3, 5, 9, 13];
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Inspectable)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Inspectable, Serialize)]
 pub enum TeamColor {
     Red,
     Blue,
@@ -35,6 +28,14 @@ impl TeamColor {
         }
     }
 
+    // the goal keeper wears the first number
+    pub fn first_number(&self) -> usize {
+        match self {
+            Self::Red => 11,
+            Self::Blue => 1,
+        }
+    }
+
     pub fn goal_keeper_color(&self) -> Color {
         match self {
             Self::Red => Color::YELLOW,
@@ -11,17 +11,26 @@ mod states;
 mod systems;
 mod util;
 
+use bevy::asset::AssetPlugin;
 use bevy::diagnostic::*;
+use bevy::hierarchy::HierarchyPlugin;
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
+use bevy::time::TimePlugin;
+use bevy::transform::TransformPlugin;
 use bevy::window::PresentMode;
 use bevy_common_assets::ron::RonAssetPlugin;
 use bevy_egui::{EguiPlugin, EguiSettings};
 use bevy_inspector_egui::prelude::*;
-use bevy_inspector_egui::WorldInspectorParams;
+use bevy_inspector_egui::{InspectableRegistry, WorldInspectorParams};
 use bevy_prototype_lyon::prelude::*;
+use game_ai_core::args::{has_arg, parse_arg, parse_seed};
+use game_ai_core::random::Random;
 
 use plugins::debug::DebugPlugin;
-use plugins::states::StatesPlugins;
+use plugins::headless::HeadlessPlugin;
+use plugins::states::{IntroStatePlugin, StatesPlugins};
+use resources::formation::*;
 use resources::ui::*;
 use resources::*;
 use states::GameState;
@@ -35,8 +44,11 @@ pub const AGENT_UPDATE_STEP: f64 = 0.8;
 pub const TEXT_SORT: f32 = 50.0;
 pub const DEBUG_SORT: f32 = 100.0;
 pub const DEBUG_RADIUS: f32 = 5.0;
+pub const DEFAULT_MATCH_SECONDS: f32 = 300.0;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, random: Res<Random>) {
+    info!("random seed: {}", random.seed());
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     #[cfg(debug_assertions)]
     asset_server.watch_for_changes().unwrap();
 
@@ -45,15 +57,25 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         handle: params_handle,
     });
 
+    let load_formation = |name: Option<String>| -> Handle<Formation> {
+        asset_server.load(&format!(
+            "formations/{}.formation",
+            name.unwrap_or_else(|| DEFAULT_FORMATION.to_owned())
+        ))
+    };
+    commands.insert_resource(FormationAssets {
+        red_team: load_formation(parse_arg("--red-formation")),
+        blue_team: load_formation(parse_arg("--blue-formation")),
+    });
+}
+
+fn setup_fonts(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(Fonts {
         normal: asset_server.load("fonts/FiraSans-Bold.ttf"),
     });
 }
 
-#[bevy_main]
-fn main() {
-    let mut app = App::new();
-
+fn add_windowed(app: &mut App) {
     // basic bevy
     app.insert_resource(WindowDescriptor {
         title: "Soccer".to_owned(),
@@ -119,10 +141,53 @@ fn main() {
     .register_inspectable::<util::Rect>();
 
     // assets
-    app.add_plugin(RonAssetPlugin::<SimulationParams>::new(&["params"]));
+    app.add_plugin(RonAssetPlugin::<SimulationParams>::new(&["params"]))
+        .add_plugin(RonAssetPlugin::<Formation>::new(&["formation"]))
+        .add_startup_system(setup_fonts);
 
     // plugins
     app.add_plugin(DebugPlugin).add_plugins(StatesPlugins);
+}
+
+fn add_headless(app: &mut App) {
+    // basic bevy, without rendering
+    app.insert_resource(bevy::log::LogSettings {
+        level: bevy::log::Level::WARN,
+        ..Default::default()
+    })
+    .add_plugins_with(MinimalPlugins, |group| group.disable::<TimePlugin>())
+    .add_plugin(LogPlugin)
+    .add_plugin(TransformPlugin)
+    .add_plugin(HierarchyPlugin)
+    .add_plugin(AssetPlugin);
+
+    // assets
+    app.add_plugin(RonAssetPlugin::<SimulationParams>::new(&["params"]))
+        .add_plugin(RonAssetPlugin::<Formation>::new(&["formation"]));
+
+    // the state machine plugins register their inspectable components
+    app.init_resource::<InspectableRegistry>();
+
+    // plugins
+    app.add_plugin(HeadlessPlugin {
+        max_seconds: parse_arg("--match-seconds").unwrap_or(DEFAULT_MATCH_SECONDS),
+        max_goals: parse_arg("--match-goals"),
+    })
+    .add_plugins_with(StatesPlugins, |group| group.disable::<IntroStatePlugin>());
+}
+
+#[bevy_main]
+fn main() {
+    let mut app = App::new();
+
+    if has_arg("--headless") {
+        add_headless(&mut app);
+    } else {
+        add_windowed(&mut app);
+    }
+
+    // seedable rng
+    app.insert_resource(parse_seed().map(Random::new).unwrap_or_default());
 
     // initial game state
     app.add_state(GameState::Intro);
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::time::FixedTimesteps;
+use game_ai_core::random::Random;
+use serde::Serialize;
+
+use crate::components::physics::PHYSICS_STEP;
+use crate::resources::formation::*;
+use crate::resources::stats::*;
+use crate::resources::ui::*;
+use crate::resources::{GameState, SimulationParams, SimulationParamsAsset};
+use crate::states;
+
+// runs a single match without rendering
+// and reports the result as JSON when it's over
+pub struct HeadlessPlugin {
+    pub max_seconds: f32,
+    pub max_goals: Option<usize>,
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MatchSettings {
+            max_seconds: self.max_seconds,
+            max_goals: self.max_goals,
+        });
+
+        // simulated time replaces the TimePlugin
+        // so that matches run as fast as possible
+        app.init_resource::<Time>()
+            .init_resource::<FixedTimesteps>()
+            .insert_resource(SimulatedTime {
+                now: Instant::now(),
+            })
+            .add_system_to_stage(CoreStage::First, update_time);
+
+        // no text rendering, so nothing to load
+        app.init_resource::<Fonts>();
+
+        // systems
+        app.add_system_set(SystemSet::on_update(states::GameState::Intro).with_system(start_match))
+            .add_system_set(
+                SystemSet::on_enter(states::GameState::Main).with_system(setup_match_clock),
+            )
+            .add_system_set(
+                SystemSet::on_update(states::GameState::Main).with_system(check_match_over),
+            );
+    }
+}
+
+struct MatchSettings {
+    max_seconds: f32,
+    max_goals: Option<usize>,
+}
+
+struct SimulatedTime {
+    now: Instant,
+}
+
+struct MatchClock {
+    started: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct MatchResult {
+    seed: u64,
+    seconds: f64,
+    red_team_score: usize,
+    blue_team_score: usize,
+    stats: MatchStats,
+}
+
+// step time by a single physics step every frame
+fn update_time(mut time: ResMut<Time>, mut simulated: ResMut<SimulatedTime>) {
+    simulated.now += Duration::from_secs_f32(PHYSICS_STEP);
+    time.update_with_instant(simulated.now);
+}
+
+// there's no intro screen to click through,
+// so start as soon as the simulation params and formations are available
+fn start_match(
+    params_asset: Res<SimulationParamsAsset>,
+    params_assets: Res<Assets<SimulationParams>>,
+    formation_assets: Res<FormationAssets>,
+    formations: Res<Assets<Formation>>,
+    mut state: ResMut<State<states::GameState>>,
+) {
+    if params_assets.get(&params_asset.handle).is_some()
+        && formations.get(&formation_assets.red_team).is_some()
+        && formations.get(&formation_assets.blue_team).is_some()
+    {
+        state.set(states::GameState::Main).unwrap();
+    }
+}
+
+fn setup_match_clock(mut commands: Commands, time: Res<Time>) {
+    commands.insert_resource(MatchClock {
+        started: time.seconds_since_startup(),
+    });
+}
+
+fn check_match_over(
+    settings: Res<MatchSettings>,
+    clock: Res<MatchClock>,
+    time: Res<Time>,
+    random: Res<Random>,
+    game_state: Res<GameState>,
+    stats: Res<MatchStats>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let seconds = time.seconds_since_startup() - clock.started;
+    let goals = game_state.red_team_score + game_state.blue_team_score;
+
+    let out_of_time = seconds >= settings.max_seconds as f64;
+    let out_of_goals = settings
+        .max_goals
+        .map(|max_goals| goals >= max_goals)
+        .unwrap_or_default();
+    if !out_of_time && !out_of_goals {
+        return;
+    }
+
+    let result = MatchResult {
+        seed: random.seed(),
+        seconds,
+        red_team_score: game_state.red_team_score,
+        blue_team_score: game_state.blue_team_score,
+        stats: stats.clone(),
+    };
+    println!("{}", serde_json::to_string_pretty(&result).unwrap());
+
+    exit.send(AppExit);
+}
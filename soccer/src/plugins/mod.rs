@@ -1,2 +1,3 @@
 pub mod debug;
+pub mod headless;
 pub mod states;
@@ -2,8 +2,10 @@ use bevy::app::PluginGroupBuilder;
 use bevy::ecs::schedule::ShouldRun;
 use bevy::prelude::*;
 use bevy::time::FixedTimestep;
+use game_ai_core::plugins::MessagingPlugin;
 
 use crate::components::physics::PHYSICS_STEP;
+use crate::components::state::StateMachineStage;
 use crate::components::team::*;
 use crate::events::*;
 use crate::game::team::*;
@@ -21,7 +23,7 @@ impl PluginGroup for StatesPlugins {
     }
 }
 
-struct IntroStatePlugin;
+pub struct IntroStatePlugin;
 
 impl Plugin for IntroStatePlugin {
     fn build(&self, app: &mut App) {
@@ -48,8 +50,11 @@ impl Plugin for MainStatePlugin {
         // events
         app.add_event::<FindSupportEvent>()
             .add_event::<GoalScoredEvent>()
-            .add_event::<FieldPlayerDispatchedMessageEvent>()
-            .add_event::<GoalKeeperDispatchedMessageEvent>();
+            .add_event::<MatchEvent>();
+
+        // messaging
+        app.add_plugin(MessagingPlugin::<FieldPlayerMessage>::default())
+            .add_plugin(MessagingPlugin::<GoalKeeperMessage>::default());
 
         // systems
         app.add_system_set(SystemSet::on_enter(GameState::Main).with_system(states::main::setup))
@@ -68,17 +73,23 @@ impl Plugin for MainStatePlugin {
                     ))
                     /*SystemSet::on_update(GameState::Main)
                     .with_run_criteria(FixedTimestep::step(PHYSICS_STEP as f64))*/
+                    // partition
+                    .with_system(systems::update_partition.label(Systems::Partition))
                     // steering
                     .with_system(systems::steering::update_seek.label(Systems::Steering))
                     .with_system(systems::steering::update_arrive.label(Systems::Steering))
                     .with_system(systems::steering::update_pursuit.label(Systems::Steering))
                     .with_system(
-                        systems::steering::update
+                        game_ai_core::systems::steering::update
                             .label(Systems::SteeringUpdatePhysics)
                             .after(Systems::Steering),
                     )
                     .with_system(systems::steering::update_interpose.label(Systems::Steering))
-                    .with_system(systems::steering::update_separation.label(Systems::Steering))
+                    .with_system(
+                        systems::steering::update_separation
+                            .label(Systems::Steering)
+                            .after(Systems::Partition),
+                    )
                     // physics
                     .with_system(
                         systems::physics::update
@@ -86,6 +97,13 @@ impl Plugin for MainStatePlugin {
                             .after(Systems::SteeringUpdatePhysics),
                     )
                     .with_system(systems::physics::facing.after(Systems::Physics))
+                    // ball
+                    .with_system(
+                        systems::ball::update
+                            .label(Systems::BallCollision)
+                            .after(Systems::Physics),
+                    )
+                    .with_system(systems::ball::update_physics.after(Systems::BallCollision))
                     // field player systems
                     .with_system(
                         systems::team::field_player::update_physics::<RedTeam>
@@ -96,9 +114,7 @@ impl Plugin for MainStatePlugin {
                         systems::team::field_player::update_physics::<BlueTeam>
                             .after(Systems::Steering)
                             .before(Systems::SteeringUpdatePhysics),
-                    )
-                    // everything else
-                    .with_system(systems::ball::update_physics),
+                    ),
             )
             // agents (fixed timestep)
             .add_system_set(
@@ -116,8 +132,8 @@ impl Plugin for MainStatePlugin {
                     /*SystemSet::on_update(GameState::Main)
                     .with_run_criteria(FixedTimestep::step(AGENT_UPDATE_STEP))*/
                     // messaging
-                    .with_system(systems::messaging::update::<FieldPlayerMessage>)
-                    .with_system(systems::messaging::update::<GoalKeeperMessage>)
+                    .with_system(game_ai_core::systems::messaging::update::<FieldPlayerMessage>)
+                    .with_system(game_ai_core::systems::messaging::update::<GoalKeeperMessage>)
                     // team systems
                     .with_system(
                         systems::team::PrepareForKickOff_execute::<RedTeam>
@@ -298,24 +314,12 @@ impl Plugin for MainStatePlugin {
                             .after(Systems::TeamStates),
                     ),
             )
-            // per-frame systems
-            .add_system_set(
-                SystemSet::on_update(GameState::Main)
-                    // steering
-                    .with_system(systems::steering::update_debug)
+            // state exit (per-frame)
+            .add_system_set_to_stage(
+                StateMachineStage::Exit,
+                SystemSet::new()
+                    .with_run_criteria(states::in_main_state)
                     // team systems
-                    .with_system(systems::team::update::<RedTeam>.label(Systems::TeamUpdate))
-                    .with_system(systems::team::update::<BlueTeam>.label(Systems::TeamUpdate))
-                    .with_system(
-                        systems::team::PrepareForKickOff_enter::<RedTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::TeamStates),
-                    )
-                    .with_system(
-                        systems::team::PrepareForKickOff_enter::<BlueTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::TeamStates),
-                    )
                     .with_system(
                         systems::team::PrepareForKickOff_exit::<RedTeam>
                             .label(Systems::StateExit)
@@ -327,215 +331,224 @@ impl Plugin for MainStatePlugin {
                             .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::Defending_enter::<RedTeam>
-                            .label(Systems::StateEnter)
+                        systems::team::Attacking_exit::<RedTeam>
+                            .label(Systems::StateExit)
                             .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::Defending_enter::<BlueTeam>
-                            .label(Systems::StateEnter)
+                        systems::team::Attacking_exit::<BlueTeam>
+                            .label(Systems::StateExit)
                             .label(Systems::TeamStates),
                     )
+                    // field player systems
                     .with_system(
-                        systems::team::Attacking_enter::<RedTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::TeamStates),
+                        systems::team::field_player::ChaseBall_exit::<RedTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::Attacking_enter::<BlueTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::TeamStates),
+                        systems::team::field_player::ChaseBall_exit::<BlueTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::Attacking_exit::<RedTeam>
+                        systems::team::field_player::ReceiveBall_exit::<RedTeam>
                             .label(Systems::StateExit)
-                            .label(Systems::TeamStates),
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::Attacking_exit::<BlueTeam>
+                        systems::team::field_player::ReceiveBall_exit::<BlueTeam>
                             .label(Systems::StateExit)
-                            .label(Systems::TeamStates),
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
-                    // field player systems
                     .with_system(
-                        systems::team::field_player::update::<RedTeam>
-                            .label(Systems::FieldPlayerUpdate)
-                            .after(Systems::TeamUpdate),
+                        systems::team::field_player::SupportAttacker_exit::<RedTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::update::<BlueTeam>
-                            .label(Systems::FieldPlayerUpdate)
-                            .after(Systems::TeamUpdate),
+                        systems::team::field_player::SupportAttacker_exit::<BlueTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::find_support_event_handler::<RedTeam>
-                            .label(Systems::FieldPlayerEvents)
-                            .after(Systems::FieldPlayerStates),
+                        systems::team::field_player::ReturnToHomeRegion_exit::<RedTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::find_support_event_handler::<BlueTeam>
-                            .label(Systems::FieldPlayerEvents)
-                            .after(Systems::FieldPlayerStates),
+                        systems::team::field_player::ReturnToHomeRegion_exit::<BlueTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::FieldPlayerStates)
+                            .after(Systems::TeamStates),
                     )
+                    // goal keeper systems
                     .with_system(
-                        systems::team::field_player::GlobalState_on_message::<RedTeam>
-                            .label(Systems::GlobalStateOnMessage),
+                        systems::team::goal_keeper::TendGoal_exit::<RedTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::GoalKeeperStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::GlobalState_on_message::<BlueTeam>
-                            .label(Systems::GlobalStateOnMessage),
+                        systems::team::goal_keeper::TendGoal_exit::<BlueTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::GoalKeeperStates)
+                            .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ChaseBall_enter::<RedTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::FieldPlayerStates)
+                        systems::team::goal_keeper::ReturnHome_exit::<RedTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ChaseBall_enter::<BlueTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::FieldPlayerStates)
+                        systems::team::goal_keeper::ReturnHome_exit::<BlueTeam>
+                            .label(Systems::StateExit)
+                            .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ChaseBall_exit::<RedTeam>
+                        systems::team::goal_keeper::InterceptBall_exit::<RedTeam>
                             .label(Systems::StateExit)
-                            .label(Systems::FieldPlayerStates)
+                            .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ChaseBall_exit::<BlueTeam>
+                        systems::team::goal_keeper::InterceptBall_exit::<BlueTeam>
                             .label(Systems::StateExit)
-                            .label(Systems::FieldPlayerStates)
+                            .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
+                    ),
+            )
+            // state enter (per-frame)
+            .add_system_set_to_stage(
+                StateMachineStage::Enter,
+                SystemSet::new()
+                    .with_run_criteria(states::in_main_state)
+                    // team systems
+                    .with_system(
+                        systems::team::PrepareForKickOff_enter::<RedTeam>
+                            .label(Systems::StateEnter)
+                            .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::Wait_enter::<RedTeam>
+                        systems::team::PrepareForKickOff_enter::<BlueTeam>
                             .label(Systems::StateEnter)
-                            .label(Systems::FieldPlayerStates)
-                            .after(Systems::TeamStates),
+                            .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::Wait_enter::<BlueTeam>
+                        systems::team::Defending_enter::<RedTeam>
                             .label(Systems::StateEnter)
-                            .label(Systems::FieldPlayerStates)
-                            .after(Systems::TeamStates),
+                            .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReceiveBall_enter::<RedTeam>
+                        systems::team::Defending_enter::<BlueTeam>
                             .label(Systems::StateEnter)
-                            .label(Systems::FieldPlayerStates)
-                            .after(Systems::TeamStates),
+                            .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReceiveBall_enter::<BlueTeam>
+                        systems::team::Attacking_enter::<RedTeam>
                             .label(Systems::StateEnter)
-                            .label(Systems::FieldPlayerStates)
-                            .after(Systems::TeamStates),
+                            .label(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReceiveBall_exit::<RedTeam>
+                        systems::team::Attacking_enter::<BlueTeam>
+                            .label(Systems::StateEnter)
+                            .label(Systems::TeamStates),
+                    )
+                    // field player systems
+                    .with_system(
+                        systems::team::field_player::ChaseBall_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReceiveBall_exit::<BlueTeam>
+                        systems::team::field_player::ChaseBall_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::KickBall_enter::<RedTeam>
+                        systems::team::field_player::Wait_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::KickBall_enter::<BlueTeam>
+                        systems::team::field_player::Wait_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::Dribble_enter::<RedTeam>
+                        systems::team::field_player::ReceiveBall_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::Dribble_enter::<BlueTeam>
+                        systems::team::field_player::ReceiveBall_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::SupportAttacker_enter::<RedTeam>
+                        systems::team::field_player::KickBall_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::SupportAttacker_enter::<BlueTeam>
+                        systems::team::field_player::KickBall_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::SupportAttacker_exit::<RedTeam>
+                        systems::team::field_player::Dribble_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::SupportAttacker_exit::<BlueTeam>
+                        systems::team::field_player::Dribble_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReturnToHomeRegion_enter::<RedTeam>
+                        systems::team::field_player::SupportAttacker_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReturnToHomeRegion_enter::<BlueTeam>
+                        systems::team::field_player::SupportAttacker_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReturnToHomeRegion_exit::<RedTeam>
-                            .label(Systems::StateExit)
+                        systems::team::field_player::ReturnToHomeRegion_enter::<RedTeam>
+                            .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::field_player::ReturnToHomeRegion_exit::<BlueTeam>
-                            .label(Systems::StateExit)
+                        systems::team::field_player::ReturnToHomeRegion_enter::<BlueTeam>
+                            .label(Systems::StateEnter)
                             .label(Systems::FieldPlayerStates)
                             .after(Systems::TeamStates),
                     )
                     // goal keeper systems
-                    .with_system(
-                        systems::team::goal_keeper::update::<RedTeam>
-                            .label(Systems::GoalKeeperUpdate)
-                            .after(Systems::TeamUpdate),
-                    )
-                    .with_system(
-                        systems::team::goal_keeper::update::<BlueTeam>
-                            .label(Systems::GoalKeeperUpdate)
-                            .after(Systems::TeamUpdate),
-                    )
-                    .with_system(
-                        systems::team::goal_keeper::GlobalState_on_message::<RedTeam>
-                            .label(Systems::GlobalStateOnMessage),
-                    )
-                    .with_system(
-                        systems::team::goal_keeper::GlobalState_on_message::<BlueTeam>
-                            .label(Systems::GlobalStateOnMessage),
-                    )
                     .with_system(
                         systems::team::goal_keeper::TendGoal_enter::<RedTeam>
                             .label(Systems::StateEnter)
@@ -549,82 +562,110 @@ impl Plugin for MainStatePlugin {
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::TendGoal_exit::<RedTeam>
+                        systems::team::goal_keeper::ReturnHome_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::TendGoal_exit::<BlueTeam>
+                        systems::team::goal_keeper::ReturnHome_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::ReturnHome_enter::<RedTeam>
+                        systems::team::goal_keeper::InterceptBall_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::ReturnHome_enter::<BlueTeam>
+                        systems::team::goal_keeper::InterceptBall_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::ReturnHome_exit::<RedTeam>
+                        systems::team::goal_keeper::PutBallBackInPlay_enter::<RedTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::ReturnHome_exit::<BlueTeam>
+                        systems::team::goal_keeper::PutBallBackInPlay_enter::<BlueTeam>
                             .label(Systems::StateEnter)
                             .label(Systems::GoalKeeperStates)
                             .after(Systems::TeamStates),
+                    ),
+            )
+            // per-frame systems
+            .add_system_set(
+                SystemSet::on_update(GameState::Main)
+                    // steering
+                    .with_system(systems::steering::update_debug)
+                    // team systems
+                    .with_system(systems::team::update::<RedTeam>.label(Systems::TeamUpdate))
+                    .with_system(systems::team::update::<BlueTeam>.label(Systems::TeamUpdate))
+                    // field player systems
+                    .with_system(
+                        systems::team::field_player::update::<RedTeam>
+                            .label(Systems::FieldPlayerUpdate)
+                            .after(Systems::TeamUpdate),
                     )
                     .with_system(
-                        systems::team::goal_keeper::InterceptBall_enter::<RedTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::GoalKeeperStates)
-                            .after(Systems::TeamStates),
+                        systems::team::field_player::update::<BlueTeam>
+                            .label(Systems::FieldPlayerUpdate)
+                            .after(Systems::TeamUpdate),
                     )
                     .with_system(
-                        systems::team::goal_keeper::InterceptBall_enter::<BlueTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::GoalKeeperStates)
-                            .after(Systems::TeamStates),
+                        systems::team::field_player::find_support_event_handler::<RedTeam>
+                            .label(Systems::FieldPlayerEvents)
+                            .after(Systems::FieldPlayerStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::InterceptBall_exit::<RedTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::GoalKeeperStates)
-                            .after(Systems::TeamStates),
+                        systems::team::field_player::find_support_event_handler::<BlueTeam>
+                            .label(Systems::FieldPlayerEvents)
+                            .after(Systems::FieldPlayerStates),
                     )
                     .with_system(
-                        systems::team::goal_keeper::InterceptBall_exit::<BlueTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::GoalKeeperStates)
-                            .after(Systems::TeamStates),
+                        systems::team::field_player::GlobalState_on_message::<RedTeam>
+                            .label(Systems::GlobalStateOnMessage),
                     )
                     .with_system(
-                        systems::team::goal_keeper::PutBallBackInPlay_enter::<RedTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::GoalKeeperStates)
-                            .after(Systems::TeamStates),
+                        systems::team::field_player::GlobalState_on_message::<BlueTeam>
+                            .label(Systems::GlobalStateOnMessage),
                     )
+                    // goal keeper systems
                     .with_system(
-                        systems::team::goal_keeper::PutBallBackInPlay_enter::<BlueTeam>
-                            .label(Systems::StateEnter)
-                            .label(Systems::GoalKeeperStates)
-                            .after(Systems::TeamStates),
+                        systems::team::goal_keeper::update::<RedTeam>
+                            .label(Systems::GoalKeeperUpdate)
+                            .after(Systems::TeamUpdate),
+                    )
+                    .with_system(
+                        systems::team::goal_keeper::update::<BlueTeam>
+                            .label(Systems::GoalKeeperUpdate)
+                            .after(Systems::TeamUpdate),
+                    )
+                    .with_system(
+                        systems::team::goal_keeper::GlobalState_on_message::<RedTeam>
+                            .label(Systems::GlobalStateOnMessage),
+                    )
+                    .with_system(
+                        systems::team::goal_keeper::GlobalState_on_message::<BlueTeam>
+                            .label(Systems::GlobalStateOnMessage),
                     )
                     // everything else
-                    .with_system(systems::ball::update)
                     .with_system(systems::goal::update::<RedTeam>.label(Systems::GoalUpdate))
                     .with_system(systems::goal::update::<BlueTeam>.label(Systems::GoalUpdate))
-                    .with_system(systems::goal_scored_event_handler.after(Systems::GoalUpdate)),
+                    .with_system(systems::goal_scored_event_handler.after(Systems::GoalUpdate))
+                    // stats
+                    .with_system(systems::stats::update)
+                    .with_system(systems::stats::message_event_handler::<RedTeam>)
+                    .with_system(systems::stats::message_event_handler::<BlueTeam>)
+                    .with_system(systems::stats::match_event_handler)
+                    .with_system(
+                        systems::stats::goal_scored_event_handler.after(Systems::GoalUpdate),
+                    ),
             )
             .add_system_set(
                 SystemSet::on_exit(GameState::Main).with_system(states::main::teardown),
@@ -0,0 +1,311 @@
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use serde::Deserialize;
+
+use crate::components::team::FieldPlayerRole;
+use crate::game::team::{TeamColor, MAX_TEAM_SIZE, MIN_TEAM_SIZE};
+use crate::resources::pitch::Pitch;
+
+pub const DEFAULT_FORMATION: &str = "default";
+
+// compiled in so there's always something valid to fall back to
+const BUILTIN_FORMATION: &str = include_str!("../../assets/formations/default.formation");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormationState {
+    KickOff,
+    Defending,
+    Attacking,
+}
+
+// home regions for a single player in each of the team states
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct FormationRegions {
+    pub kickoff: usize,
+    pub defending: usize,
+    pub attacking: usize,
+}
+
+impl FormationRegions {
+    pub fn region(&self, state: FormationState) -> usize {
+        match state {
+            FormationState::KickOff => self.kickoff,
+            FormationState::Defending => self.defending,
+            FormationState::Attacking => self.attacking,
+        }
+    }
+
+    fn regions(&self) -> [usize; 3] {
+        [self.kickoff, self.defending, self.attacking]
+    }
+
+    // pitch regions are laid out in reverse
+    // so mirroring a region across the center is just reversing its index
+    fn mirrored(&self, region_count: usize) -> Self {
+        Self {
+            kickoff: region_count - 1 - self.kickoff,
+            defending: region_count - 1 - self.defending,
+            attacking: region_count - 1 - self.attacking,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct FormationFieldPlayer {
+    pub role: FieldPlayerRole,
+    pub regions: FormationRegions,
+}
+
+// red team regions, used as-is rather than mirrored
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct FormationLayout {
+    pub goal_keeper: FormationRegions,
+    pub field_players: Vec<FormationFieldPlayer>,
+}
+
+// region indices are from the blue team's side of the pitch
+// and are mirrored for the red team unless it has its own layout
+#[derive(Debug, Default, Clone, Deserialize, TypeUuid)]
+#[uuid = "0a8e4d0c-2f3b-4c8e-9a51-6d3b7f2e1c94"]
+pub struct Formation {
+    pub name: String,
+
+    pub goal_keeper: FormationRegions,
+    pub field_players: Vec<FormationFieldPlayer>,
+
+    #[serde(default)]
+    pub red_team: Option<FormationLayout>,
+}
+
+impl Formation {
+    pub fn builtin() -> Self {
+        ron::from_str(BUILTIN_FORMATION).expect("invalid built-in formation")
+    }
+
+    // the goal keeper plus the field players
+    pub fn team_size(&self) -> usize {
+        self.field_players.len() + 1
+    }
+
+    pub fn validate(&self, pitch: &Pitch) -> Result<(), String> {
+        let team_size = self.team_size();
+        if !(MIN_TEAM_SIZE..=MAX_TEAM_SIZE).contains(&team_size) {
+            return Err(format!(
+                "team size {} is outside of {}-{} players",
+                team_size, MIN_TEAM_SIZE, MAX_TEAM_SIZE
+            ));
+        }
+
+        if let Some(red_team) = &self.red_team {
+            if red_team.field_players.len() != self.field_players.len() {
+                return Err(format!(
+                    "red team has {} field players, blue team has {}",
+                    red_team.field_players.len(),
+                    self.field_players.len()
+                ));
+            }
+        }
+
+        let region_count = pitch.regions.len();
+        let red_team = self.red_team.iter().flat_map(|red_team| {
+            std::iter::once(&red_team.goal_keeper)
+                .chain(red_team.field_players.iter().map(|player| &player.regions))
+        });
+        for (idx, regions) in std::iter::once(&self.goal_keeper)
+            .chain(self.field_players.iter().map(|player| &player.regions))
+            .enumerate()
+            .chain(red_team.enumerate())
+        {
+            for region in regions.regions() {
+                if region >= region_count {
+                    return Err(format!(
+                        "player {} home region {} is outside of the {} pitch regions",
+                        idx, region, region_count
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn for_team(&self, team_color: TeamColor, pitch: &Pitch) -> Self {
+        match team_color {
+            TeamColor::Blue => self.clone(),
+            TeamColor::Red => match &self.red_team {
+                Some(red_team) => Self {
+                    name: self.name.clone(),
+                    goal_keeper: red_team.goal_keeper,
+                    field_players: red_team.field_players.clone(),
+                    red_team: None,
+                },
+                None => {
+                    let region_count = pitch.regions.len();
+
+                    Self {
+                        name: self.name.clone(),
+                        goal_keeper: self.goal_keeper.mirrored(region_count),
+                        field_players: self
+                            .field_players
+                            .iter()
+                            .map(|player| FormationFieldPlayer {
+                                role: player.role,
+                                regions: player.regions.mirrored(region_count),
+                            })
+                            .collect(),
+                        red_team: None,
+                    }
+                }
+            },
+        }
+    }
+
+    pub fn field_player_home_region(&self, slot: usize, state: FormationState) -> usize {
+        self.field_players[slot].regions.region(state)
+    }
+}
+
+pub struct FormationAssets {
+    pub red_team: Handle<Formation>,
+    pub blue_team: Handle<Formation>,
+}
+
+impl FormationAssets {
+    pub fn team(&self, team_color: TeamColor) -> &Handle<Formation> {
+        match team_color {
+            TeamColor::Red => &self.red_team,
+            TeamColor::Blue => &self.blue_team,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resources::SimulationParams;
+
+    use super::*;
+
+    // 6x3 regions, the same as simulation.params
+    fn pitch() -> Pitch {
+        Pitch::new(&SimulationParams {
+            pitch_extents: Vec2::new(900.0, 450.0),
+            num_regions_horizontal: 6,
+            num_regions_vertical: 3,
+            ..Default::default()
+        })
+    }
+
+    fn regions(region: usize) -> FormationRegions {
+        FormationRegions {
+            kickoff: region,
+            defending: region,
+            attacking: region,
+        }
+    }
+
+    fn player(role: FieldPlayerRole, region: usize) -> FormationFieldPlayer {
+        FormationFieldPlayer {
+            role,
+            regions: regions(region),
+        }
+    }
+
+    fn formation() -> Formation {
+        Formation {
+            name: "Test".to_owned(),
+            goal_keeper: regions(1),
+            field_players: vec![
+                player(FieldPlayerRole::Defender, 6),
+                player(FieldPlayerRole::Attacker, 8),
+            ],
+            red_team: None,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let pitch = pitch();
+
+        assert!(formation().validate(&pitch).is_ok());
+        assert!(Formation::builtin().validate(&pitch).is_ok());
+    }
+
+    #[test]
+    fn test_validate_region_outside_grid() {
+        let pitch = pitch();
+
+        let mut formation = formation();
+        formation.field_players[1].regions.attacking = pitch.regions.len();
+        assert!(formation.validate(&pitch).is_err());
+
+        // the red team layout is checked too
+        let mut formation = self::formation();
+        formation.red_team = Some(FormationLayout {
+            goal_keeper: regions(pitch.regions.len()),
+            field_players: formation.field_players.clone(),
+        });
+        assert!(formation.validate(&pitch).is_err());
+    }
+
+    #[test]
+    fn test_validate_player_count() {
+        let pitch = pitch();
+
+        let mut formation = formation();
+        formation.field_players.truncate(1);
+        assert!(formation.validate(&pitch).is_err());
+
+        let mut formation = self::formation();
+        formation.field_players = vec![player(FieldPlayerRole::Attacker, 8); MAX_TEAM_SIZE];
+        assert!(formation.validate(&pitch).is_err());
+
+        // both teams need the same number of field players
+        let mut formation = self::formation();
+        formation.red_team = Some(FormationLayout {
+            goal_keeper: regions(16),
+            field_players: vec![player(FieldPlayerRole::Defender, 9)],
+        });
+        assert!(formation.validate(&pitch).is_err());
+    }
+
+    #[test]
+    fn test_for_team_mirrors_red() {
+        let pitch = pitch();
+        let formation = formation();
+
+        let blue = formation.for_team(TeamColor::Blue, &pitch);
+        assert_eq!(blue.goal_keeper.kickoff, 1);
+
+        // mirrored across the center of the 18 regions
+        let red = formation.for_team(TeamColor::Red, &pitch);
+        assert_eq!(red.goal_keeper.kickoff, 16);
+        assert_eq!(
+            red.field_player_home_region(0, FormationState::Defending),
+            11
+        );
+        assert_eq!(
+            red.field_player_home_region(1, FormationState::Attacking),
+            9
+        );
+        assert_eq!(red.field_players[1].role, FieldPlayerRole::Attacker);
+        assert!(red.red_team.is_none());
+    }
+
+    #[test]
+    fn test_for_team_red_layout() {
+        let pitch = pitch();
+        let formation = Formation::builtin();
+
+        // an explicit red layout is used as-is
+        let red = formation.for_team(TeamColor::Red, &pitch);
+        assert_eq!(red.goal_keeper.kickoff, 16);
+        assert_eq!(
+            red.field_player_home_region(0, FormationState::Attacking),
+            3
+        );
+        assert_eq!(
+            red.field_player_home_region(3, FormationState::Attacking),
+            13
+        );
+    }
+}
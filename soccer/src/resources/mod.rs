@@ -1,6 +1,7 @@
 pub mod debug;
-pub mod messaging;
+pub mod formation;
 pub mod pitch;
+pub mod stats;
 pub mod ui;
 
 use bevy::prelude::*;
@@ -31,12 +32,15 @@ pub struct SimulationParams {
     pub player_max_force: f32,
     pub player_max_speed_without_ball: f32,
     pub player_max_speed_with_ball: f32,
+    // radians per second
     pub player_max_turn_rate: f32,
     pub player_kick_frequency: usize,
     pub player_kick_accuracy: f32,
 
     // ball
     pub ball_mass: f32,
+    // how much of the ball's speed into a wall is kept when it bounces off
+    pub ball_restitution: f32,
     //pub ball_max_force: f32,
     //pub ball_max_speed: f32,
 
@@ -0,0 +1,214 @@
+use serde::Serialize;
+
+use crate::game::team::TeamColor;
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TeamStats {
+    pub passes_attempted: usize,
+    pub passes_completed: usize,
+    pub passes_requested: usize,
+
+    pub shots: usize,
+    pub shots_on_target: usize,
+
+    pub saves: usize,
+    pub interceptions: usize,
+
+    pub possession_seconds: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalRecord {
+    pub team: TeamColor,
+    pub seconds: f64,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MatchStats {
+    pub red_team: TeamStats,
+    pub blue_team: TeamStats,
+
+    pub goals: Vec<GoalRecord>,
+
+    // time since startup that the match started
+    #[serde(skip)]
+    pub started: f64,
+
+    // the team that last gained control of the ball
+    #[serde(skip)]
+    pub possession: Option<TeamColor>,
+
+    // the team with a shot in flight
+    // used to tell saves from interceptions
+    #[serde(skip)]
+    pub pending_shot: Option<TeamColor>,
+}
+
+impl MatchStats {
+    pub fn new(started: f64) -> Self {
+        Self {
+            started,
+            ..Default::default()
+        }
+    }
+
+    pub fn team(&self, team: TeamColor) -> &TeamStats {
+        match team {
+            TeamColor::Red => &self.red_team,
+            TeamColor::Blue => &self.blue_team,
+        }
+    }
+
+    pub fn team_mut(&mut self, team: TeamColor) -> &mut TeamStats {
+        match team {
+            TeamColor::Red => &mut self.red_team,
+            TeamColor::Blue => &mut self.blue_team,
+        }
+    }
+
+    pub fn gain_possession(&mut self, team: TeamColor) {
+        self.possession = Some(team);
+    }
+
+    pub fn add_possession_time(&mut self, seconds: f32) {
+        if let Some(possession) = self.possession {
+            self.team_mut(possession).possession_seconds += seconds;
+        }
+    }
+
+    pub fn pass_requested(&mut self, team: TeamColor) {
+        self.team_mut(team).passes_requested += 1;
+    }
+
+    pub fn pass_attempted(&mut self, team: TeamColor) {
+        self.team_mut(team).passes_attempted += 1;
+        self.pending_shot = None;
+    }
+
+    pub fn pass_completed(&mut self, team: TeamColor) {
+        self.team_mut(team).passes_completed += 1;
+    }
+
+    pub fn shot(&mut self, team: TeamColor, on_target: bool) {
+        let team_stats = self.team_mut(team);
+        team_stats.shots += 1;
+        if on_target {
+            team_stats.shots_on_target += 1;
+        }
+
+        self.pending_shot = Some(team);
+    }
+
+    // the keeper has the ball now either way
+    pub fn goal_keeper_trap(&mut self, team: TeamColor, intercepting: bool) {
+        // trapping an opponent's shot is a save,
+        // anything else picked off while intercepting is an interception
+        if self.pending_shot.is_some_and(|shooter| shooter != team) {
+            self.team_mut(team).saves += 1;
+        } else if intercepting {
+            self.team_mut(team).interceptions += 1;
+        }
+
+        self.pending_shot = None;
+        self.gain_possession(team);
+    }
+
+    pub fn goal_scored(&mut self, team: TeamColor, now: f64) {
+        self.goals.push(GoalRecord {
+            team,
+            seconds: now - self.started,
+        });
+
+        self.pending_shot = None;
+    }
+
+    pub fn possession_percent(&self, team: TeamColor) -> f32 {
+        let total = self.red_team.possession_seconds + self.blue_team.possession_seconds;
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        self.team(team).possession_seconds / total * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completed_pass() {
+        let mut stats = MatchStats::default();
+        stats.gain_possession(TeamColor::Red);
+
+        stats.pass_requested(TeamColor::Red);
+        stats.pass_attempted(TeamColor::Red);
+        stats.pass_completed(TeamColor::Red);
+        stats.add_possession_time(2.0);
+
+        let red = stats.team(TeamColor::Red);
+        assert_eq!(red.passes_requested, 1);
+        assert_eq!(red.passes_attempted, 1);
+        assert_eq!(red.passes_completed, 1);
+        assert_eq!(red.possession_seconds, 2.0);
+        assert_eq!(stats.team(TeamColor::Blue).passes_attempted, 0);
+        assert_eq!(stats.possession_percent(TeamColor::Red), 100.0);
+    }
+
+    #[test]
+    fn test_interception() {
+        let mut stats = MatchStats::default();
+        stats.gain_possession(TeamColor::Red);
+        stats.add_possession_time(3.0);
+
+        // red's pass is picked off by the blue keeper
+        stats.pass_attempted(TeamColor::Red);
+        stats.goal_keeper_trap(TeamColor::Blue, true);
+        stats.add_possession_time(1.0);
+
+        assert_eq!(stats.possession, Some(TeamColor::Blue));
+        assert_eq!(stats.team(TeamColor::Blue).interceptions, 1);
+        assert_eq!(stats.team(TeamColor::Blue).saves, 0);
+        assert_eq!(stats.team(TeamColor::Red).passes_completed, 0);
+        assert_eq!(stats.possession_percent(TeamColor::Red), 75.0);
+        assert_eq!(stats.possession_percent(TeamColor::Blue), 25.0);
+
+        // just picking up a loose ball isn't an interception
+        stats.goal_keeper_trap(TeamColor::Blue, false);
+        assert_eq!(stats.team(TeamColor::Blue).interceptions, 1);
+    }
+
+    #[test]
+    fn test_save() {
+        let mut stats = MatchStats::default();
+
+        stats.shot(TeamColor::Red, true);
+        stats.goal_keeper_trap(TeamColor::Blue, true);
+
+        let red = stats.team(TeamColor::Red);
+        assert_eq!(red.shots, 1);
+        assert_eq!(red.shots_on_target, 1);
+        assert_eq!(stats.team(TeamColor::Blue).saves, 1);
+        assert_eq!(stats.team(TeamColor::Blue).interceptions, 0);
+        assert_eq!(stats.pending_shot, None);
+
+        // a pass after the shot means the keeper didn't save it
+        stats.shot(TeamColor::Red, false);
+        stats.pass_attempted(TeamColor::Red);
+        stats.goal_keeper_trap(TeamColor::Blue, true);
+        assert_eq!(stats.team(TeamColor::Blue).saves, 1);
+        assert_eq!(stats.team(TeamColor::Blue).interceptions, 1);
+    }
+
+    #[test]
+    fn test_goal_scored() {
+        let mut stats = MatchStats::new(10.0);
+
+        stats.shot(TeamColor::Blue, true);
+        stats.goal_scored(TeamColor::Blue, 25.0);
+
+        assert_eq!(stats.goals.len(), 1);
+        assert_eq!(stats.goals[0].seconds, 15.0);
+        assert_eq!(stats.pending_shot, None);
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+#[derive(Default)]
 pub struct Fonts {
     pub normal: Handle<Font>,
 }
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use game_ai_core::partition::*;
 
 use crate::bundles::ball::*;
 use crate::bundles::goal::*;
@@ -7,7 +8,9 @@ use crate::bundles::team::*;
 use crate::components::camera::*;
 use crate::components::team::*;
 use crate::game::team::*;
+use crate::resources::formation::*;
 use crate::resources::pitch::*;
+use crate::resources::stats::*;
 use crate::resources::ui::*;
 use crate::resources::*;
 
@@ -15,7 +18,10 @@ pub fn setup(
     mut commands: Commands,
     params_asset: Res<SimulationParamsAsset>,
     mut params_assets: ResMut<Assets<SimulationParams>>,
+    formation_assets: Res<FormationAssets>,
+    formations: Res<Assets<Formation>>,
     fonts: Res<Fonts>,
+    time: Res<Time>,
 ) {
     debug!("entering main state");
 
@@ -56,6 +62,7 @@ pub fn setup(
 
     // game state
     commands.insert_resource(GameState::default());
+    commands.insert_resource(MatchStats::new(time.seconds_since_startup()));
 
     let pitch = Pitch::new(params);
 
@@ -70,8 +77,42 @@ pub fn setup(
     BallBundle::spawn(&mut commands, params, Vec2::ZERO);
 
     // teams
-    SoccerTeamBundle::<RedTeam>::spawn(&mut commands, params, &fonts, &pitch);
-    SoccerTeamBundle::<BlueTeam>::spawn(&mut commands, params, &fonts, &pitch);
+    let get_formation = |team_color: TeamColor| {
+        let formation = formations.get(formation_assets.team(team_color)).unwrap();
+        match formation.validate(&pitch) {
+            Ok(()) => formation.clone(),
+            Err(err) => {
+                error!(
+                    "invalid {:?} team formation '{}', using the built-in default: {}",
+                    team_color, formation.name, err
+                );
+                Formation::builtin()
+            }
+        }
+    };
+
+    SoccerTeamBundle::<RedTeam>::spawn(
+        &mut commands,
+        params,
+        &fonts,
+        &pitch,
+        &get_formation(TeamColor::Red),
+    );
+    SoccerTeamBundle::<BlueTeam>::spawn(
+        &mut commands,
+        params,
+        &fonts,
+        &pitch,
+        &get_formation(TeamColor::Blue),
+    );
+
+    // players are bucketed by the same grid as the pitch regions
+    commands.insert_resource(CellSpacePartition::new(
+        -pitch.extents * 0.5,
+        pitch.extents * 0.5,
+        params.num_regions_horizontal,
+        params.num_regions_vertical,
+    ));
 
     commands.insert_resource(pitch);
 
@@ -89,7 +130,9 @@ pub fn teardown(mut commands: Commands, entities: Query<Entity>) {
 
     commands.remove_resource::<GoalKeeperMessageDispatcher>();
     commands.remove_resource::<FieldPlayerMessageDispatcher>();
+    commands.remove_resource::<CellSpacePartition>();
     commands.remove_resource::<Pitch>();
+    commands.remove_resource::<MatchStats>();
     commands.remove_resource::<GameState>();
     commands.remove_resource::<ClearColor>();
 }
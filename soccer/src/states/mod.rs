@@ -1,6 +1,7 @@
 pub mod intro;
 pub mod main;
 
+use bevy::ecs::schedule::ShouldRun;
 use bevy::prelude::*;
 
 use crate::resources::ui::*;
@@ -68,3 +69,13 @@ fn spawn_button(parent: &mut ChildBuilder, fonts: &Fonts, text: impl Into<String
                 });
         });
 }
+
+// run criteria for the state machine stages,
+// which don't have the game state driver
+pub fn in_main_state(state: Res<State<GameState>>) -> ShouldRun {
+    if state.current() == &GameState::Main {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
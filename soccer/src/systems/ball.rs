@@ -5,10 +5,23 @@ use crate::components::obstacle::*;
 use crate::components::physics::*;
 use crate::resources::*;
 
-pub fn update(mut ball: Query<&Ball>, walls: Query<WallQuery>) {
-    let ball = ball.single_mut();
+pub fn update(
+    params_asset: Res<SimulationParamsAsset>,
+    params_assets: Res<Assets<SimulationParams>>,
+    mut ball: Query<(&Ball, PhysicalQueryUpdateMut, &BoundingCircle)>,
+    walls: Query<WallQuery, Without<Ball>>,
+) {
+    let params = params_assets.get(&params_asset.handle).unwrap();
+
+    let (ball, mut physical, bounds) = ball.single_mut();
 
-    ball.test_collision_with_walls(walls.iter());
+    ball.test_collision_with_walls(
+        params,
+        &mut physical.physical,
+        &mut physical.transform,
+        bounds.radius,
+        walls.iter(),
+    );
 }
 
 pub fn update_physics(
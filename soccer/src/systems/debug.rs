@@ -4,7 +4,11 @@ use bevy_egui::{egui, EguiContext};
 use bevy_inspector_egui::WorldInspectorParams;
 
 use crate::events::debug::*;
+use crate::game::team::TeamColor;
 use crate::resources::debug::*;
+use crate::resources::stats::*;
+
+const STATS_EXPORT_PATH: &str = "match_stats.json";
 
 pub(crate) fn setup_debug(mut commands: Commands) {
     commands.insert_resource(DebugState::default());
@@ -47,12 +51,67 @@ fn fps(diagnostics: &Diagnostics, dt: f64) -> (f64, f64) {
     (fps, frame_time)
 }
 
+fn stats_ui(ui: &mut egui::Ui, stats: &MatchStats) {
+    egui::Grid::new("Match Stats").show(ui, |ui| {
+        ui.label("");
+        ui.label("Red");
+        ui.label("Blue");
+        ui.end_row();
+
+        let mut row = |label: &str, value: &dyn Fn(TeamColor) -> String| {
+            ui.label(label);
+            ui.label(value(TeamColor::Red));
+            ui.label(value(TeamColor::Blue));
+            ui.end_row();
+        };
+
+        row("Possession", &|team| {
+            format!("{:.0}%", stats.possession_percent(team))
+        });
+        row("Passes", &|team| {
+            let team = stats.team(team);
+            format!("{} / {}", team.passes_completed, team.passes_attempted)
+        });
+        row("Pass requests", &|team| {
+            stats.team(team).passes_requested.to_string()
+        });
+        row("Shots", &|team| {
+            let team = stats.team(team);
+            format!("{} ({} on target)", team.shots, team.shots_on_target)
+        });
+        row("Saves", &|team| stats.team(team).saves.to_string());
+        row("Interceptions", &|team| {
+            stats.team(team).interceptions.to_string()
+        });
+    });
+
+    for goal in &stats.goals {
+        ui.label(format!("{:?} goal at {:.1}s", goal.team, goal.seconds));
+    }
+}
+
+fn export_stats(stats: &MatchStats) {
+    let json = match serde_json::to_string_pretty(stats) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("failed to serialize match stats: {}", err);
+            return;
+        }
+    };
+
+    match std::fs::write(STATS_EXPORT_PATH, json) {
+        Ok(_) => info!("exported match stats to {}", STATS_EXPORT_PATH),
+        Err(err) => error!("failed to export match stats: {}", err),
+    }
+}
+
 pub fn debug_ui(
     debug_state: Res<DebugState>,
     mut context: ResMut<EguiContext>,
     mut inspector: ResMut<WorldInspectorParams>,
     time: Res<Time>,
     diagnostics: Res<Diagnostics>,
+    stats: Option<Res<MatchStats>>,
 ) {
     if !debug_state.enabled {
         return;
@@ -71,6 +130,16 @@ pub fn debug_ui(
             if ui.button("Inspector").clicked() {
                 inspector.enabled = !inspector.enabled;
             }
+
+            if let Some(stats) = stats {
+                ui.separator();
+
+                stats_ui(ui, &stats);
+
+                if ui.button("Export Stats").clicked() {
+                    export_stats(&stats);
+                }
+            }
         });
     });
 }
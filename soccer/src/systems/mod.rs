@@ -1,12 +1,13 @@
 pub mod ball;
 pub mod debug;
 pub mod goal;
-pub mod messaging;
 pub mod physics;
+pub mod stats;
 pub mod steering;
 pub mod team;
 
 use bevy::prelude::*;
+use game_ai_core::partition::*;
 
 use crate::components::ball::*;
 use crate::components::physics::*;
@@ -18,6 +19,8 @@ use crate::resources::*;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
 pub enum Systems {
     Physics,
+    BallCollision,
+    Partition,
 
     // steering
     Steering,
@@ -42,6 +45,16 @@ pub enum Systems {
     GoalUpdate,
 }
 
+pub fn update_partition(
+    mut partition: ResMut<CellSpacePartition>,
+    players: Query<(Entity, &Transform), With<SoccerPlayer>>,
+) {
+    partition.clear();
+    for (entity, transform) in players.iter() {
+        partition.insert(entity, transform.translation.truncate());
+    }
+}
+
 pub fn goal_scored_event_handler(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
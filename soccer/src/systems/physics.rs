@@ -10,13 +10,13 @@ pub fn update(_time: Res<Time>, mut physicals: Query<PhysicalQueryUpdateMut>) {
     }
 }
 
-pub fn facing(_time: Res<Time>, mut query: Query<PhysicalQueryUpdateMut>) {
+pub fn facing(mut query: Query<PhysicalQueryUpdateMut>) {
     for mut physical in query.iter_mut() {
-        if physical.physical.heading.length_squared() < std::f32::EPSILON {
+        if physical.physical.smoothed_heading.length_squared() < f32::EPSILON {
             continue;
         }
 
-        let angle = -physical.physical.heading.angle_between(Vec2::Y);
+        let angle = -physical.physical.smoothed_heading.angle_between(Vec2::Y);
         physical.transform.rotation = Quat::from_rotation_z(angle);
     }
 }
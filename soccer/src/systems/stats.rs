@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::components::team::*;
+use crate::events::*;
+use crate::game::team::*;
+use crate::resources::stats::*;
+use crate::resources::*;
+
+pub fn update(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mut stats: ResMut<MatchStats>,
+    red_gained: Query<(), (With<RedTeam>, Added<ControllingPlayer>)>,
+    blue_gained: Query<(), (With<BlueTeam>, Added<ControllingPlayer>)>,
+) {
+    if !red_gained.is_empty() {
+        stats.gain_possession(TeamColor::Red);
+    } else if !blue_gained.is_empty() {
+        stats.gain_possession(TeamColor::Blue);
+    }
+
+    if !game_state.is_game_on() {
+        return;
+    }
+
+    stats.add_possession_time(time.delta_seconds());
+}
+
+pub fn message_event_handler<T>(
+    mut stats: ResMut<MatchStats>,
+    mut events: EventReader<FieldPlayerDispatchedMessageEvent>,
+    players: Query<&T>,
+) where
+    T: TeamColorMarker,
+{
+    for event in events.iter() {
+        match event.message {
+            FieldPlayerMessage::ReceiveBall(_) => {
+                if let Some(team) = event
+                    .receiver
+                    .and_then(|receiver| players.get(receiver).ok())
+                {
+                    stats.pass_attempted(team.team_color());
+                }
+            }
+            FieldPlayerMessage::PassToMe(requester, _) => {
+                if let Ok(team) = players.get(requester) {
+                    stats.pass_requested(team.team_color());
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+pub fn match_event_handler(mut stats: ResMut<MatchStats>, mut events: EventReader<MatchEvent>) {
+    for event in events.iter() {
+        match *event {
+            MatchEvent::Shot { team, on_target } => stats.shot(team, on_target),
+            MatchEvent::PassCompleted(team) => stats.pass_completed(team),
+            MatchEvent::GoalKeeperTrap { team, intercepting } => {
+                stats.goal_keeper_trap(team, intercepting)
+            }
+        }
+    }
+}
+
+pub fn goal_scored_event_handler(
+    time: Res<Time>,
+    mut stats: ResMut<MatchStats>,
+    mut events: EventReader<GoalScoredEvent>,
+) {
+    for event in events.iter() {
+        stats.goal_scored(event.0, time.seconds_since_startup());
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use game_ai_core::partition::*;
 
 use crate::components::physics::*;
 use crate::components::steering::*;
@@ -7,12 +8,6 @@ use crate::resources::*;
 use crate::util::*;
 use crate::DEBUG_SORT;
 
-pub fn update(mut steering_behaviors: Query<SteeringQueryMut>) {
-    for mut steering in steering_behaviors.iter_mut() {
-        steering.steering.update(&mut steering.physical);
-    }
-}
-
 pub fn update_debug(
     agents: Query<(&Steering, &Children), Without<SteeringTargetDebug>>,
     mut steering_debug: Query<TransformQueryMut, With<SteeringTargetDebug>>,
@@ -37,7 +32,9 @@ pub fn update_seek(
     let params = params_assets.get(&params_asset.handle).unwrap();
 
     for (mut steering, physical) in seeking.iter_mut() {
-        let force = steering.seek.force(&steering.steering, &physical);
+        let force = steering
+            .seek
+            .force(&steering.steering, physical.transform, physical.physical);
         steering
             .steering
             .accumulate_force(physical.physical, force, params.seek_weight);
@@ -52,7 +49,10 @@ pub fn update_arrive(
     let params = params_assets.get(&params_asset.handle).unwrap();
 
     for (mut steering, physical) in arriving.iter_mut() {
-        let force = steering.arrive.force(&steering.steering, &physical);
+        let force =
+            steering
+                .arrive
+                .force(&steering.steering, physical.transform, physical.physical);
         steering
             .steering
             .accumulate_force(physical.physical, force, params.arrive_weight);
@@ -98,15 +98,23 @@ pub fn update_interpose(
 pub fn update_separation(
     params_asset: Res<SimulationParamsAsset>,
     params_assets: ResMut<Assets<SimulationParams>>,
+    partition: Res<CellSpacePartition>,
     mut separating: Query<(Entity, SoccerPlayerSeparationQueryMut, PhysicalQuery)>,
     players: Query<(Entity, &Transform), With<SoccerPlayer>>,
 ) {
     let params = params_assets.get(&params_asset.handle).unwrap();
 
     for (entity, mut steering, physical) in separating.iter_mut() {
+        let neighbors = partition
+            .query(
+                physical.transform.translation.truncate(),
+                params.view_distance,
+            )
+            .filter_map(|player| players.get(player).ok());
+
         let force = steering
             .separation
-            .force(params, entity, physical.transform, players.iter());
+            .force(params, entity, physical.transform, neighbors);
         steering
             .steering
             .accumulate_force(physical.physical, force, params.separation_weight);
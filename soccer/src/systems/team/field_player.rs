@@ -1,6 +1,8 @@
 #![allow(non_snake_case)]
 
 use bevy::prelude::*;
+use game_ai_core::partition::*;
+use game_ai_core::random::Random;
 use rand::Rng;
 
 use crate::components::ball::*;
@@ -45,6 +47,7 @@ pub fn find_support_event_handler<T>(
     mut commands: Commands,
     params_asset: Res<SimulationParamsAsset>,
     params_assets: ResMut<Assets<SimulationParams>>,
+    mut rng: ResMut<Random>,
     mut message_dispatcher: ResMut<FieldPlayerMessageDispatcher>,
     mut events: EventReader<FindSupportEvent>,
     players: Query<&SoccerPlayer, With<T>>,
@@ -58,6 +61,8 @@ pub fn find_support_event_handler<T>(
 ) where
     T: TeamColorMarker,
 {
+    let rng = rng.stream(("find_support_event_handler", std::any::type_name::<T>()));
+
     let params = params_assets.get(&params_asset.handle).unwrap();
 
     let (mut team, mut support_calculator) = team.single_mut();
@@ -76,6 +81,7 @@ pub fn find_support_event_handler<T>(
             player.find_support(
                 &mut commands,
                 params,
+                rng,
                 &mut message_dispatcher,
                 &mut team,
                 &mut support_calculator,
@@ -147,9 +153,11 @@ pub fn GlobalState_on_message<T>(
     let ball_position = ball_physical.transform.translation.truncate();
 
     for event in message_events.iter() {
-        if let Ok((entity, mut field_player, transform)) =
-            field_players.get_mut(event.receiver.unwrap())
-        {
+        for (entity, mut field_player, transform) in field_players.iter_mut() {
+            if !event.is_for(entity) {
+                continue;
+            }
+
             match event.message {
                 FieldPlayerMessage::ReceiveBall(position) => {
                     field_player.steering.target = position;
@@ -165,7 +173,7 @@ pub fn GlobalState_on_message<T>(
                         .state_machine
                         .is_in_state(FieldPlayerState::SupportAttacker)
                     {
-                        return;
+                        continue;
                     }
 
                     field_player.steering.target = team.team.best_support_spot.unwrap();
@@ -212,7 +220,7 @@ pub fn GlobalState_on_message<T>(
                             "{} cannot make request pass <cannot kick ball>",
                             field_player.name
                         );
-                        return;
+                        continue;
                     }
 
                     ball.kick(
@@ -379,7 +387,7 @@ pub fn Wait_execute<T>(
         // get back to our home if we got bumped off it
         if !field_player
             .steering
-            .is_at_target(params, physical.transform)
+            .is_at_target(params.player_in_target_range_squared, physical.transform)
         {
             if arrive.is_none() {
                 info!("{} heading back home", field_player.name);
@@ -450,7 +458,9 @@ pub fn ReceiveBall_enter<T>(
     mut commands: Commands,
     params_asset: Res<SimulationParamsAsset>,
     params_assets: Res<Assets<SimulationParams>>,
+    mut rng: ResMut<Random>,
     pitch: Res<Pitch>,
+    partition: Res<CellSpacePartition>,
     field_player: Query<
         (Entity, FieldPlayerQuery<T>, &Transform),
         With<FieldPlayerStateReceiveBallEnter>,
@@ -463,6 +473,8 @@ pub fn ReceiveBall_enter<T>(
 ) where
     T: TeamColorMarker,
 {
+    let rng = rng.stream(("ReceiveBall_enter", std::any::type_name::<T>()));
+
     if let Some((entity, field_player, transform)) = field_player.optional_single() {
         let params = params_assets.get(&params_asset.handle).unwrap();
 
@@ -478,8 +490,6 @@ pub fn ReceiveBall_enter<T>(
                 .remove::<ReceivingPlayer>();
         }
 
-        let mut rng = rand::thread_rng();
-
         // this player is now the receiver / controller
         commands
             .entity(entity)
@@ -494,7 +504,9 @@ pub fn ReceiveBall_enter<T>(
             && rng.gen::<f32>() < params.chance_of_using_arrive_type_receive_behavior
             && !field_player.player.is_opponent_within_radius::<T, _>(
                 transform,
-                opponents.iter(),
+                partition
+                    .query(transform.translation.truncate(), params.pass_threat_radius)
+                    .filter_map(|opponent| opponents.get(opponent).ok()),
                 params.pass_threat_radius,
             )
         {
@@ -522,6 +534,7 @@ pub fn ReceiveBall_execute<T>(
         ),
         With<FieldPlayerStateReceiveBallExecute>,
     >,
+    mut match_events: EventWriter<MatchEvent>,
     controlling: Query<ControllingPlayerQuery<T>>,
     ball: Query<&Transform, With<Ball>>,
 ) where
@@ -535,12 +548,16 @@ pub fn ReceiveBall_execute<T>(
         let ball_position = ball.single().translation.truncate();
 
         // chase the ball if it's close enough
-        if field_player.field_player.is_ball_within_receiving_range(
+        let received = field_player.field_player.is_ball_within_receiving_range(
             params,
             physical.transform,
             ball_position,
-        ) || controlling.optional_single().is_none()
-        {
+        );
+        if received {
+            match_events.send(MatchEvent::PassCompleted(field_player.team.team_color()));
+        }
+
+        if received || controlling.optional_single().is_none() {
             field_player.state_machine.change_state(
                 &mut commands,
                 entity,
@@ -557,7 +574,7 @@ pub fn ReceiveBall_execute<T>(
         // stop if we've arrived
         if field_player
             .steering
-            .is_at_target(params, physical.transform)
+            .is_at_target(params.player_in_target_range_squared, physical.transform)
         {
             field_player.agent.arrive_off(&mut commands, entity);
             field_player.agent.pursuit_off(&mut commands, entity);
@@ -623,8 +640,11 @@ pub fn KickBall_execute<T>(
     mut commands: Commands,
     params_asset: Res<SimulationParamsAsset>,
     params_assets: Res<Assets<SimulationParams>>,
+    mut rng: ResMut<Random>,
     mut message_dispatcher: ResMut<FieldPlayerMessageDispatcher>,
     mut find_support_events: EventWriter<FindSupportEvent>,
+    mut match_events: EventWriter<MatchEvent>,
+    partition: Res<CellSpacePartition>,
     mut field_player: Query<
         (
             Entity,
@@ -647,6 +667,8 @@ pub fn KickBall_execute<T>(
 ) where
     T: TeamColorMarker,
 {
+    let rng = rng.stream(("KickBall_execute", std::any::type_name::<T>()));
+
     if let Some((entity, mut field_player, physical, bounds)) = field_player.optional_single_mut() {
         let params = params_assets.get(&params_asset.handle).unwrap();
 
@@ -678,12 +700,11 @@ pub fn KickBall_execute<T>(
         let team = team.single();
         let opponent_goal = opponent_goal.single();
 
-        let mut rng = rand::thread_rng();
-
         // attempt a kick
         let power = params.max_shooting_force * dot;
         let (mut ball_target, can_shoot) = team.team.can_shoot::<T, _, _>(
             params,
+            rng,
             ball_position,
             &opponent_goal,
             (&ball_physical.physical, ball_bounds),
@@ -693,7 +714,12 @@ pub fn KickBall_execute<T>(
         if can_shoot || rng.gen::<f32>() < params.chance_player_attempts_pot_shot {
             info!("{} attempts a shot at {}", field_player.name, ball_target);
 
-            ball_target = ball.add_noise_to_kick(params, ball_physical.transform, ball_target);
+            match_events.send(MatchEvent::Shot {
+                team: team.color.team_color(),
+                on_target: can_shoot,
+            });
+
+            ball_target = ball.add_noise_to_kick(params, rng, ball_physical.transform, ball_target);
             let direction = ball_target - ball_position;
             ball.kick(&mut ball_physical.physical, direction, power);
 
@@ -711,13 +737,19 @@ pub fn KickBall_execute<T>(
             params,
             physical.transform,
             physical.physical,
-            opponents.iter(),
+            partition
+                .query(position, params.player_comfort_zone)
+                .filter_map(|opponent| opponents.get(opponent).ok()),
         ) {
             let (receiver, mut ball_target) = team.team.find_pass::<T, _, _, _>(
                 params,
                 (entity, physical.transform),
                 teammates.iter(),
-                || opponents.iter(),
+                |position, radius| {
+                    partition
+                        .query(position, radius)
+                        .filter_map(|opponent| opponents.get(opponent).ok())
+                },
                 &opponent_goal,
                 (
                     &ball_physical.physical,
@@ -728,7 +760,8 @@ pub fn KickBall_execute<T>(
                 params.min_pass_distance,
             );
             if let Some(receiver) = receiver {
-                ball_target = ball.add_noise_to_kick(params, ball_physical.transform, ball_target);
+                ball_target =
+                    ball.add_noise_to_kick(params, rng, ball_physical.transform, ball_target);
                 let direction = ball_target - ball_position;
                 ball.kick(&mut ball_physical.physical, direction, power);
 
@@ -854,7 +887,9 @@ pub fn SupportAttacker_execute<T>(
     mut commands: Commands,
     params_asset: Res<SimulationParamsAsset>,
     params_assets: Res<Assets<SimulationParams>>,
+    mut rng: ResMut<Random>,
     mut player_message_dispatcher: ResMut<FieldPlayerMessageDispatcher>,
+    partition: Res<CellSpacePartition>,
     mut field_player: Query<
         (Entity, FieldPlayerQueryMut<T>, PhysicalQueryMut),
         With<FieldPlayerStateSupportAttackerExecute>,
@@ -867,6 +902,8 @@ pub fn SupportAttacker_execute<T>(
 ) where
     T: TeamColorMarker,
 {
+    let rng = rng.stream(("SupportAttacker_execute", std::any::type_name::<T>()));
+
     if let Some((entity, mut field_player, mut physical)) = field_player.optional_single_mut() {
         let params = params_assets.get(&params_asset.handle).unwrap();
 
@@ -898,6 +935,7 @@ pub fn SupportAttacker_execute<T>(
         // if we can shoot, request a pass
         let (_, can_shoot) = team.team.can_shoot::<T, _, _>(
             params,
+            rng,
             ball_position,
             &opponent_goal,
             (ball_physical.physical, ball_bounds),
@@ -919,7 +957,7 @@ pub fn SupportAttacker_execute<T>(
 
         if field_player
             .steering
-            .is_at_target(params, physical.transform)
+            .is_at_target(params.player_in_target_range_squared, physical.transform)
         {
             field_player.agent.arrive_off(&mut commands, entity);
 
@@ -934,7 +972,12 @@ pub fn SupportAttacker_execute<T>(
                     params,
                     physical.transform,
                     &physical.physical,
-                    opponents.iter(),
+                    partition
+                        .query(
+                            physical.transform.translation.truncate(),
+                            params.player_comfort_zone,
+                        )
+                        .filter_map(|opponent| opponents.get(opponent).ok()),
                 )
             {
                 team.team.request_pass::<T, _>(
@@ -1045,7 +1088,10 @@ pub fn ReturnToHomeRegion_execute<T>(
                     FieldPlayerState::Wait,
                 );
             }
-        } else if field_player.steering.is_at_target(params, transform) {
+        } else if field_player
+            .steering
+            .is_at_target(params.player_in_target_range_squared, transform)
+        {
             field_player
                 .state_machine
                 .change_state(&mut commands, entity, FieldPlayerState::Wait);
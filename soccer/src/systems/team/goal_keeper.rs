@@ -1,11 +1,13 @@
 #![allow(non_snake_case)]
 
 use bevy::prelude::*;
+use game_ai_core::partition::*;
 
 use crate::components::ball::*;
 use crate::components::goal::*;
 use crate::components::physics::*;
 use crate::components::team::*;
+use crate::events::*;
 use crate::game::team::*;
 use crate::resources::pitch::*;
 use crate::resources::*;
@@ -39,7 +41,7 @@ pub fn GlobalState_on_message<T>(
 {
     for event in message_events.iter() {
         if let Some((entity, mut _goal_keeper)) = goal_keeper.optional_single_mut() {
-            if entity != event.receiver.unwrap() {
+            if !event.is_for(entity) {
                 continue;
             }
 
@@ -105,6 +107,7 @@ pub fn TendGoal_execute<T>(
         (Entity, GoalKeeperQueryMut<T>, PhysicalQuery),
         (With<GoalKeeperStateTendGoalExecute>, Without<Ball>),
     >,
+    mut match_events: EventWriter<MatchEvent>,
     goal: Query<TeamGoalQuery<T>>,
     mut ball: Query<PhysicalQueryMut, With<Ball>>,
     controlling: Query<ControllingPlayerQuery<T>>,
@@ -132,6 +135,11 @@ pub fn TendGoal_execute<T>(
         ) {
             ball_physical.physical.velocity = Vec2::ZERO;
 
+            match_events.send(MatchEvent::GoalKeeperTrap {
+                team: goal_keeper.team.team_color(),
+                intercepting: false,
+            });
+
             if let Some(controlling) = controlling.optional_single() {
                 commands
                     .entity(controlling.entity)
@@ -278,6 +286,7 @@ pub fn InterceptBall_execute<T>(
         ),
         (With<GoalKeeperStateInterceptBallExecute>, Without<Ball>),
     >,
+    mut match_events: EventWriter<MatchEvent>,
     controlling: Query<ControllingPlayerQuery<T>>,
     closest_opponent: Query<&Transform, (With<ClosestPlayer>, Without<T>)>,
     goal: Query<TeamGoalQuery<T>>,
@@ -333,6 +342,11 @@ pub fn InterceptBall_execute<T>(
         ) {
             ball_physical.physical.velocity = Vec2::ZERO;
 
+            match_events.send(MatchEvent::GoalKeeperTrap {
+                team: goal_keeper.team.team_color(),
+                intercepting: true,
+            });
+
             if let Some(controlling) = controlling.optional_single() {
                 commands
                     .entity(controlling.entity)
@@ -408,6 +422,7 @@ pub fn PutBallBackInPlay_execute<T>(
     mut ball: Query<(&Ball, PhysicalQueryMut, &BoundingCircle), Without<SoccerPlayer>>,
     opponent_goal: Query<GoalQuery, Without<T>>,
     opponents: Query<(PhysicalQuery, &BoundingCircle), (With<SoccerPlayer>, Without<T>)>,
+    partition: Res<CellSpacePartition>,
 ) where
     T: TeamColorMarker,
 {
@@ -425,7 +440,11 @@ pub fn PutBallBackInPlay_execute<T>(
             params,
             (entity, physical.transform),
             teammates.iter(),
-            || opponents.iter(),
+            |position, radius| {
+                partition
+                    .query(position, radius)
+                    .filter_map(|opponent| opponents.get(opponent).ok())
+            },
             &opponent_goal,
             (
                 &ball_physical.physical,
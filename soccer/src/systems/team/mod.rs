@@ -4,12 +4,14 @@ pub mod field_player;
 pub mod goal_keeper;
 
 use bevy::prelude::*;
+use game_ai_core::random::Random;
 
 use crate::components::ball::*;
 use crate::components::goal::*;
 use crate::components::physics::*;
 use crate::components::team::*;
 use crate::game::team::*;
+use crate::resources::formation::*;
 use crate::resources::pitch::*;
 use crate::resources::*;
 use crate::util::*;
@@ -136,15 +138,13 @@ pub fn Defending_enter<T>(
     if let Some(team) = teams.optional_single() {
         info!("{:?} team defending", team.color.team_color());
 
-        let home_regions = match team.color.team_color() {
-            TeamColor::Red => RED_TEAM_DEFENDING_HOME_REGIONS,
-            TeamColor::Blue => BLUE_TEAM_DEFENDING_HOME_REGIONS,
-        };
-
         let mut goal_keeper = goal_keeper.single_mut();
 
-        team.team
-            .reset_player_home_regions(&mut field_players, &mut goal_keeper, home_regions);
+        team.team.update_player_home_regions(
+            &mut field_players,
+            &mut goal_keeper,
+            FormationState::Defending,
+        );
 
         team.team
             .update_targets_of_waiting_players(&pitch, &mut field_players);
@@ -177,15 +177,13 @@ pub fn Attacking_enter<T>(
     if let Some(team) = teams.optional_single() {
         info!("{:?} team attacking", team.color.team_color());
 
-        let home_regions = match team.color.team_color() {
-            TeamColor::Red => RED_TEAM_ATTACKING_HOME_REGIONS,
-            TeamColor::Blue => BLUE_TEAM_ATTACKING_HOME_REGIONS,
-        };
-
         let mut goal_keeper = goal_keeper.single_mut();
 
-        team.team
-            .reset_player_home_regions(&mut field_players, &mut goal_keeper, home_regions);
+        team.team.update_player_home_regions(
+            &mut field_players,
+            &mut goal_keeper,
+            FormationState::Attacking,
+        );
 
         team.team
             .update_targets_of_waiting_players(&pitch, &mut field_players);
@@ -196,6 +194,7 @@ pub fn Attacking_execute<T>(
     mut commands: Commands,
     params_asset: Res<SimulationParamsAsset>,
     params_assets: ResMut<Assets<SimulationParams>>,
+    mut rng: ResMut<Random>,
     mut teams: Query<
         (Entity, SoccerTeamQueryMut<T>, &mut SupportSpotCalculator),
         With<SoccerTeamStateAttackingExecute>,
@@ -208,6 +207,8 @@ pub fn Attacking_execute<T>(
 ) where
     T: TeamColorMarker,
 {
+    let rng = rng.stream(("Attacking_execute", std::any::type_name::<T>()));
+
     if let Some((entity, mut team, mut support_calculator)) = teams.optional_single_mut() {
         let params = params_assets.get(&params_asset.handle).unwrap();
 
@@ -217,6 +218,7 @@ pub fn Attacking_execute<T>(
         if let Some(controller_transform) = controller.optional_single() {
             team.team.determine_best_supporting_position(
                 params,
+                rng,
                 team.color,
                 &mut support_calculator,
                 || opponents.iter(),
@@ -1,26 +1,23 @@
 mod bundles;
 mod components;
+mod resources;
 mod states;
 mod systems;
 
 use bevy::log::LogPlugin;
 use bevy::prelude::*;
 
+use components::state::StateMachineStage;
+use resources::StateTestLog;
 use states::GameState;
 
 fn setup(mut _commands: Commands) {}
 
-#[bevy_main]
-fn main() {
-    let mut app = App::new();
-
-    // basic bevy
-    app.insert_resource(bevy::log::LogSettings {
-        level: bevy::log::Level::INFO,
-        ..Default::default()
-    })
-    .add_plugins(MinimalPlugins)
-    .add_plugin(LogPlugin);
+// everything but the basic bevy plugins,
+// so that the tests can drive the app themselves
+fn setup_app(app: &mut App) {
+    // resources
+    app.init_resource::<StateTestLog>();
 
     // plugins
     app.add_plugin(components::state_test::TestStateMachinePlugin);
@@ -31,38 +28,40 @@ fn main() {
     // main setup
     app.add_startup_system(setup);
 
-    // add game states to state machine stages
-    /*app.add_state_to_stage(components::state::StateMachineStage::Enter, GameState::Main)
-    .add_state_to_stage(components::state::StateMachineStage::Exit, GameState::Main);*/
-
     app.add_system_set(SystemSet::on_enter(GameState::Main).with_system(states::main::setup))
-        /*.add_system_set_to_stage(
-            components::state::StateMachineStage::Enter,
-            SystemSet::on_update(GameState::Main)
+        .add_system_set_to_stage(
+            StateMachineStage::Exit,
+            SystemSet::new()
+                .with_run_criteria(states::in_main_state)
                 .with_system(
-                    systems::state_test::state_test_idle_enter.label(systems::Systems::StateEnter),
+                    systems::state_test::state_test_idle_exit.label(systems::Systems::StateExit),
                 )
                 .with_system(
-                    systems::state_test::state_test_walk_enter.label(systems::Systems::StateEnter),
+                    systems::state_test::state_test_walk_exit.label(systems::Systems::StateExit),
+                )
+                .with_system(
+                    systems::state_test::state_test_run_exit.label(systems::Systems::StateExit),
                 ),
         )
         .add_system_set_to_stage(
-            components::state::StateMachineStage::Exit,
-            SystemSet::on_update(GameState::Main).with_system(
-                systems::state_test::state_test_run_exit.label(systems::Systems::StateExit),
-            ),
-        )*/
-        .add_system_set(
-            SystemSet::on_update(GameState::Main)
+            StateMachineStage::Enter,
+            SystemSet::new()
+                .with_run_criteria(states::in_main_state)
                 .with_system(
                     systems::state_test::state_test_idle_enter.label(systems::Systems::StateEnter),
                 )
                 .with_system(
-                    systems::state_test::state_test_idle_execute
-                        .label(systems::Systems::StateExecute),
+                    systems::state_test::state_test_walk_enter.label(systems::Systems::StateEnter),
                 )
                 .with_system(
-                    systems::state_test::state_test_walk_enter.label(systems::Systems::StateEnter),
+                    systems::state_test::state_test_run_enter.label(systems::Systems::StateEnter),
+                ),
+        )
+        .add_system_set(
+            SystemSet::on_update(GameState::Main)
+                .with_system(
+                    systems::state_test::state_test_idle_execute
+                        .label(systems::Systems::StateExecute),
                 )
                 .with_system(
                     systems::state_test::state_test_walk_execute
@@ -72,9 +71,6 @@ fn main() {
                     systems::state_test::state_test_run_execute
                         .label(systems::Systems::StateExecute),
                 )
-                .with_system(
-                    systems::state_test::state_test_run_exit.label(systems::Systems::StateExit),
-                )
                 // end of frame marker
                 .with_system(
                     systems::end_of_frame
@@ -83,6 +79,77 @@ fn main() {
                 ),
         )
         .add_system_set(SystemSet::on_exit(GameState::Main).with_system(states::main::teardown));
+}
+
+#[bevy_main]
+fn main() {
+    let mut app = App::new();
+
+    // basic bevy
+    app.insert_resource(bevy::log::LogSettings {
+        level: bevy::log::Level::INFO,
+        ..Default::default()
+    })
+    .add_plugins(MinimalPlugins)
+    .add_plugin(LogPlugin);
+
+    setup_app(&mut app);
 
     app.run();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use components::state_test::TestState;
+    use resources::StateTestStep;
+
+    #[test]
+    fn test_single_frame_transitions() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+
+        setup_app(&mut app);
+
+        // end_of_frame asserts that each transition completes within the frame
+        const FRAMES: usize = 10;
+        for _ in 0..FRAMES {
+            app.update();
+        }
+
+        let log = app.world.resource::<StateTestLog>();
+        let count =
+            |f: fn(&StateTestStep) -> bool| log.history.iter().filter(|step| f(step)).count();
+
+        // the state machine is spawned in the first frame
+        // and enters and executes its starting state in the second.
+        // after that it should transition every frame, rather than every third frame
+        let transitions = FRAMES - 2;
+        assert_eq!(log.transitions, transitions);
+        assert_eq!(
+            count(|step| matches!(step, StateTestStep::Exit(_))),
+            transitions
+        );
+        assert_eq!(
+            count(|step| matches!(step, StateTestStep::Enter(_))),
+            transitions + 1
+        );
+        assert_eq!(
+            count(|step| matches!(step, StateTestStep::Execute(_))),
+            transitions + 1
+        );
+
+        assert_eq!(
+            &log.history[..6],
+            &[
+                StateTestStep::Enter(TestState::Idle),
+                StateTestStep::Execute(TestState::Idle),
+                StateTestStep::Exit(TestState::Idle),
+                StateTestStep::Enter(TestState::Walk),
+                StateTestStep::Execute(TestState::Walk),
+                StateTestStep::Exit(TestState::Walk),
+            ]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use crate::components::state_test::TestState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateTestStep {
+    Enter(TestState),
+    Execute(TestState),
+    Exit(TestState),
+}
+
+// the state machine steps taken this frame
+#[derive(Debug, Default)]
+pub struct StateTestLog {
+    pub steps: Vec<StateTestStep>,
+
+    // every state machine step taken since startup
+    pub history: Vec<StateTestStep>,
+
+    // total number of completed state transitions
+    pub transitions: usize,
+}
+
+impl StateTestLog {
+    pub fn record(&mut self, step: StateTestStep) {
+        self.steps.push(step);
+        self.history.push(step);
+    }
+}
@@ -2,6 +2,7 @@ pub mod state_test;
 
 use bevy::prelude::*;
 
+use crate::resources::*;
 use crate::states::GameState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
@@ -13,6 +14,32 @@ pub enum Systems {
     EndOfFrame,
 }
 
-pub fn end_of_frame(state: Res<State<GameState>>) {
+pub fn end_of_frame(state: Res<State<GameState>>, mut log: ResMut<StateTestLog>) {
     info!("{:?}: **mark**", state.current());
+
+    let steps = std::mem::take(&mut log.steps);
+
+    // every state exited this frame should have been
+    // followed by entering and executing the next state in the same frame
+    for (idx, step) in steps.iter().enumerate() {
+        if let StateTestStep::Exit(exited) = step {
+            let remaining = &steps[idx + 1..];
+
+            let entered = remaining
+                .iter()
+                .find_map(|step| match step {
+                    StateTestStep::Enter(state) => Some(*state),
+                    _ => None,
+                })
+                .unwrap_or_else(|| panic!("exited {:?} without entering a new state", exited));
+
+            assert!(
+                remaining.contains(&StateTestStep::Execute(entered)),
+                "entered {:?} without executing it",
+                entered
+            );
+
+            log.transitions += 1;
+        }
+    }
 }
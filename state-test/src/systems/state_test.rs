@@ -1,51 +1,85 @@
 use bevy::prelude::*;
 
 use crate::components::state_test::*;
+use crate::resources::*;
 
-pub fn state_test_idle_enter(mut query: Query<&TestStateIdleEnter>) {
-    for _ in query.iter_mut() {
+pub fn state_test_idle_enter(mut log: ResMut<StateTestLog>, query: Query<&TestStateIdleEnter>) {
+    for _ in query.iter() {
         info!("just gonna take a quick rest ...");
+        log.record(StateTestStep::Enter(TestState::Idle));
     }
 }
 
 pub fn state_test_idle_execute(
     mut commands: Commands,
+    mut log: ResMut<StateTestLog>,
     mut query: Query<(Entity, &mut TestStateMachine), With<TestStateIdleExecute>>,
 ) {
     for (entity, mut machine) in query.iter_mut() {
         info!("standing around ...");
+        log.record(StateTestStep::Execute(TestState::Idle));
+
         machine.change_state(&mut commands, entity, TestState::Walk);
     }
 }
 
-pub fn state_test_walk_enter(mut query: Query<&TestStateWalkEnter>) {
-    for _ in query.iter_mut() {
+pub fn state_test_idle_exit(mut log: ResMut<StateTestLog>, query: Query<&TestStateIdleExit>) {
+    for _ in query.iter() {
+        info!("enough resting");
+        log.record(StateTestStep::Exit(TestState::Idle));
+    }
+}
+
+pub fn state_test_walk_enter(mut log: ResMut<StateTestLog>, query: Query<&TestStateWalkEnter>) {
+    for _ in query.iter() {
         info!("best be on my way!");
+        log.record(StateTestStep::Enter(TestState::Walk));
     }
 }
 
 pub fn state_test_walk_execute(
     mut commands: Commands,
+    mut log: ResMut<StateTestLog>,
     mut query: Query<(Entity, &mut TestStateMachine), With<TestStateWalkExecute>>,
 ) {
     for (entity, mut machine) in query.iter_mut() {
         info!("what a nice day!");
+        log.record(StateTestStep::Execute(TestState::Walk));
+
         machine.change_state(&mut commands, entity, TestState::Run);
     }
 }
 
+pub fn state_test_walk_exit(mut log: ResMut<StateTestLog>, query: Query<&TestStateWalkExit>) {
+    for _ in query.iter() {
+        info!("time to pick up the pace");
+        log.record(StateTestStep::Exit(TestState::Walk));
+    }
+}
+
+pub fn state_test_run_enter(mut log: ResMut<StateTestLog>, query: Query<&TestStateRunEnter>) {
+    for _ in query.iter() {
+        info!("here we go!");
+        log.record(StateTestStep::Enter(TestState::Run));
+    }
+}
+
 pub fn state_test_run_execute(
     mut commands: Commands,
+    mut log: ResMut<StateTestLog>,
     mut query: Query<(Entity, &mut TestStateMachine), With<TestStateRunExecute>>,
 ) {
     for (entity, mut machine) in query.iter_mut() {
         info!("moving fast now!");
+        log.record(StateTestStep::Execute(TestState::Run));
+
         machine.change_state(&mut commands, entity, TestState::Idle);
     }
 }
 
-pub fn state_test_run_exit(mut query: Query<&TestStateRunExit>) {
-    for _ in query.iter_mut() {
+pub fn state_test_run_exit(mut log: ResMut<StateTestLog>, query: Query<&TestStateRunExit>) {
+    for _ in query.iter() {
         info!("phew, I'm tired!");
+        log.record(StateTestStep::Exit(TestState::Run));
     }
 }
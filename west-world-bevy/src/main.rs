@@ -10,12 +10,17 @@ mod systems;
 use bevy::log::LogPlugin;
 use bevy::prelude::*;
 use bevy::time::FixedTimestep;
+use game_ai_core::args::parse_seed;
+use game_ai_core::random::Random;
 
+use components::state::StateMachineStage;
 use events::messaging::MessageEvent;
 use states::GameState;
 use systems::Systems;
 
-fn setup(mut _commands: Commands) {}
+fn setup(mut _commands: Commands, random: Res<Random>) {
+    info!("random seed: {}", random.seed());
+}
 
 #[bevy_main]
 fn main() {
@@ -29,6 +34,9 @@ fn main() {
     .add_plugins(MinimalPlugins)
     .add_plugin(LogPlugin);
 
+    // seedable rng
+    app.insert_resource(parse_seed().map(Random::new).unwrap_or_default());
+
     // plugins
     app.add_plugin(components::miner::MinerStateMachinePlugin)
         .add_plugin(components::wife::WifeStateMachinePlugin);
@@ -89,40 +97,54 @@ fn main() {
                         .after(Systems::GlobalStateExecute),
                 ),
         )
-        .add_system_set(
-            // per-frame systems
-            SystemSet::on_update(GameState::Main)
+        .add_system_set_to_stage(
+            StateMachineStage::Exit,
+            SystemSet::new()
+                .with_run_criteria(states::in_main_state)
                 // miner systems
                 .with_system(
-                    systems::miner::EnterMineAndDigForNugget_enter.label(Systems::StateEnter),
+                    systems::miner::EnterMineAndDigForNugget_exit.label(Systems::StateExit),
                 )
+                .with_system(systems::miner::VisitBankAndDepositGold_exit.label(Systems::StateExit))
+                .with_system(systems::miner::GoHomeAndSleepTilRested_exit.label(Systems::StateExit))
+                .with_system(systems::miner::QuenchThirst_exit.label(Systems::StateExit))
+                .with_system(systems::miner::EatStew_exit.label(Systems::StateExit))
+                // wife systems
+                .with_system(systems::wife::VisitBathroom_exit.label(Systems::StateExit)),
+        )
+        .add_system_set_to_stage(
+            StateMachineStage::Enter,
+            SystemSet::new()
+                .with_run_criteria(states::in_main_state)
+                // miner systems
                 .with_system(
-                    systems::miner::EnterMineAndDigForNugget_exit.label(Systems::StateExit),
+                    systems::miner::EnterMineAndDigForNugget_enter.label(Systems::StateEnter),
                 )
                 .with_system(
                     systems::miner::VisitBankAndDepositGold_enter.label(Systems::StateEnter),
                 )
-                .with_system(systems::miner::VisitBankAndDepositGold_exit.label(Systems::StateExit))
                 .with_system(
                     systems::miner::GoHomeAndSleepTilRested_enter.label(Systems::StateEnter),
                 )
-                .with_system(systems::miner::GoHomeAndSleepTilRested_exit.label(Systems::StateExit))
+                .with_system(systems::miner::QuenchThirst_enter.label(Systems::StateEnter))
+                .with_system(systems::miner::EatStew_enter.label(Systems::StateEnter))
+                // wife systems
+                .with_system(systems::wife::VisitBathroom_enter.label(Systems::StateEnter))
+                .with_system(systems::wife::CookStew_enter.label(Systems::StateEnter)),
+        )
+        .add_system_set(
+            // per-frame systems
+            SystemSet::on_update(GameState::Main)
+                // miner systems
                 .with_system(
                     systems::miner::GoHomeAndSleepTilRested_on_message
                         .label(Systems::StateOnMessage)
                         .after(Systems::GlobalStateOnMessage),
                 )
-                .with_system(systems::miner::QuenchThirst_enter.label(Systems::StateEnter))
-                .with_system(systems::miner::QuenchThirst_exit.label(Systems::StateExit))
-                .with_system(systems::miner::EatStew_enter.label(Systems::StateEnter))
-                .with_system(systems::miner::EatStew_exit.label(Systems::StateExit))
                 // wife systems
                 .with_system(
                     systems::wife::GlobalState_on_message.label(Systems::GlobalStateOnMessage),
                 )
-                .with_system(systems::wife::VisitBathroom_enter.label(Systems::StateEnter))
-                .with_system(systems::wife::VisitBathroom_exit.label(Systems::StateExit))
-                .with_system(systems::wife::CookStew_enter.label(Systems::StateEnter))
                 .with_system(
                     systems::wife::CookStew_on_message
                         .label(Systems::StateOnMessage)
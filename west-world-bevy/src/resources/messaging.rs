@@ -10,6 +10,9 @@ use crate::events::messaging::MessageEvent;
 struct Telegram {
     dispatch_time: i64,
 
+    // dispatch order, breaks ties between telegrams with the same dispatch time
+    sequence: u64,
+
     pub receiver: Entity,
 
     pub message: MessageEvent,
@@ -19,9 +22,7 @@ impl Eq for Telegram {}
 
 impl PartialEq for Telegram {
     fn eq(&self, other: &Self) -> bool {
-        self.dispatch_time == other.dispatch_time
-            && self.receiver == other.receiver
-            && self.message == other.message
+        self.dispatch_time == other.dispatch_time && self.sequence == other.sequence
     }
 }
 
@@ -33,16 +34,21 @@ impl PartialOrd for Telegram {
 
 impl Ord for Telegram {
     fn cmp(&self, other: &Self) -> Ordering {
-        // TODO: this may need to be reversed
-        // so that we get a time-ordered min-heap?
-        self.dispatch_time.cmp(&other.dispatch_time)
+        // BinaryHeap is a max-heap, so this is reversed
+        // to pop the earliest telegram first,
+        // and the first dispatched of those with the same dispatch time
+        other
+            .dispatch_time
+            .cmp(&self.dispatch_time)
+            .then_with(|| other.sequence.cmp(&self.sequence))
     }
 }
 
 impl Telegram {
-    fn new(dispatch_time: i64, receiver: Entity, message: MessageEvent) -> Self {
+    fn new(dispatch_time: i64, sequence: u64, receiver: Entity, message: MessageEvent) -> Self {
         Self {
             dispatch_time,
+            sequence,
             receiver,
             message,
         }
@@ -52,6 +58,7 @@ impl Telegram {
 #[derive(Default)]
 pub struct MessageDispatcher {
     queue: BinaryHeap<Telegram>,
+    next_sequence: u64,
 }
 
 impl MessageDispatcher {
@@ -61,20 +68,19 @@ impl MessageDispatcher {
     ) {
         let now = Utc::now().timestamp_millis();
 
-        loop {
-            if let Some(telegram) = self.queue.peek() {
-                if telegram.dispatch_time > now {
-                    return;
-                }
-            } else {
-                return;
-            }
-
-            let telegram = self.queue.pop().unwrap();
+        while let Some(telegram) = self.pop_due(now) {
             self.discharge(telegram, message_events);
         }
     }
 
+    fn pop_due(&mut self, now: i64) -> Option<Telegram> {
+        if self.queue.peek()?.dispatch_time > now {
+            return None;
+        }
+
+        self.queue.pop()
+    }
+
     fn discharge(
         &self,
         telegram: Telegram,
@@ -96,8 +102,50 @@ impl MessageDispatcher {
         delay_seconds: f64,
     ) {
         let now = Utc::now().timestamp_millis();
-        let telegram = Telegram::new(now + (delay_seconds * 1000.0) as i64, receiver, message);
+        self.push(now + (delay_seconds * 1000.0) as i64, receiver, message);
+    }
+
+    fn push(&mut self, dispatch_time: i64, receiver: Entity, message: MessageEvent) {
+        let telegram = Telegram::new(dispatch_time, self.next_sequence, receiver, message);
+        self.next_sequence += 1;
 
         self.queue.push(telegram);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receivers(dispatcher: &mut MessageDispatcher, now: i64) -> Vec<u32> {
+        std::iter::from_fn(|| dispatcher.pop_due(now))
+            .map(|telegram| telegram.receiver.id())
+            .collect()
+    }
+
+    #[test]
+    fn test_pops_earliest_first() {
+        let mut dispatcher = MessageDispatcher::default();
+
+        // the receiver id stands in for the dispatch order
+        for (dispatch_time, id) in [(300, 3), (100, 1), (200, 2)] {
+            let receiver = Entity::from_raw(id);
+            dispatcher.push(dispatch_time, receiver, MessageEvent::StewIsReady(receiver));
+        }
+
+        assert_eq!(receivers(&mut dispatcher, 150), vec![1]);
+        assert_eq!(receivers(&mut dispatcher, 300), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_same_time_is_fifo() {
+        let mut dispatcher = MessageDispatcher::default();
+
+        for id in 0..10 {
+            let receiver = Entity::from_raw(id);
+            dispatcher.push(100, receiver, MessageEvent::HiHoneyImHome(receiver));
+        }
+
+        assert_eq!(receivers(&mut dispatcher, 100), (0..10).collect::<Vec<_>>());
+    }
+}
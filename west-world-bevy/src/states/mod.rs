@@ -1,6 +1,19 @@
 pub mod main;
 
+use bevy::ecs::schedule::ShouldRun;
+use bevy::prelude::*;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum GameState {
     Main,
 }
+
+// run criteria for the state machine stages,
+// which don't have the game state driver
+pub fn in_main_state(state: Res<State<GameState>>) -> ShouldRun {
+    if state.current() == &GameState::Main {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
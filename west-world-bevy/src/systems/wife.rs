@@ -2,6 +2,7 @@
 
 use bevy::prelude::*;
 use chrono::prelude::*;
+use game_ai_core::random::Random;
 use rand::Rng;
 
 use crate::components::wife::*;
@@ -9,8 +10,12 @@ use crate::events::messaging::MessageEvent;
 use crate::game::wife::*;
 use crate::resources::messaging::MessageDispatcher;
 
-pub fn GlobalState_execute(mut commands: Commands, mut query: Query<(Entity, WifeQuery)>) {
-    let mut rng = rand::thread_rng();
+pub fn GlobalState_execute(
+    mut commands: Commands,
+    mut rng: ResMut<Random>,
+    mut query: Query<(Entity, WifeQuery)>,
+) {
+    let rng = rng.stream("GlobalState_execute");
 
     for (entity, mut wife) in query.iter_mut() {
         debug!("executing wife global state for {}", wife.name.as_ref());
@@ -48,8 +53,11 @@ pub fn GlobalState_on_message(
     }
 }
 
-pub fn DoHouseWork_execute(query: Query<WifeQuery, With<WifeStateDoHouseWorkExecute>>) {
-    let mut rng = rand::thread_rng();
+pub fn DoHouseWork_execute(
+    mut rng: ResMut<Random>,
+    query: Query<WifeQuery, With<WifeStateDoHouseWorkExecute>>,
+) {
+    let rng = rng.stream("DoHouseWork_execute");
 
     for wife in query.iter() {
         match rng.gen_range(0..=2) {
@@ -0,0 +1,58 @@
+#[cfg(test)]
+use std::cell::Cell;
+use std::thread;
+use std::time::Duration;
+
+use chrono::prelude::*;
+
+pub trait Clock {
+    // current time in milliseconds
+    fn now(&self) -> i64;
+
+    // block (or pretend to) until duration has passed
+    fn sleep(&self, duration: Duration);
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis(self.now())
+    }
+}
+
+// wall-clock time
+#[derive(Debug, Default)]
+pub struct RealTimeClock;
+
+impl Clock for RealTimeClock {
+    fn now(&self) -> i64 {
+        Utc::now().timestamp_millis()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+// manually stepped time,
+// sleeping just advances the clock without blocking
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct SimulatedClock {
+    now: Cell<i64>,
+}
+
+#[cfg(test)]
+impl SimulatedClock {
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration.as_millis() as i64);
+    }
+}
+
+#[cfg(test)]
+impl Clock for SimulatedClock {
+    fn now(&self) -> i64 {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -1,3 +1,4 @@
+mod clock;
 mod entity;
 mod location;
 mod messaging;
@@ -7,12 +8,15 @@ mod wife;
 
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::thread;
 use std::time::Duration;
 
-use tracing::Level;
+use game_ai_core::args::parse_seed;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
+use clock::{Clock, RealTimeClock};
 use messaging::MessageDispatcher;
 use miner::Miner;
 use wife::Wife;
@@ -27,30 +31,135 @@ fn init_logging() -> anyhow::Result<()> {
     Ok(())
 }
 
+const UPDATE_RATE: Duration = Duration::from_millis(800);
+
+struct World {
+    clock: Rc<dyn Clock>,
+    dispatcher: Rc<RefCell<MessageDispatcher>>,
+
+    bob: Rc<RefCell<Miner>>,
+    elsa: Rc<RefCell<Wife>>,
+}
+
+impl World {
+    fn new(clock: Rc<dyn Clock>, rng: StdRng) -> Self {
+        let rng = Rc::new(RefCell::new(rng));
+
+        let dispatcher = Rc::new(RefCell::new(MessageDispatcher::new(clock.clone())));
+
+        let bob = Rc::new(RefCell::new(Miner::new("Miner Bob", dispatcher.clone())));
+        dispatcher
+            .borrow()
+            .register_message_receiver(bob.borrow().entity(), bob.clone());
+
+        let elsa = Rc::new(RefCell::new(Wife::new("Elsa", dispatcher.clone(), rng)));
+        dispatcher
+            .borrow()
+            .register_message_receiver(elsa.borrow().entity(), elsa.clone());
+
+        bob.borrow_mut().set_wife_id(elsa.borrow().entity().id());
+        elsa.borrow_mut().set_miner_id(bob.borrow().entity().id());
+
+        Self {
+            clock,
+            dispatcher,
+            bob,
+            elsa,
+        }
+    }
+
+    fn update(&self) {
+        self.bob.borrow_mut().update();
+        self.elsa.borrow_mut().update();
+
+        self.dispatcher.borrow().dispatch_deferred_messages();
+
+        self.clock.sleep(UPDATE_RATE);
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     init_logging()?;
 
-    let dispatcher = Rc::new(RefCell::new(MessageDispatcher::default()));
+    let seed = parse_seed().unwrap_or_else(|| rand::thread_rng().gen());
+    info!("random seed: {}", seed);
 
-    let bob = Rc::new(RefCell::new(Miner::new("Miner Bob", dispatcher.clone())));
-    dispatcher
-        .borrow()
-        .register_message_receiver(bob.borrow().entity(), bob.clone());
+    let world = World::new(Rc::new(RealTimeClock), StdRng::seed_from_u64(seed));
 
-    let elsa = Rc::new(RefCell::new(Wife::new("Elsa", dispatcher.clone())));
-    dispatcher
-        .borrow()
-        .register_message_receiver(elsa.borrow().entity(), elsa.clone());
+    loop {
+        world.update();
+    }
+}
 
-    bob.borrow_mut().set_wife_id(elsa.borrow().entity().id());
-    elsa.borrow_mut().set_miner_id(bob.borrow().entity().id());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    loop {
-        bob.borrow_mut().update();
-        elsa.borrow_mut().update();
+    use clock::SimulatedClock;
+    use location::Location;
+    use miner::MinerState;
+    use wife::WifeState;
+
+    #[test]
+    fn test_simulated_day() {
+        let clock = Rc::new(SimulatedClock::default());
+        let world = World::new(clock.clone(), StdRng::seed_from_u64(0));
+
+        let mut miner_states = vec![world.bob.borrow().state()];
+        let mut wife_states = vec![world.elsa.borrow().state()];
+        let mut locations = vec![world.bob.borrow().location()];
+        let mut max_gold_carried = 0;
+        let mut max_wealth = 0;
+
+        // a day is plenty of time for bob to dig, bank, and come home for stew
+        let updates = 24 * 60 * 60 * 1000 / UPDATE_RATE.as_millis() as i64;
+        for _ in 0..updates {
+            world.update();
+
+            let bob = world.bob.borrow();
+            if miner_states.last() != Some(&bob.state()) {
+                miner_states.push(bob.state());
+            }
+            if locations.last() != Some(&bob.location()) {
+                locations.push(bob.location());
+            }
+            max_gold_carried = max_gold_carried.max(bob.gold_carried());
+            max_wealth = max_wealth.max(bob.wealth());
+
+            let elsa = world.elsa.borrow();
+            if wife_states.last() != Some(&elsa.state()) {
+                wife_states.push(elsa.state());
+            }
+        }
+
+        assert_eq!(clock.now(), updates * UPDATE_RATE.as_millis() as i64);
+
+        // bob starts at home and works the mine -> bank loop
+        assert_eq!(miner_states[0], MinerState::GoHomeAndSleepTilRested);
+        assert_eq!(locations[0], Location::Shack);
+        assert!(miner_states.windows(2).any(|w| w
+            == [
+                MinerState::EnterMineAndDigForNugget,
+                MinerState::VisitBankAndDepositGold
+            ]));
+        assert!(locations
+            .windows(2)
+            .any(|w| w == [Location::GoldMine, Location::Bank]));
 
-        dispatcher.borrow().dispatch_deferred_messages();
+        // pockets fill before banking and he gets rich enough to go home
+        assert!(max_gold_carried >= 3);
+        assert!(max_wealth >= 5);
+        assert!(miner_states.windows(2).any(|w| w
+            == [
+                MinerState::VisitBankAndDepositGold,
+                MinerState::GoHomeAndSleepTilRested
+            ]));
+        assert!(miner_states.contains(&MinerState::QuenchThirst));
 
-        thread::sleep(Duration::from_millis(800));
+        // coming home gets elsa cooking and bob eating
+        assert!(wife_states
+            .windows(2)
+            .any(|w| w == [WifeState::CookStew, WifeState::DoHouseWork]));
+        assert!(miner_states.contains(&MinerState::EatStew));
     }
 }
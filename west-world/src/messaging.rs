@@ -1,11 +1,11 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 use std::rc::Rc;
 
-use chrono::prelude::*;
 use tracing::debug;
 
+use crate::clock::Clock;
 use crate::entity::{Entity, EntityId};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -22,6 +22,9 @@ pub trait MessageReceiver {
 struct Telegram {
     dispatch_time: i64,
 
+    // dispatch order, breaks ties between telegrams with the same dispatch time
+    sequence: u64,
+
     sender: EntityId,
     receiver: EntityId,
 
@@ -32,10 +35,7 @@ impl Eq for Telegram {}
 
 impl PartialEq for Telegram {
     fn eq(&self, other: &Self) -> bool {
-        self.dispatch_time == other.dispatch_time
-            && self.sender == other.sender
-            && self.receiver == other.receiver
-            && self.message == other.message
+        self.dispatch_time == other.dispatch_time && self.sequence == other.sequence
     }
 }
 
@@ -47,16 +47,27 @@ impl PartialOrd for Telegram {
 
 impl Ord for Telegram {
     fn cmp(&self, other: &Self) -> Ordering {
-        // TODO: this may need to be reversed
-        // so that we get a time-ordered min-heap?
-        self.dispatch_time.cmp(&other.dispatch_time)
+        // BinaryHeap is a max-heap, so this is reversed
+        // to pop the earliest telegram first,
+        // and the first dispatched of those with the same dispatch time
+        other
+            .dispatch_time
+            .cmp(&self.dispatch_time)
+            .then_with(|| other.sequence.cmp(&self.sequence))
     }
 }
 
 impl Telegram {
-    fn new(dispatch_time: i64, sender: EntityId, receiver: EntityId, message: Message) -> Self {
+    fn new(
+        dispatch_time: i64,
+        sequence: u64,
+        sender: EntityId,
+        receiver: EntityId,
+        message: Message,
+    ) -> Self {
         Self {
             dispatch_time,
+            sequence,
             sender,
             receiver,
             message,
@@ -64,14 +75,29 @@ impl Telegram {
     }
 }
 
-#[derive(Default)]
 pub struct MessageDispatcher {
+    clock: Rc<dyn Clock>,
+
     receivers: RefCell<HashMap<EntityId, Rc<RefCell<dyn MessageReceiver>>>>,
 
     queue: RefCell<BinaryHeap<Telegram>>,
+    next_sequence: Cell<u64>,
 }
 
 impl MessageDispatcher {
+    pub fn new(clock: Rc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            receivers: RefCell::default(),
+            queue: RefCell::default(),
+            next_sequence: Cell::default(),
+        }
+    }
+
+    pub fn clock(&self) -> &Rc<dyn Clock> {
+        &self.clock
+    }
+
     pub fn register_message_receiver(
         &self,
         entity: &Entity,
@@ -81,24 +107,24 @@ impl MessageDispatcher {
     }
 
     pub fn dispatch_deferred_messages(&self) {
-        let now = Utc::now().timestamp_millis();
+        let now = self.clock.now();
 
         debug!("now: {}, queue: {:?}", now, self.queue.borrow());
 
-        loop {
-            if let Some(telegram) = self.queue.borrow().peek() {
-                if telegram.dispatch_time > now {
-                    return;
-                }
-            } else {
-                return;
-            }
-
-            let telegram = self.queue.borrow_mut().pop().unwrap();
+        while let Some(telegram) = self.pop_due(now) {
             self.discharge(telegram);
         }
     }
 
+    fn pop_due(&self, now: i64) -> Option<Telegram> {
+        let mut queue = self.queue.borrow_mut();
+        if queue.peek()?.dispatch_time > now {
+            return None;
+        }
+
+        queue.pop()
+    }
+
     fn discharge(&self, telegram: Telegram) {
         if let Some(receiver) = self.receivers.borrow_mut().get_mut(&telegram.receiver) {
             receiver
@@ -120,9 +146,14 @@ impl MessageDispatcher {
         message: Message,
         delay_seconds: f64,
     ) {
-        let now = Utc::now().timestamp_millis();
+        let now = self.clock.now();
+
+        let sequence = self.next_sequence.get();
+        self.next_sequence.set(sequence + 1);
+
         let telegram = Telegram::new(
             now + (delay_seconds * 1000.0) as i64,
+            sequence,
             sender,
             receiver,
             message,
@@ -131,3 +162,99 @@ impl MessageDispatcher {
         self.queue.borrow_mut().push(telegram);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    use crate::clock::SimulatedClock;
+
+    #[derive(Default)]
+    struct Recorder {
+        messages: Vec<(EntityId, Message)>,
+    }
+
+    impl MessageReceiver for Recorder {
+        fn receive_message(&mut self, sender: EntityId, message: Message) {
+            self.messages.push((sender, message));
+        }
+    }
+
+    fn setup() -> (
+        Rc<SimulatedClock>,
+        MessageDispatcher,
+        Entity,
+        Rc<RefCell<Recorder>>,
+    ) {
+        let clock = Rc::new(SimulatedClock::default());
+        let dispatcher = MessageDispatcher::new(clock.clone());
+
+        let entity = Entity::new("Recorder");
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        dispatcher.register_message_receiver(&entity, recorder.clone());
+
+        (clock, dispatcher, entity, recorder)
+    }
+
+    fn senders(recorder: &RefCell<Recorder>) -> Vec<EntityId> {
+        recorder
+            .borrow()
+            .messages
+            .iter()
+            .map(|(sender, _)| *sender)
+            .collect()
+    }
+
+    #[test]
+    fn test_deferred_message() {
+        let (clock, dispatcher, entity, recorder) = setup();
+
+        dispatcher.defer_dispatch_message(entity.id(), entity.id(), Message::StewIsReady, 1.5);
+
+        dispatcher.dispatch_deferred_messages();
+        assert!(recorder.borrow().messages.is_empty());
+
+        clock.advance(Duration::from_millis(1499));
+        dispatcher.dispatch_deferred_messages();
+        assert!(recorder.borrow().messages.is_empty());
+
+        clock.advance(Duration::from_millis(1));
+        dispatcher.dispatch_deferred_messages();
+        assert_eq!(
+            recorder.borrow().messages,
+            vec![(entity.id(), Message::StewIsReady)]
+        );
+    }
+
+    #[test]
+    fn test_deferred_message_order() {
+        let (clock, dispatcher, entity, recorder) = setup();
+
+        // the sender id stands in for the dispatch order
+        dispatcher.defer_dispatch_message(3, entity.id(), Message::StewIsReady, 3.0);
+        dispatcher.defer_dispatch_message(1, entity.id(), Message::StewIsReady, 1.0);
+        dispatcher.defer_dispatch_message(2, entity.id(), Message::StewIsReady, 2.0);
+
+        clock.advance(Duration::from_millis(1500));
+        dispatcher.dispatch_deferred_messages();
+        assert_eq!(senders(&recorder), vec![1]);
+
+        clock.advance(Duration::from_millis(1500));
+        dispatcher.dispatch_deferred_messages();
+        assert_eq!(senders(&recorder), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_same_time_messages_are_fifo() {
+        let (_clock, dispatcher, entity, recorder) = setup();
+
+        for sender in 0..10 {
+            dispatcher.dispatch_message(sender, entity.id(), Message::HiHoneyImHome);
+        }
+
+        dispatcher.dispatch_deferred_messages();
+        assert_eq!(senders(&recorder), (0..10).collect::<Vec<_>>());
+    }
+}
@@ -3,7 +3,6 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use chrono::prelude::*;
 use tracing::info;
 
 use crate::entity::{Entity, EntityId};
@@ -17,7 +16,7 @@ const THIRST_LEVEL: i64 = 5;
 const TIREDNESS_THRESHOLD: i64 = 5;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum MinerState {
+pub enum MinerState {
     GlobalState,
 
     EnterMineAndDigForNugget,
@@ -263,7 +262,11 @@ impl MinerState {
     ) -> bool {
         match message {
             Message::StewIsReady => {
-                let now = Utc::now();
+                let now = state_machine
+                    .message_dispatcher()
+                    .borrow()
+                    .clock()
+                    .now_utc();
 
                 info!("Message handled by {} at time: {}", entity.name(), now);
                 info!("{}: Ok hun, ahm a-comin'!", entity.name());
@@ -503,6 +506,26 @@ impl Miner {
         self.components.wife_id = Some(wife_id);
     }
 
+    #[cfg(test)]
+    pub fn state(&self) -> MinerState {
+        self.state_machine.current_state
+    }
+
+    #[cfg(test)]
+    pub fn location(&self) -> Location {
+        self.components.location
+    }
+
+    #[cfg(test)]
+    pub fn gold_carried(&self) -> i64 {
+        self.components.stats.gold_carried
+    }
+
+    #[cfg(test)]
+    pub fn wealth(&self) -> i64 {
+        self.components.wealth()
+    }
+
     pub fn update(&mut self) {
         self.components.update();
 
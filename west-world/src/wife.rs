@@ -3,7 +3,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use chrono::prelude::*;
+use rand::rngs::StdRng;
 use rand::Rng;
 use tracing::info;
 
@@ -14,7 +14,7 @@ use crate::state::{State, StateMachine};
 const BATHROOM_CHANCE: f32 = 0.1;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum WifeState {
+pub enum WifeState {
     GlobalState,
 
     DoHouseWork,
@@ -94,9 +94,8 @@ impl WifeState {
         state_machine: &mut WifeStateMachine,
         wife: &mut WifeComponents,
     ) {
-        let mut rng = rand::thread_rng();
-
-        if rng.gen::<f32>() < BATHROOM_CHANCE {
+        let bathroom = state_machine.rng().borrow_mut().gen::<f32>() < BATHROOM_CHANCE;
+        if bathroom {
             state_machine.change_state(entity, Self::VisitBathroom, wife)
         }
     }
@@ -110,7 +109,11 @@ impl WifeState {
     ) -> bool {
         match message {
             Message::HiHoneyImHome => {
-                let now = Utc::now();
+                let now = state_machine
+                    .message_dispatcher()
+                    .borrow()
+                    .clock()
+                    .now_utc();
 
                 info!("Message handled by {} at time: {}", entity.name(), now);
                 info!(
@@ -132,12 +135,10 @@ impl WifeState {
 
     fn DoHouseWork_execute(
         entity: &Entity,
-        _state_machine: &mut WifeStateMachine,
+        state_machine: &mut WifeStateMachine,
         _wife: &mut WifeComponents,
     ) {
-        let mut rng = rand::thread_rng();
-
-        match rng.gen_range(0..=2) {
+        match state_machine.rng().borrow_mut().gen_range(0..=2) {
             0 => info!("{}: Moppin' the floor", entity.name()),
             1 => info!("{}: Washin' the dishes", entity.name()),
             2 => info!("{}: Makin' the bed", entity.name()),
@@ -209,7 +210,11 @@ impl WifeState {
     ) -> bool {
         match message {
             Message::StewIsReady => {
-                let now = Utc::now();
+                let now = state_machine
+                    .message_dispatcher()
+                    .borrow()
+                    .clock()
+                    .now_utc();
 
                 info!("Message received by {} at time: {}", entity.name(), now);
                 info!("{}: Stew ready! Let's eat", entity.name());
@@ -237,21 +242,27 @@ struct WifeStateMachine {
     previous_state: Option<WifeState>,
 
     message_dispatcher: Rc<RefCell<MessageDispatcher>>,
+    rng: Rc<RefCell<StdRng>>,
 }
 
 impl WifeStateMachine {
-    fn new(message_dispatcher: Rc<RefCell<MessageDispatcher>>) -> Self {
+    fn new(message_dispatcher: Rc<RefCell<MessageDispatcher>>, rng: Rc<RefCell<StdRng>>) -> Self {
         Self {
             global_state: WifeState::GlobalState,
             current_state: WifeState::DoHouseWork,
             previous_state: None,
             message_dispatcher,
+            rng,
         }
     }
 
     fn message_dispatcher(&self) -> &Rc<RefCell<MessageDispatcher>> {
         &self.message_dispatcher
     }
+
+    fn rng(&self) -> &Rc<RefCell<StdRng>> {
+        &self.rng
+    }
 }
 
 impl StateMachine<WifeComponents> for WifeStateMachine {
@@ -316,10 +327,11 @@ impl Wife {
     pub fn new(
         name: impl Into<String>,
         message_dispatcher: Rc<RefCell<MessageDispatcher>>,
+        rng: Rc<RefCell<StdRng>>,
     ) -> Self {
         Self {
             entity: Entity::new(name),
-            state_machine: WifeStateMachine::new(message_dispatcher),
+            state_machine: WifeStateMachine::new(message_dispatcher, rng),
             components: WifeComponents::default(),
         }
     }
@@ -332,6 +344,11 @@ impl Wife {
         &self.entity
     }
 
+    #[cfg(test)]
+    pub fn state(&self) -> WifeState {
+        self.state_machine.current_state
+    }
+
     pub fn update(&mut self) {
         self.components.update();
 